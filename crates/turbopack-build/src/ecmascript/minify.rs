@@ -34,12 +34,40 @@ pub async fn minify(path: Vc<FileSystemPath>, code: Vc<Code>) -> Result<Vc<Code>
     let code = code.await?;
 
     let cm = Arc::new(SwcSourceMap::new(FilePathMapping::empty()));
-    let compiler = Arc::new(Compiler::new(cm.clone()));
-    let fm = compiler.cm.new_source_file(
-        FileName::Custom(path.path.to_string()),
+    let program = minify_program(
+        cm.clone(),
+        path.path.to_string(),
         code.source_code().to_str()?.to_string(),
+    )?;
+
+    let (src, src_map_buf) = print_program(cm.clone(), program)?;
+
+    let mut builder = CodeBuilder::default();
+    builder.push_source(
+        &src.into(),
+        Some(Vc::upcast(
+            ParseResultSourceMap::new(cm, src_map_buf, original_map).cell(),
+        )),
     );
 
+    write!(
+        builder,
+        "\n\n//# sourceMappingURL={}.map",
+        urlencoding::encode(path.file_name())
+    )?;
+    Ok(builder.build().cell())
+}
+
+/// Parses `source` and runs the minifier on it.
+///
+/// Compile-time constant conditions are emitted by code generation as
+/// sequence expressions like `("TURBOPACK compile-time falsy", 0)`, which the
+/// compressor folds. Branches guarded by them, including the imports inside,
+/// are removed completely.
+fn minify_program(cm: Arc<SwcSourceMap>, path: String, source: String) -> Result<Program> {
+    let compiler = Arc::new(Compiler::new(cm.clone()));
+    let fm = compiler.cm.new_source_file(FileName::Custom(path), source);
+
     let lexer = Lexer::new(
         Syntax::default(),
         EsVersion::latest(),
@@ -48,7 +76,7 @@ pub async fn minify(path: Vc<FileSystemPath>, code: Vc<Code>) -> Result<Vc<Code>
     );
     let mut parser = Parser::new_from(lexer);
     // TODO should use our own handler that emits issues instead.
-    let program = try_with_handler(cm.clone(), Default::default(), |handler| {
+    try_with_handler(cm.clone(), Default::default(), |handler| {
         GLOBALS.set(&Default::default(), || {
             let program = match parser.parse_program() {
                 Ok(program) => program,
@@ -90,24 +118,7 @@ pub async fn minify(path: Vc<FileSystemPath>, code: Vc<Code>) -> Result<Vc<Code>
                 )))
             }))
         })
-    })?;
-
-    let (src, src_map_buf) = print_program(cm.clone(), program)?;
-
-    let mut builder = CodeBuilder::default();
-    builder.push_source(
-        &src.into(),
-        Some(Vc::upcast(
-            ParseResultSourceMap::new(cm, src_map_buf, original_map).cell(),
-        )),
-    );
-
-    write!(
-        builder,
-        "\n\n//# sourceMappingURL={}.map",
-        urlencoding::encode(path.file_name())
-    )?;
-    Ok(builder.build().cell())
+    })
 }
 
 // From https://github.com/swc-project/swc/blob/11efd4e7c5e8081f8af141099d3459c3534c1e1d/crates/swc/src/lib.rs#L523-L560
@@ -144,3 +155,30 @@ fn print_program(
 
     Ok((src, src_map_buf))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minify_source(source: &str) -> String {
+        let cm = Arc::new(SwcSourceMap::new(FilePathMapping::empty()));
+        let program =
+            minify_program(cm.clone(), "test.js".to_string(), source.to_string()).unwrap();
+        print_program(cm, program).unwrap().0
+    }
+
+    #[test]
+    fn removes_imports_in_constant_false_branches() {
+        let minified = minify_source(
+            r#"
+            if (("TURBOPACK compile-time falsy", 0)) {
+                __turbopack_require__("./dev-only.js");
+            } else {
+                __turbopack_require__("./prod.js");
+            }
+            "#,
+        );
+        assert!(!minified.contains("./dev-only.js"));
+        assert!(minified.contains("./prod.js"));
+    }
+}
@@ -57,6 +57,17 @@ pub trait ChunkingContext {
         Vc::cell(false)
     }
 
+    /// Whether references that don't request a chunking type themselves, e.g.
+    /// by an annotation, are chunked with their default chunking type.
+    /// Otherwise they aren't chunked, e.g. for server builds that load the
+    /// referenced modules by other means. See
+    /// [ChunkableModuleReference::chunking_type_for].
+    ///
+    /// [ChunkableModuleReference::chunking_type_for]: super::ChunkableModuleReference::chunking_type_for
+    fn chunk_unannotated_references(self: Vc<Self>) -> Vc<bool> {
+        Vc::cell(true)
+    }
+
     fn async_loader_chunk_item(
         &self,
         module: Vc<Box<dyn ChunkableModule>>,
@@ -85,6 +85,10 @@ impl ModuleId {
 #[turbo_tasks::value(transparent, shared)]
 pub struct ModuleIds(Vec<Vc<ModuleId>>);
 
+/// An optional module id.
+#[turbo_tasks::value(transparent, shared)]
+pub struct OptionModuleId(Option<Vc<ModuleId>>);
+
 /// A [Module] that can be converted into a [Chunk].
 #[turbo_tasks::value_trait]
 pub trait ChunkableModule: Module + Asset {
@@ -163,6 +167,9 @@ pub enum ChunkingType {
     Async,
     /// Module not placed in chunk group, but its references are still followed.
     Passthrough,
+    /// Module is neither placed in the chunk group nor are its references
+    /// followed. It's only available when another reference includes it.
+    Weak,
 }
 
 #[turbo_tasks::value(transparent)]
@@ -179,6 +186,17 @@ pub trait ChunkableModuleReference: ModuleReference + ValueToString {
     fn chunking_type(self: Vc<Self>) -> Vc<ChunkingTypeOption> {
         Vc::cell(Some(ChunkingType::default()))
     }
+
+    /// The chunking type of this reference when it's chunked by
+    /// `chunking_context`. References can consult the chunking context, e.g.
+    /// its environment, when they don't request a chunking type themselves.
+    /// Defaults to [ChunkableModuleReference::chunking_type].
+    fn chunking_type_for(
+        self: Vc<Self>,
+        _chunking_context: Vc<Box<dyn ChunkingContext>>,
+    ) -> Vc<ChunkingTypeOption> {
+        self.chunking_type()
+    }
 }
 
 type AsyncInfo = IndexMap<Vc<Box<dyn ChunkItem>>, Vec<Vc<Box<dyn ChunkItem>>>>;
@@ -314,7 +332,10 @@ async fn graph_node_to_referenced_nodes(
                 }]);
             };
 
-            let Some(chunking_type) = *chunkable_module_reference.chunking_type().await? else {
+            let Some(chunking_type) = *chunkable_module_reference
+                .chunking_type_for(chunk_content_context.chunking_context)
+                .await?
+            else {
                 return Ok(vec![ChunkGraphEdge {
                     key: None,
                     node: ChunkContentGraphNode::ExternalModuleReference(reference),
@@ -430,6 +451,7 @@ async fn graph_node_to_referenced_nodes(
                                 None,
                             ))
                         }
+                        ChunkingType::Weak => Ok((None, None)),
                         ChunkingType::Async => {
                             let chunk_loading = chunk_content_context
                                 .chunking_context
@@ -12,6 +12,63 @@ use crate::target::CompileTarget;
 
 static DEFAULT_NODEJS_VERSION: &str = "16.0.0";
 
+/// The builtin modules of Node.js, without the `node:` protocol.
+pub const NODE_BUILTIN_MODULES: [&str; 50] = [
+    "assert",
+    "async_hooks",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "diagnostics_channel",
+    "dns",
+    "dns/promises",
+    "domain",
+    "events",
+    "fs",
+    "fs/promises",
+    "http",
+    "http2",
+    "https",
+    "inspector",
+    "module",
+    "net",
+    "os",
+    "path",
+    "path/posix",
+    "path/win32",
+    "perf_hooks",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "stream/promises",
+    "stream/web",
+    "string_decoder",
+    "sys",
+    "timers",
+    "timers/promises",
+    "tls",
+    "trace_events",
+    "tty",
+    "url",
+    "util",
+    "util/types",
+    "v8",
+    "vm",
+    "wasi",
+    "worker_threads",
+    "zlib",
+];
+
+/// The Node.js builtin modules edge workers provide.
+pub const EDGE_BUILTIN_MODULES: [&str; 5] = ["buffer", "events", "assert", "util", "async_hooks"];
+
 #[turbo_tasks::value]
 #[derive(Default)]
 pub enum Rendering {
@@ -109,6 +166,21 @@ impl Environment {
         })
     }
 
+    /// The Node.js builtin modules the environment provides, without the
+    /// `node:` protocol.
+    #[turbo_tasks::function]
+    pub async fn builtin_modules(self: Vc<Self>) -> Result<Vc<Vec<String>>> {
+        let this = self.await?;
+        let modules: &[&str] = match this.execution {
+            ExecutionEnvironment::NodeJsBuildTime(..) | ExecutionEnvironment::NodeJsLambda(_) => {
+                &NODE_BUILTIN_MODULES
+            }
+            ExecutionEnvironment::EdgeWorker(_) => &EDGE_BUILTIN_MODULES,
+            ExecutionEnvironment::Browser(_) | ExecutionEnvironment::Custom(_) => &[],
+        };
+        Ok(Vc::cell(modules.iter().map(|m| m.to_string()).collect()))
+    }
+
     #[turbo_tasks::function]
     pub async fn supports_commonjs_externals(self: Vc<Self>) -> Result<Vc<bool>> {
         let this = self.await?;
@@ -122,6 +194,22 @@ impl Environment {
         })
     }
 
+    /// Whether external modules can be imported with static `import`
+    /// declarations. Chunk items are emitted as module factories, which
+    /// can't contain `import` declarations, so no environment supports this
+    /// yet.
+    #[turbo_tasks::function]
+    pub async fn supports_esm_externals(self: Vc<Self>) -> Result<Vc<bool>> {
+        let this = self.await?;
+        Ok(match this.execution {
+            ExecutionEnvironment::NodeJsBuildTime(..)
+            | ExecutionEnvironment::NodeJsLambda(_)
+            | ExecutionEnvironment::Browser(_)
+            | ExecutionEnvironment::EdgeWorker(_)
+            | ExecutionEnvironment::Custom(_) => Vc::cell(false),
+        })
+    }
+
     #[turbo_tasks::function]
     pub async fn supports_wasm(self: Vc<Self>) -> Result<Vc<bool>> {
         let this = self.await?;
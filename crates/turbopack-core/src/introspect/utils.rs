@@ -38,6 +38,11 @@ fn passthrough_reference_ty() -> Vc<String> {
     Vc::cell("passthrough reference".to_string())
 }
 
+#[turbo_tasks::function]
+fn weak_reference_ty() -> Vc<String> {
+    Vc::cell("weak reference".to_string())
+}
+
 #[turbo_tasks::function]
 pub async fn content_to_details(content: Vc<AssetContent>) -> Result<Vc<String>> {
     Ok(match &*content.await? {
@@ -77,6 +82,7 @@ pub async fn children_from_module_references(
                 }
                 Some(ChunkingType::Async) => key = async_reference_ty(),
                 Some(ChunkingType::Passthrough) => key = passthrough_reference_ty(),
+                Some(ChunkingType::Weak) => key = weak_reference_ty(),
             }
         }
 
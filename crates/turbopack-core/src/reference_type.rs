@@ -38,6 +38,8 @@ pub enum CommonJsReferenceSubType {
 pub enum EcmaScriptModulesReferenceSubType {
     ImportPart(Vc<ModulePart>),
     Import,
+    /// An import with a `type` import attribute, e.g. `with { type: "json" }`.
+    ImportWithType(Vc<String>),
     DynamicImport,
     Custom(u8),
     #[default]
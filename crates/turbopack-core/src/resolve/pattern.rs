@@ -1131,6 +1131,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn template_literal_pattern() {
+        let pat = Pattern::Concatenation(vec![
+            Pattern::Constant("./pages/".to_string()),
+            Pattern::Dynamic,
+            Pattern::Constant(".js".to_string()),
+        ]);
+        for file in ["./pages/a.js", "./pages/b.js", "./pages/c.js"] {
+            assert!(pat.is_match(file));
+        }
+        assert!(!pat.is_match("./pages/a.css"));
+    }
+
     #[test]
     fn is_match() {
         let pat = Pattern::Concatenation(vec![
@@ -23,6 +23,21 @@ pub use source_map_asset::SourceMapAsset;
 /// Represents an empty value in a u32 variable in the sourcemap crate.
 static SOURCEMAP_CRATE_NONE_U32: u32 = !0;
 
+/// The name of the source that generated code (e. g. runtime imports inserted
+/// by turbopack) is mapped to. Sources ending with this name are added to the
+/// `x_google_ignoreList` of the source map, which keeps them out of user-facing
+/// stack traces.
+pub const GENERATED_CODE_SOURCE_NAME: &str = "[turbopack]/generated";
+
+/// Returns the indices of all sources of `map` that are ignore listed.
+fn ignore_list(map: &RegularMap) -> Vec<u32> {
+    map.sources()
+        .enumerate()
+        .filter(|(_, source)| source.ends_with(GENERATED_CODE_SOURCE_NAME))
+        .map(|(i, _)| i as u32)
+        .collect()
+}
+
 /// Allows callers to generate source maps.
 #[turbo_tasks::value_trait]
 pub trait GenerateSourceMap {
@@ -247,6 +262,16 @@ impl SourceMap {
             SourceMap::Decoded(r) => {
                 let mut bytes = vec![];
                 r.0.to_writer(&mut bytes)?;
+                if let DecodedMap::Regular(map) = &r.0 {
+                    let ignore_list = ignore_list(map);
+                    if !ignore_list.is_empty() {
+                        // The sourcemap crate doesn't know about the ignore list, so it's
+                        // added to the serialized map.
+                        let mut json: serde_json::Value = serde_json::from_slice(&bytes)?;
+                        json["x_google_ignoreList"] = ignore_list.into();
+                        bytes = serde_json::to_vec(&json)?;
+                    }
+                }
                 Rope::from(bytes)
             }
 
@@ -677,3 +702,20 @@ impl SourceMapSection {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sourcemap::SourceMapBuilder;
+
+    use super::{ignore_list, GENERATED_CODE_SOURCE_NAME};
+
+    #[test]
+    fn ignore_lists_generated_code() {
+        let mut builder = SourceMapBuilder::new(None);
+        builder.add_source("/turbopack/[project]/input/index.js");
+        builder.add_source(&format!("/turbopack/{GENERATED_CODE_SOURCE_NAME}"));
+        let map = builder.into_sourcemap();
+
+        assert_eq!(ignore_list(&map), vec![1]);
+    }
+}
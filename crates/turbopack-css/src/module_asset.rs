@@ -24,6 +24,7 @@ use turbopack_ecmascript::{
     chunk::{
         EcmascriptChunkItem, EcmascriptChunkItemContent, EcmascriptChunkPlaceable,
         EcmascriptChunkType, EcmascriptChunkingContext, EcmascriptExports,
+        OptionCssModuleClassNames,
     },
     utils::StringifyJs,
     ParseResultSourceMap,
@@ -259,6 +260,13 @@ impl EcmascriptChunkPlaceable for ModuleCssAsset {
     fn get_exports(&self) -> Vc<EcmascriptExports> {
         EcmascriptExports::Value.cell()
     }
+
+    #[turbo_tasks::function]
+    async fn css_module_class_names(self: Vc<Self>) -> Result<Vc<OptionCssModuleClassNames>> {
+        Ok(Vc::cell(Some(
+            self.classes().await?.keys().cloned().collect(),
+        )))
+    }
 }
 
 #[turbo_tasks::value_impl]
@@ -17,6 +17,7 @@ use turbopack_core::{
 use turbopack_ecmascript::{
     chunk::{EcmascriptChunk, EcmascriptChunkingContext},
     manifest::{chunk_asset::ManifestAsyncModule, loader_item::ManifestLoaderChunkItem},
+    references::esm::EsmNamespaceShape,
 };
 use turbopack_ecmascript_runtime::RuntimeType;
 
@@ -61,6 +62,51 @@ impl DevChunkingContextBuilder {
         self
     }
 
+    pub fn import_coverage_instrumentation(mut self, enable: bool) -> Self {
+        self.chunking_context.import_coverage_instrumentation = enable;
+        self
+    }
+
+    pub fn esm_namespace_shape(mut self, shape: EsmNamespaceShape) -> Self {
+        self.chunking_context.esm_namespace_shape = shape;
+        self
+    }
+
+    pub fn inline_eager_json_imports(mut self, enable: bool) -> Self {
+        self.chunking_context.inline_eager_json_imports = enable;
+        self
+    }
+
+    pub fn esm_cycle_checks(mut self, enable: bool) -> Self {
+        self.chunking_context.esm_cycle_checks = enable;
+        self
+    }
+
+    pub fn esm_live_bindings(mut self, enable: bool) -> Self {
+        self.chunking_context.esm_live_bindings = enable;
+        self
+    }
+
+    pub fn async_dependency_timeout_ms(mut self, timeout_ms: Option<u32>) -> Self {
+        self.chunking_context.async_dependency_timeout_ms = timeout_ms;
+        self
+    }
+
+    pub fn import_target_chunks(mut self, enable: bool) -> Self {
+        self.chunking_context.import_target_chunks = enable;
+        self
+    }
+
+    pub fn chunk_unannotated_references(mut self, enable: bool) -> Self {
+        self.chunking_context.chunk_unannotated_references = enable;
+        self
+    }
+
+    pub fn inline_json_max_size(mut self, max_size: Option<u32>) -> Self {
+        self.chunking_context.inline_json_max_size = max_size;
+        self
+    }
+
     pub fn build(self) -> Vc<DevChunkingContext> {
         DevChunkingContext::new(Value::new(self.chunking_context))
     }
@@ -101,6 +147,29 @@ pub struct DevChunkingContext {
     environment: Vc<Environment>,
     /// The kind of runtime to include in the output.
     runtime_type: RuntimeType,
+    /// Count the evaluation of generated imports in the global import
+    /// coverage registry
+    import_coverage_instrumentation: bool,
+    /// The shape of the namespace objects bound by namespace imports
+    esm_namespace_shape: EsmNamespaceShape,
+    /// Inline the default import of JSON modules annotated with
+    /// `chunking-type: eager`
+    inline_eager_json_imports: bool,
+    /// Check that exports of modules in import cycles aren't read before the
+    /// module finished evaluating
+    esm_cycle_checks: bool,
+    /// Read named imports of internal modules on every access
+    esm_live_bindings: bool,
+    /// Warn when the async dependencies of async modules haven't resolved
+    /// after this many milliseconds
+    async_dependency_timeout_ms: Option<u32>,
+    /// Pass the path of the chunk containing the module async loaders import
+    /// to the import
+    import_target_chunks: bool,
+    /// Chunk references that don't request a chunking type themselves
+    chunk_unannotated_references: bool,
+    /// Inline the default import of JSON modules up to this many bytes
+    inline_json_max_size: Option<u32>,
 }
 
 impl DevChunkingContext {
@@ -126,6 +195,15 @@ impl DevChunkingContext {
                 enable_hot_module_replacement: false,
                 environment,
                 runtime_type: Default::default(),
+                import_coverage_instrumentation: false,
+                esm_namespace_shape: Default::default(),
+                inline_eager_json_imports: false,
+                esm_cycle_checks: false,
+                esm_live_bindings: false,
+                async_dependency_timeout_ms: None,
+                import_target_chunks: false,
+                chunk_unannotated_references: true,
+                inline_json_max_size: None,
             },
         }
     }
@@ -316,6 +394,11 @@ impl ChunkingContext for DevChunkingContext {
         Vc::cell(self.enable_hot_module_replacement)
     }
 
+    #[turbo_tasks::function]
+    fn chunk_unannotated_references(&self) -> Vc<bool> {
+        Vc::cell(self.chunk_unannotated_references)
+    }
+
     #[turbo_tasks::function]
     async fn chunk_group(
         self: Vc<Self>,
@@ -448,4 +531,44 @@ impl EcmascriptChunkingContext for DevChunkingContext {
     fn has_react_refresh(&self) -> Vc<bool> {
         Vc::cell(true)
     }
+
+    #[turbo_tasks::function]
+    fn import_coverage_instrumentation(&self) -> Vc<bool> {
+        Vc::cell(self.import_coverage_instrumentation)
+    }
+
+    #[turbo_tasks::function]
+    fn esm_namespace_shape(&self) -> Vc<EsmNamespaceShape> {
+        self.esm_namespace_shape.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn inline_eager_json_imports(&self) -> Vc<bool> {
+        Vc::cell(self.inline_eager_json_imports)
+    }
+
+    #[turbo_tasks::function]
+    fn esm_cycle_checks(&self) -> Vc<bool> {
+        Vc::cell(self.esm_cycle_checks)
+    }
+
+    #[turbo_tasks::function]
+    fn esm_live_bindings(&self) -> Vc<bool> {
+        Vc::cell(self.esm_live_bindings)
+    }
+
+    #[turbo_tasks::function]
+    fn async_dependency_timeout_ms(&self) -> Vc<Option<u32>> {
+        Vc::cell(self.async_dependency_timeout_ms)
+    }
+
+    #[turbo_tasks::function]
+    fn import_target_chunks(&self) -> Vc<bool> {
+        Vc::cell(self.import_target_chunks)
+    }
+
+    #[turbo_tasks::function]
+    fn inline_json_max_size(&self) -> Vc<Option<u32>> {
+        Vc::cell(self.inline_json_max_size)
+    }
 }
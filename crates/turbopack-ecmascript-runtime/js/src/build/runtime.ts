@@ -27,6 +27,7 @@ type SourceInfo =
 
 type ExternalRequire = (id: ModuleId) => Exports | EsmNamespaceObject;
 type ExternalImport = (id: ModuleId) => Promise<Exports | EsmNamespaceObject>;
+type RequireBuiltin = (name: string) => Exports | EsmNamespaceObject;
 type ResolveAbsolutePath = (modulePath?: string) => string;
 
 interface TurbopackNodeBuildContext extends TurbopackBaseContext {
@@ -34,6 +35,8 @@ interface TurbopackNodeBuildContext extends TurbopackBaseContext {
   R: ResolvePathFromModule;
   x: ExternalRequire;
   y: ExternalImport;
+  X: ExternalRequire;
+  b: RequireBuiltin;
 }
 
 type ModuleFactory = (
@@ -122,7 +125,11 @@ function loadWebAssemblyModule(chunkPath: ChunkPath) {
   return compileWebAssemblyFromPath(resolved);
 }
 
-function instantiateModule(id: ModuleId, source: SourceInfo): Module {
+function instantiateModule(
+  id: ModuleId,
+  source: SourceInfo,
+  cache: boolean = true
+): Module {
   const moduleFactory = moduleFactories[id];
   if (typeof moduleFactory !== "function") {
     // This can happen if modules incorrectly handle HMR disposes/updates,
@@ -163,22 +170,35 @@ function instantiateModule(id: ModuleId, source: SourceInfo): Module {
     children: [],
     namespaceObject: undefined,
   };
-  moduleCache[id] = module;
+  if (cache) {
+    moduleCache[id] = module;
+  }
 
   // NOTE(alexkirsz) This can fail when the module encounters a runtime error.
   try {
     const r = commonJsRequire.bind(null, module);
     moduleFactory.call(module.exports, {
       a: asyncModule.bind(null, module),
+      A: asyncImport,
+      o: federationImport,
       e: module.exports,
       r,
       t: runtimeRequire,
       x: externalRequire,
       y: externalImport,
+      X: externalLazy,
+      b: requireBuiltin,
       f: requireContext.bind(null, module),
       i: esmImport.bind(null, module),
+      I: esmImportNamespace.bind(null, module),
+      C: esmImportUncached.bind(null, module),
+      E: esmImportBinding.bind(null, module),
+      h: registerResourceHint,
+      H: registerResourceHint,
+      F: registerResourceHint,
       s: esmExport.bind(null, module, module.exports),
       j: dynamicExport.bind(null, module, module.exports),
+      N: reexportNamespace.bind(null, module, module.exports),
       p: moduleLookup,
       v: exportValue.bind(null, module),
       n: exportNamespace.bind(null, module),
@@ -235,6 +255,24 @@ function getOrInstantiateModuleFromParent(
   });
 }
 
+/**
+ * Instantiates a module without reading or writing the module cache, see
+ * `esmImportUncached`.
+ */
+function instantiateModuleUncached(id: ModuleId, sourceModule: Module): Module {
+  return instantiateModule(
+    id,
+    { type: SourceType.Parent, parentId: sourceModule.id },
+    false
+  );
+}
+
+/**
+ * Resource hints only affect browsers. In Node.js, chunks are read from disk
+ * when they're required, so there's nothing to fetch ahead of time.
+ */
+function registerResourceHint(_moduleId: ModuleId) {}
+
 /**
  * Instantiates a runtime module.
  */
@@ -94,11 +94,14 @@ type SourceInfo =
       parents?: ModuleId[];
     };
 
+type ResourceHint = "prefetch" | "preload";
+
 interface RuntimeBackend {
   registerChunk: (chunkPath: ChunkPath, params?: DevRuntimeParams) => void;
   loadChunk: (chunkPath: ChunkPath, source: SourceInfo) => Promise<void>;
   reloadChunk?: (chunkPath: ChunkPath) => Promise<void>;
   unloadChunk?: (chunkPath: ChunkPath) => void;
+  registerResourceHint?: (rel: ResourceHint, chunkPath: ChunkPath) => void;
 
   restart: () => void;
 }
@@ -134,6 +137,11 @@ const moduleChunksMap: Map<ModuleId, Set<ChunkPath>> = new Map();
  * Map from a chunk path to all modules it contains.
  */
 const chunkModulesMap: Map<ModuleId, Set<ChunkPath>> = new Map();
+/**
+ * Map from module ID to the chunk containing it, as named by the async loaders
+ * importing it, see `esmImportWithTargetChunk`.
+ */
+const moduleTargetChunks: Map<ModuleId, ChunkPath> = new Map();
 /**
  * Chunk lists that contain a runtime. When these chunk lists receive an update
  * that can't be reconciled with the current state of the page, we need to
@@ -259,7 +267,11 @@ async function loadChunkPath(
   }
 }
 
-function instantiateModule(id: ModuleId, source: SourceInfo): Module {
+function instantiateModule(
+  id: ModuleId,
+  source: SourceInfo,
+  cache: boolean = true
+): Module {
   const moduleFactory = moduleFactories[id];
   if (typeof moduleFactory !== "function") {
     // This can happen if modules incorrectly handle HMR disposes/updates,
@@ -311,7 +323,9 @@ function instantiateModule(id: ModuleId, source: SourceInfo): Module {
     hot,
   };
 
-  moduleCache[id] = module;
+  if (cache) {
+    moduleCache[id] = module;
+  }
   moduleHotState.set(module, hotState);
 
   // NOTE(alexkirsz) This can fail when the module encounters a runtime error.
@@ -323,13 +337,22 @@ function instantiateModule(id: ModuleId, source: SourceInfo): Module {
         module.exports,
         augmentContext({
           a: asyncModule.bind(null, module),
+          A: asyncImport,
+          o: federationImport,
           e: module.exports,
           r: commonJsRequire.bind(null, module),
           t: runtimeRequire,
           f: requireContext.bind(null, module),
-          i: esmImport.bind(null, module),
+          i: esmImportWithTargetChunk.bind(null, module),
+          I: esmImportNamespace.bind(null, module),
+          C: esmImportUncached.bind(null, module),
+          E: esmImportBinding.bind(null, module),
+          h: registerResourceHint.bind(null, "prefetch"),
+          H: registerResourceHint.bind(null, "preload"),
+          F: prefetchWhenIdle,
           s: esmExport.bind(null, module, module.exports),
           j: dynamicExport.bind(null, module, module.exports),
+          N: reexportNamespace.bind(null, module, module.exports),
           p: moduleLookup,
           v: exportValue.bind(null, module),
           n: exportNamespace.bind(null, module),
@@ -396,6 +419,72 @@ function runModuleExecutionHooks(
   }
 }
 
+/**
+ * Instantiates a module without reading or writing the module cache, see
+ * `esmImportUncached`.
+ */
+function instantiateModuleUncached(id: ModuleId, sourceModule: Module): Module {
+  return instantiateModule(
+    id,
+    { type: SourceType.Parent, parentId: sourceModule.id },
+    false
+  );
+}
+
+/**
+ * Imports a module like `esmImport`. Async loaders also name the chunk
+ * containing the module they import, which resource hints for the module then
+ * fetch.
+ */
+function esmImportWithTargetChunk(
+  sourceModule: Module,
+  id: ModuleId,
+  chunkPath?: ChunkPath
+) {
+  if (chunkPath != null) {
+    moduleTargetChunks.set(id, chunkPath);
+  }
+  return esmImport(sourceModule, id);
+}
+
+/**
+ * Registers a `prefetch` or `preload` resource hint for the chunks containing
+ * a module, or the chunk named by an async loader importing it. Backends that
+ * can't fetch chunks ahead of time ignore it.
+ */
+function registerResourceHint(rel: ResourceHint, moduleId: ModuleId) {
+  const targetChunk = moduleTargetChunks.get(moduleId);
+  if (targetChunk != null) {
+    BACKEND.registerResourceHint?.(rel, targetChunk);
+    return;
+  }
+  const chunkPaths = moduleChunksMap.get(moduleId);
+  if (chunkPaths == null) {
+    return;
+  }
+  for (const chunkPath of chunkPaths) {
+    BACKEND.registerResourceHint?.(rel, chunkPath);
+  }
+}
+
+type GlobalWithIdleCallback = typeof globalThis & {
+  requestIdleCallback?: (callback: () => void) => number;
+};
+
+/**
+ * Prefetches the chunks containing a module once the browser is idle, without
+ * blocking the evaluation of the importing module.
+ */
+function prefetchWhenIdle(moduleId: ModuleId) {
+  const prefetch = () => registerResourceHint("prefetch", moduleId);
+  const { requestIdleCallback } = globalThis as GlobalWithIdleCallback;
+  if (requestIdleCallback != null) {
+    requestIdleCallback(prefetch);
+  } else {
+    setTimeout(prefetch, 0);
+  }
+}
+
 /**
  * Retrieves a module from the cache, or instantiate it if it is not cached.
  */
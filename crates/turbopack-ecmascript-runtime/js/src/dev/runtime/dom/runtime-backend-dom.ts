@@ -175,6 +175,29 @@ async function loadWebAssemblyModule(
       });
     },
 
+    registerResourceHint(rel, chunkPath) {
+      // A loaded chunk doesn't need to be fetched again.
+      if (chunkResolvers.get(chunkPath)?.resolved) {
+        return;
+      }
+
+      const chunkUrl = getChunkRelativeUrl(chunkPath);
+      const previousLinks = document.querySelectorAll(
+        `link[rel=${rel}][href="${chunkUrl}"]`
+      );
+      if (previousLinks.length > 0) {
+        return;
+      }
+
+      const link = document.createElement("link");
+      link.rel = rel;
+      link.href = chunkUrl;
+      if (rel === "preload") {
+        link.as = chunkPath.endsWith(".css") ? "style" : "script";
+      }
+      document.head.appendChild(link);
+    },
+
     restart: () => self.location.reload(),
   };
 
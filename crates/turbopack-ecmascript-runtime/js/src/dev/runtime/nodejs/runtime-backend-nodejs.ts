@@ -20,16 +20,21 @@ type ExternalRequire = (
   esm?: boolean
 ) => Exports | EsmNamespaceObject;
 type ExternalImport = (id: ModuleId) => Promise<Exports | EsmNamespaceObject>;
+type RequireBuiltin = (name: string) => Exports | EsmNamespaceObject;
 
 interface TurbopackDevContext extends TurbopackDevBaseContext {
   x: ExternalRequire;
   y: ExternalImport;
+  X: ExternalRequire;
+  b: RequireBuiltin;
 }
 
 function augmentContext(context: TurbopackDevBaseContext): TurbopackDevContext {
   const nodejsContext = context as TurbopackDevContext;
   nodejsContext.x = externalRequire;
   nodejsContext.y = externalImport;
+  nodejsContext.X = externalLazy;
+  nodejsContext.b = requireBuiltin;
   return nodejsContext;
 }
 
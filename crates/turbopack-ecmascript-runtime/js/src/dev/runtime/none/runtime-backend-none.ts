@@ -25,12 +25,14 @@ type ExternalImport = (id: ModuleId) => Promise<Exports | EsmNamespaceObject>;
 interface TurbopackDevContext extends TurbopackDevBaseContext {
   x: ExternalRequire;
   y: ExternalImport;
+  X: ExternalRequire;
 }
 
 function augmentContext(context: TurbopackDevBaseContext): TurbopackDevContext {
   const nodejsContext = context as TurbopackDevContext;
   nodejsContext.x = externalRequire;
   nodejsContext.y = externalImport;
+  nodejsContext.X = externalLazy;
   return nodejsContext;
 }
 
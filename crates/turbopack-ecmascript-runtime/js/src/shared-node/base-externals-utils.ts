@@ -66,3 +66,29 @@ externalRequire.resolve = (
 ) => {
   return require.resolve(id, options);
 };
+
+/**
+ * Binds an external without requiring it. The external is required on the
+ * first access to one of its exports.
+ */
+function externalLazy(
+  id: ModuleId,
+  esm: boolean = false
+): Exports | EsmNamespaceObject {
+  let raw: Exports | EsmNamespaceObject | undefined;
+  const load = () => (raw ??= externalRequire(id, esm));
+  return new Proxy(
+    {},
+    {
+      get: (_target, prop) => Reflect.get(load(), prop),
+      has: (_target, prop) => Reflect.has(load(), prop),
+      ownKeys: () => Reflect.ownKeys(load()),
+      getOwnPropertyDescriptor: (_target, prop) => {
+        const descriptor = Reflect.getOwnPropertyDescriptor(load(), prop);
+        // The proxy target doesn't have the property, so it can't be reported
+        // as non-configurable.
+        return descriptor && { ...descriptor, configurable: true };
+      },
+    }
+  );
+}
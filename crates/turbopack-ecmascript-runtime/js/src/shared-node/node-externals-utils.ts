@@ -28,3 +28,12 @@ function resolveAbsolutePath(modulePath?: string): string {
   }
   return ABSOLUTE_ROOT;
 }
+
+/**
+ * Requires the Node.js builtin `name`, which is imported with the `node:`
+ * protocol in the source. The protocol is stripped at compile time, as older
+ * Node.js versions don't support it in `require`.
+ */
+function requireBuiltin(name: string): Exports | EsmNamespaceObject {
+  return externalRequire(name, true);
+}
@@ -7,3 +7,4 @@
 
 declare var commonJsRequireContext: CommonJsRequireContext;
 declare var getOrInstantiateModuleFromParent: GetOrInstantiateModuleFromParent;
+declare var instantiateModuleUncached: InstantiateModuleUncached;
@@ -22,12 +22,26 @@ type ChunkData =
 type CommonJsRequire = (moduleId: ModuleId) => Exports;
 type EsmImport = (
   moduleId: ModuleId,
-  allowExportDefault: boolean
+  chunkPath?: ChunkPath
 ) => EsmNamespaceObject | Promise<EsmNamespaceObject>;
+type EsmImportNamespace = (
+  moduleId: ModuleId,
+  freeze?: boolean
+) => EsmNamespaceObject | Promise<EsmNamespaceObject>;
+type EsmImportUncached = (
+  moduleId: ModuleId,
+  freeze?: boolean
+) => EsmNamespaceObject | Promise<EsmNamespaceObject>;
+type EsmImportBinding = (moduleId: ModuleId, name: string) => any;
+type RegisterResourceHint = (moduleId: ModuleId) => void;
 type EsmExport = (exportGetters: Record<string, () => any>) => void;
 type ExportValue = (value: any) => void;
 type ExportNamespace = (namespace: any) => void;
 type DynamicExport = (object: Record<string, any>) => void;
+type ReexportNamespace = (
+  object: Record<string, any>,
+  exclude: string[]
+) => void;
 type ModuleLookup = (
   object: Record<string, any>,
   name: string,
@@ -50,22 +64,38 @@ type ResolvePathFromModule = (moduleId: string) => string;
 type AsyncModule = (
   body: (
     handleAsyncDependencies: (
-      deps: Dep[]
+      deps: Dep[],
+      options?: AsyncDependencyOptions
     ) => Exports[] | Promise<() => Exports[]>,
-    asyncResult: (err?: any) => void
+    asyncResult: (err?: any, value?: Exports) => void
   ) => void,
   hasAwait: boolean
 ) => void;
+type AsyncImport = (dep: Dep) => Promise<Exports>;
+type FederationImport = (
+  container: string,
+  module: string,
+  shareScope?: string
+) => Promise<Exports>;
 
 interface TurbopackBaseContext {
   a: AsyncModule;
+  A: AsyncImport;
+  o: FederationImport;
   e: Module["exports"];
   r: CommonJsRequire;
   t: CommonJsRequire;
   f: RequireContextFactory;
   i: EsmImport;
+  I: EsmImportNamespace;
+  C: EsmImportUncached;
+  E: EsmImportBinding;
+  h: RegisterResourceHint;
+  H: RegisterResourceHint;
+  F: RegisterResourceHint;
   s: EsmExport;
   j: DynamicExport;
+  N: ReexportNamespace;
   p: ModuleLookup;
   v: ExportValue;
   n: ExportNamespace;
@@ -18,6 +18,7 @@ interface Exports {
 type EsmNamespaceObject = Record<string, any>;
 
 const REEXPORTED_OBJECTS = Symbol("reexported objects");
+const REEXPORT_EXCLUDED = Symbol("reexport excluded");
 
 interface BaseModule {
   exports: Exports | Promise<Exports> | AsyncModulePromise;
@@ -31,6 +32,7 @@ interface BaseModule {
     | Promise<EsmNamespaceObject>
     | AsyncModulePromise<EsmNamespaceObject>;
   [REEXPORTED_OBJECTS]?: any[];
+  [REEXPORT_EXCLUDED]?: Set<PropertyKey>;
 }
 
 interface Module extends BaseModule {}
@@ -49,6 +51,8 @@ interface RequireContext {
   resolve(moduleId: ModuleId): ModuleId;
 }
 
+type InstantiateModuleUncached = (id: ModuleId, sourceModule: Module) => Module;
+
 type GetOrInstantiateModuleFromParent = (
   moduleId: ModuleId,
   parentModule: Module
@@ -108,6 +112,7 @@ function ensureDynamicExports(module: Module, exports: Exports) {
         ) {
           return Reflect.get(target, prop);
         }
+        if (module[REEXPORT_EXCLUDED]?.has(prop)) return undefined;
         for (const obj of reexportedObjects!) {
           const value = Reflect.get(obj, prop);
           if (value !== undefined) return value;
@@ -118,7 +123,13 @@ function ensureDynamicExports(module: Module, exports: Exports) {
         const keys = Reflect.ownKeys(target);
         for (const obj of reexportedObjects!) {
           for (const key of Reflect.ownKeys(obj)) {
-            if (key !== "default" && !keys.includes(key)) keys.push(key);
+            if (
+              key !== "default" &&
+              !keys.includes(key) &&
+              !module[REEXPORT_EXCLUDED]?.has(key)
+            ) {
+              keys.push(key);
+            }
           }
         }
         return keys;
@@ -142,6 +153,24 @@ function dynamicExport(
   }
 }
 
+/**
+ * Re-exports a star re-exported namespace, except for `default` and the names
+ * the module declares itself. Like `dynamicExport`, exports are looked up on
+ * every access, so exports the namespace gets later are re-exported too.
+ */
+function reexportNamespace(
+  module: Module,
+  exports: Exports,
+  object: Record<string, any>,
+  exclude: string[]
+) {
+  dynamicExport(module, exports, object);
+
+  let excluded = module[REEXPORT_EXCLUDED];
+  if (!excluded) excluded = module[REEXPORT_EXCLUDED] = new Set();
+  for (const name of exclude) excluded.add(name);
+}
+
 /**
  * Access one entry from a mapping from name to functor.
  */
@@ -227,6 +256,34 @@ function esmImport(
   const module = getOrInstantiateModuleFromParent(id, sourceModule);
   if (module.error) throw module.error;
 
+  return getNamespaceObject(module);
+}
+
+/**
+ * Imports a module for an import annotated with `turbopackNoCache`. The
+ * module factory runs on every call, and the module is neither read from nor
+ * written to the module cache, so every call returns a fresh namespace
+ * object. For an async module, that's a fresh promise, which the async
+ * dependency handler awaits like the one of a cached import.
+ *
+ * With `freeze`, the namespace object is frozen like with
+ * `esmImportNamespace`.
+ */
+function esmImportUncached(
+  sourceModule: Module,
+  id: ModuleId,
+  freeze?: boolean
+): Exclude<Module["namespaceObject"], undefined> {
+  const module = instantiateModuleUncached(id, sourceModule);
+  if (module.error) throw module.error;
+
+  const namespace = getNamespaceObject(module);
+  return freeze ? toNamespaceObject(namespace, freeze) : namespace;
+}
+
+function getNamespaceObject(
+  module: Module
+): Exclude<Module["namespaceObject"], undefined> {
   // any ES module has to have `module.namespaceObject` defined.
   if (module.namespaceObject) return module.namespaceObject;
 
@@ -239,6 +296,62 @@ function esmImport(
   ));
 }
 
+/**
+ * Imports the namespace object of a module for a namespace import
+ * (`import * as ns from "..."`). It's the same object `esmImport` returns,
+ * but namespace objects set with `exportNamespace` don't have a
+ * `Symbol.toStringTag` of "Module" yet.
+ *
+ * With `freeze`, the namespace object is frozen as required by the spec.
+ */
+function esmImportNamespace(
+  sourceModule: Module,
+  id: ModuleId,
+  freeze?: boolean
+): Exclude<Module["namespaceObject"], undefined> {
+  return toNamespaceObject(esmImport(sourceModule, id), freeze);
+}
+
+/**
+ * Marks `namespace` as a namespace object and freezes it with `freeze`.
+ *
+ * The namespace object of an async module is a promise, which the async
+ * dependency handler replaces with its exports. It's shared by all importers
+ * and the runtime writes to it when the module settles, so it's left alone.
+ */
+function toNamespaceObject<T>(namespace: T, freeze?: boolean): T {
+  if (
+    typeof namespace !== "object" ||
+    namespace === null ||
+    isPromise(namespace) ||
+    !Object.isExtensible(namespace)
+  ) {
+    return namespace;
+  }
+  if (toStringTag) {
+    defineProp(namespace, toStringTag, { value: "Module" });
+  }
+  if (freeze) {
+    Object.freeze(namespace);
+  }
+  return namespace;
+}
+
+/**
+ * Reads the export `name` of a module for a live binding of a named import.
+ * It's read on every access, so reassignments of the export in the exporting
+ * module are observable.
+ */
+function esmImportBinding(sourceModule: Module, id: ModuleId, name: string) {
+  const namespace = esmImport(sourceModule, id);
+  // The namespace object of an async module is a promise, which carries its
+  // exports.
+  const exports = isAsyncModuleExt(namespace)
+    ? namespace[turbopackExports]
+    : namespace;
+  return (exports as any)[name];
+}
+
 // Add a simple runtime require so that environments without one can still pass
 // `typeof require` CommonJS checks so that exports are correctly registered.
 const runtimeRequire =
@@ -355,6 +468,28 @@ type AsyncModuleExt = {
 
 type AsyncModulePromise<T = Exports> = Promise<T> & AsyncModuleExt;
 
+type AsyncDependencyOptions = { timeout?: number };
+
+/**
+ * Warns when `promise`, which settles once the async dependencies of the
+ * module `id` resolved, hasn't settled `timeout` milliseconds after it was
+ * created. This helps diagnosing dependencies that hang, e.g. on a top-level
+ * await that never settles. The module's own top-level await isn't timed.
+ */
+function warnOnSlowAsyncDependencies(
+  id: ModuleId,
+  promise: Promise<unknown>,
+  timeout: number
+) {
+  const timer = setTimeout(() => {
+    console.warn(
+      `The async dependencies of module ${id} haven't resolved after ${timeout}ms.`
+    );
+  }, timeout);
+  const clear = () => clearTimeout(timer);
+  promise.then(clear, clear);
+}
+
 function wrapDeps(deps: Dep[]): AsyncModuleExt[] {
   return deps.map((dep) => {
     if (dep !== null && typeof dep === "object") {
@@ -395,9 +530,10 @@ function asyncModule(
   module: Module,
   body: (
     handleAsyncDependencies: (
-      deps: Dep[]
+      deps: Dep[],
+      options?: AsyncDependencyOptions
     ) => Exports[] | Promise<() => Exports[]>,
-    asyncResult: (err?: any) => void
+    asyncResult: (err?: any, value?: Exports) => void
   ) => void,
   hasAwait: boolean
 ) {
@@ -423,7 +559,10 @@ function asyncModule(
 
   module.exports = module.namespaceObject = promise;
 
-  function handleAsyncDependencies(deps: Dep[]) {
+  function handleAsyncDependencies(
+    deps: Dep[],
+    options?: AsyncDependencyOptions
+  ) {
     const currentDeps = wrapDeps(deps);
 
     const getResult = () =>
@@ -450,14 +589,20 @@ function asyncModule(
 
     currentDeps.map((dep) => dep[turbopackQueues](fnQueue));
 
-    return fn.queueCount ? promise : getResult();
+    if (!fn.queueCount) return getResult();
+    if (options?.timeout != null) {
+      warnOnSlowAsyncDependencies(module.id, promise, options.timeout);
+    }
+    return promise;
   }
 
-  function asyncResult(err?: any) {
+  // CommonJS modules pass their final `module.exports`, which may have been
+  // reassigned by the module body.
+  function asyncResult(err?: any, value: Exports = exports) {
     if (err) {
       reject((promise[turbopackError] = err));
     } else {
-      resolve(exports);
+      resolve((promise[turbopackExports] = value));
     }
 
     resolveQueue(queue);
@@ -470,6 +615,82 @@ function asyncModule(
   }
 }
 
+/**
+ * Waits for the dynamically imported async module `dep` the same way
+ * `handleAsyncDependencies` waits for a static one: through the queues of the
+ * module and its async dependencies. Resolves to the exports of the module.
+ */
+function asyncImport(dep: Dep): Promise<Exports> {
+  const [current] = wrapDeps([dep]);
+
+  const { promise, resolve, reject } = createPromise<Exports>();
+
+  const fn: AsyncQueueFn = Object.assign(
+    () => {
+      if (current[turbopackError]) {
+        reject(current[turbopackError]);
+      } else {
+        // The module promise resolves to the final exports, which a CommonJS
+        // module may have reassigned.
+        resolve(isPromise(dep) ? dep : current[turbopackExports]);
+      }
+    },
+    { queueCount: 0 }
+  );
+
+  current[turbopackQueues]((q) => {
+    if (q && !q.resolved) {
+      fn.queueCount++;
+      q.push(fn);
+    }
+  });
+
+  if (!fn.queueCount) fn();
+  return promise;
+}
+
+type FederationContainer = {
+  init(shareScope: Record<string, unknown>): void | Promise<void>;
+  get(module: string): Promise<() => Exports>;
+};
+
+/** The share scopes of module federation remotes, by name. */
+const federationShareScopes: Record<string, Record<string, unknown>> = {};
+/** The share scope each remote container was initialized with. */
+const federationContainerScopes = new WeakMap<FederationContainer, string>();
+
+/**
+ * Imports the module `module` exposed by the module federation remote
+ * container `container`. The container is expected to be loaded as a global
+ * with its name, and is initialized with the share scope `shareScope` on its
+ * first import.
+ */
+async function federationImport(
+  container: string,
+  module: string,
+  shareScope: string = "default"
+): Promise<Exports> {
+  const remote = (globalThis as Record<string, any>)[container] as
+    | FederationContainer
+    | undefined;
+  if (remote == null) {
+    throw new Error(
+      `module federation remote container "${container}" is not loaded`
+    );
+  }
+  const initializedScope = federationContainerScopes.get(remote);
+  if (initializedScope == null) {
+    federationContainerScopes.set(remote, shareScope);
+    await remote.init((federationShareScopes[shareScope] ??= {}));
+  } else if (initializedScope !== shareScope) {
+    throw new Error(
+      `module federation remote container "${container}" was initialized with the share scope "${initializedScope}", not "${shareScope}"`
+    );
+  }
+  const factory = await remote.get(module);
+  return factory();
+}
+
 /**
  * A pseudo, `fake` URL object to resolve to the its relative path.
  * When urlrewritebehavior is set to relative, calls to the `new URL()` will construct url without base using this
@@ -1,9 +1,10 @@
 use std::{collections::BTreeMap, fmt::Display, mem::take};
 
+use anyhow::{bail, Context, Result};
 use indexmap::{IndexMap, IndexSet};
 use once_cell::sync::Lazy;
 use swc_core::{
-    common::{source_map::Pos, Span},
+    common::{source_map::Pos, Span, Spanned},
     ecma::{
         ast::*,
         atoms::{js_word, JsWord},
@@ -30,7 +31,84 @@ static ANNOTATION_TRANSITION: Lazy<JsWord> = Lazy::new(|| "transition".into());
 /// Changes the chunking type for the annotated import
 static ANNOTATION_CHUNKING_TYPE: Lazy<JsWord> = Lazy::new(|| "chunking-type".into());
 
+/// Bypasses the module cache for the annotated import
+static ANNOTATION_NO_CACHE: Lazy<JsWord> = Lazy::new(|| "no-cache".into());
+
+/// Requests a prefetch resource hint for the chunk of the annotated import
+static ANNOTATION_PREFETCH: Lazy<JsWord> = Lazy::new(|| "prefetch".into());
+
+/// Requests a preload resource hint for the chunk of the annotated import
+static ANNOTATION_PRELOAD: Lazy<JsWord> = Lazy::new(|| "preload".into());
+
+/// Binds `undefined` when the annotated external fails to load
+static ANNOTATION_OPTIONAL: Lazy<JsWord> = Lazy::new(|| "optional".into());
+
+/// Defers requiring the annotated external until its first use
+static ANNOTATION_LAZY: Lazy<JsWord> = Lazy::new(|| "lazy".into());
+
+/// Imports the annotated import from a module federation remote container
+static ANNOTATION_REMOTE: Lazy<JsWord> = Lazy::new(|| "remote".into());
+
+/// Names the share scope the remote container of the annotated import is
+/// initialized with
+static ANNOTATION_REMOTE_SCOPE: Lazy<JsWord> = Lazy::new(|| "remote-scope".into());
+
+/// Resolves the annotated import with other package export conditions
+static ANNOTATION_CONDITIONS: Lazy<JsWord> = Lazy::new(|| "conditions".into());
+
+/// The share scope of remote containers without a `remote-scope` annotation
+pub const DEFAULT_REMOTE_SCOPE: &str = "default";
+
+/// A resource hint requested for an import by an annotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportResourceHint {
+    Prefetch,
+    Preload,
+}
+
+/// A module exposed by a module federation remote container, requested by a
+/// `remote` annotation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRemote {
+    /// The name of the remote container, e.g. `app1`.
+    pub container: String,
+    /// The share scope the container is initialized with.
+    pub scope: String,
+    /// The module the container exposes, e.g. `./Button`.
+    pub module: String,
+}
+
 impl ImportAnnotations {
+    /// Parses annotations in the format of their [Display] implementation,
+    /// e.g. `{ chunking-type: parallel; no-cache }`, which it's the inverse
+    /// of.
+    ///
+    /// Keys and values can't contain `;`, and keys can't contain `: `, as
+    /// [Display] doesn't escape them.
+    pub fn parse(annotations: &str) -> Result<ImportAnnotations> {
+        let inner = annotations
+            .trim()
+            .strip_prefix('{')
+            .and_then(|annotations| annotations.strip_suffix('}'))
+            .context("import annotations must be enclosed in `{ }`")?
+            .trim();
+        let mut result = ImportAnnotations::default();
+        if inner.is_empty() {
+            return Ok(result);
+        }
+        for annotation in inner.split(';') {
+            let (key, value) = match annotation.split_once(": ") {
+                Some((key, value)) => (key.trim(), Some(value.trim().into())),
+                None => (annotation.trim(), None),
+            };
+            if key.is_empty() {
+                bail!("empty import annotation in `{annotations}`");
+            }
+            result.insert(key.into(), value);
+        }
+        Ok(result)
+    }
+
     fn insert(&mut self, key: JsWord, value: Option<JsWord>) {
         self.map.insert(key, value);
     }
@@ -52,6 +130,94 @@ impl ImportAnnotations {
             .get(&ANNOTATION_CHUNKING_TYPE)
             .and_then(|w| w.as_ref().map(|w| &**w))
     }
+
+    /// Returns a copy of these annotations without the chunking-type
+    /// annotation, so the import uses the default chunking type.
+    pub fn without_chunking_type(&self) -> ImportAnnotations {
+        let mut annotations = self.clone();
+        annotations.map.remove(&*ANNOTATION_CHUNKING_TYPE);
+        annotations
+    }
+
+    /// Returns true when the import is annotated with `no-cache` or
+    /// `no-cache: true`
+    pub fn no_cache(&self) -> bool {
+        self.flag(&ANNOTATION_NO_CACHE)
+    }
+
+    /// Returns true when the import is annotated with `optional` or
+    /// `optional: true`
+    pub fn optional(&self) -> bool {
+        self.flag(&ANNOTATION_OPTIONAL)
+    }
+
+    /// Returns true when the import is annotated with `lazy` or `lazy: true`
+    pub fn lazy(&self) -> bool {
+        self.flag(&ANNOTATION_LAZY)
+    }
+
+    /// Returns the remote module requested by a `remote` annotation, e.g.
+    /// `remote: app1/Button` for the module `./Button` exposed by the
+    /// container `app1`, like webpack's remote requests. Without a module
+    /// path, the container's `.` module is imported. The share scope is taken
+    /// from the `remote-scope` annotation, and is `default` without one.
+    pub fn remote(&self) -> Option<ImportRemote> {
+        let remote = self.map.get(&ANNOTATION_REMOTE)?.as_deref()?;
+        let (container, module) = match remote.split_once('/') {
+            Some((container, module)) => (container, format!("./{module}")),
+            None => (remote, ".".to_string()),
+        };
+        if container.is_empty() {
+            return None;
+        }
+        let scope = self
+            .map
+            .get(&ANNOTATION_REMOTE_SCOPE)
+            .and_then(|scope| scope.as_deref())
+            .unwrap_or(DEFAULT_REMOTE_SCOPE);
+        Some(ImportRemote {
+            container: container.to_string(),
+            scope: scope.to_string(),
+            module,
+        })
+    }
+
+    /// Returns the package export conditions requested by a `conditions`
+    /// annotation, e.g. `conditions: worker, browser`. They replace the
+    /// conditions of the resolve options for the annotated import.
+    pub fn conditions(&self) -> Option<Vec<String>> {
+        let conditions = self.map.get(&ANNOTATION_CONDITIONS)?.as_deref()?;
+        Some(
+            conditions
+                .split(',')
+                .map(|condition| condition.trim())
+                .filter(|condition| !condition.is_empty())
+                .map(|condition| condition.to_string())
+                .collect(),
+        )
+    }
+
+    /// Returns the resource hint requested by a `prefetch` or `preload`
+    /// annotation. Preloading wins when both are present.
+    pub fn resource_hint(&self) -> Option<ImportResourceHint> {
+        if self.flag(&ANNOTATION_PRELOAD) {
+            Some(ImportResourceHint::Preload)
+        } else if self.flag(&ANNOTATION_PREFETCH) {
+            Some(ImportResourceHint::Prefetch)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true when the annotation `key` is present without a value or
+    /// with `true`
+    fn flag(&self, key: &JsWord) -> bool {
+        match self.map.get(key) {
+            Some(None) => true,
+            Some(Some(value)) => &**value == "true",
+            None => false,
+        }
+    }
 }
 
 impl Display for ImportAnnotations {
@@ -77,6 +243,81 @@ impl Display for ImportAnnotations {
     }
 }
 
+/// The import attributes of an import, e.g. `with { type: "json" }`.
+#[turbo_tasks::value(shared)]
+#[derive(Default, Debug, Clone, Hash, PartialOrd, Ord)]
+pub struct ImportAttributes {
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl ImportAttributes {
+    /// Collects the string-valued attributes of a `with` (or legacy
+    /// `assert`) clause.
+    fn from_with_clause(with: Option<&ObjectLit>) -> Self {
+        let attributes = with
+            .into_iter()
+            .flat_map(|with| with.props.iter())
+            .filter_map(|prop| {
+                let PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp { key, value })) = prop
+                else {
+                    return None;
+                };
+                let key = match key {
+                    PropName::Ident(ident) => ident.sym.to_string(),
+                    PropName::Str(str) => str.value.to_string(),
+                    _ => return None,
+                };
+                let Expr::Lit(Lit::Str(value)) = &**value else {
+                    return None;
+                };
+                Some((key, value.value.to_string()))
+            })
+            .collect();
+        ImportAttributes { attributes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+
+    /// Returns the value of the `type` attribute
+    pub fn ty(&self) -> Option<&str> {
+        self.attributes.get("type").map(|ty| &**ty)
+    }
+
+    /// The span of the `type` attribute of a `with` clause, from its key to
+    /// its value.
+    fn type_span_in_with_clause(with: Option<&ObjectLit>) -> Option<Span> {
+        with.into_iter()
+            .flat_map(|with| with.props.iter())
+            .find_map(|prop| {
+                let PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp { key, value })) = prop
+                else {
+                    return None;
+                };
+                let is_type = match key {
+                    PropName::Ident(ident) => &*ident.sym == "type",
+                    PropName::Str(str) => &*str.value == "type",
+                    _ => false,
+                };
+                is_type.then(|| key.span().to(value.span()))
+            })
+    }
+}
+
+impl Display for ImportAttributes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("with {")?;
+        for (i, (key, value)) in self.attributes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, " {key}: {value:?}")?;
+        }
+        f.write_str(" }")
+    }
+}
+
 #[derive(Debug)]
 pub(crate) enum Reexport {
     Star,
@@ -118,7 +359,12 @@ pub(crate) struct ImportMapReference {
     pub module_path: JsWord,
     pub imported_symbol: ImportedSymbol,
     pub annotations: ImportAnnotations,
+    pub attributes: ImportAttributes,
+    /// The span of the `type` import attribute, if any. Mismatches of the
+    /// type are reported there.
+    pub type_attribute_span: Option<Span>,
     pub issue_source: Option<Vc<IssueSource>>,
+    pub span: Span,
 }
 
 impl ImportMap {
@@ -166,6 +412,21 @@ impl ImportMap {
         self.reexports.iter().map(|(i, r)| (*i, r))
     }
 
+    /// Returns the names that are imported or re-exported by name from the
+    /// reference with the given index.
+    pub fn imported_names(&self, reference: usize) -> IndexSet<&JsWord> {
+        let imports = self
+            .imports
+            .values()
+            .filter(|(i, _)| *i == reference)
+            .map(|(_, name)| name);
+        let reexports = self.reexports.iter().filter_map(|(i, r)| match r {
+            Reexport::Named { imported, .. } if *i == reference => Some(imported),
+            _ => None,
+        });
+        imports.chain(reexports).collect()
+    }
+
     /// Analyze ES import
     pub(super) fn analyze(m: &Program, source: Option<Vc<Box<dyn Source>>>) -> Self {
         let mut data = ImportMap::default();
@@ -193,6 +454,7 @@ impl<'a> Analyzer<'a> {
         module_path: JsWord,
         imported_symbol: ImportedSymbol,
         annotations: ImportAnnotations,
+        with: Option<&ObjectLit>,
     ) -> usize {
         let issue_source = self
             .source
@@ -203,6 +465,9 @@ impl<'a> Analyzer<'a> {
             imported_symbol,
             issue_source,
             annotations,
+            attributes: ImportAttributes::from_with_clause(with),
+            type_attribute_span: ImportAttributes::type_span_in_with_clause(with),
+            span,
         };
         if let Some(i) = self.data.references.get_index_of(&r) {
             i
@@ -263,6 +528,7 @@ impl Visit for Analyzer<'_> {
             import.src.value.clone(),
             ImportedSymbol::ModuleEvaluation,
             annotations.clone(),
+            import.with.as_deref(),
         );
 
         for s in &import.specifiers {
@@ -272,6 +538,7 @@ impl Visit for Analyzer<'_> {
                 import.src.value.clone(),
                 symbol,
                 annotations.clone(),
+                import.with.as_deref(),
             );
 
             let (local, orig_sym) = match s {
@@ -301,12 +568,14 @@ impl Visit for Analyzer<'_> {
             export.src.value.clone(),
             ImportedSymbol::ModuleEvaluation,
             annotations.clone(),
+            export.with.as_deref(),
         );
         let i = self.ensure_reference(
             export.span,
             export.src.value.clone(),
             ImportedSymbol::Namespace,
             annotations,
+            export.with.as_deref(),
         );
         self.data.reexports.push((i, Reexport::Star));
     }
@@ -321,6 +590,7 @@ impl Visit for Analyzer<'_> {
                 src.value.clone(),
                 ImportedSymbol::ModuleEvaluation,
                 annotations.clone(),
+                export.with.as_deref(),
             );
 
             for spec in export.specifiers.iter() {
@@ -331,6 +601,7 @@ impl Visit for Analyzer<'_> {
                     src.value.clone(),
                     symbol,
                     annotations.clone(),
+                    export.with.as_deref(),
                 );
 
                 match spec {
@@ -408,3 +679,229 @@ fn get_import_symbol_from_export(specifier: &ExportSpecifier) -> ImportedSymbol
         ExportSpecifier::Namespace(..) => ImportedSymbol::Namespace,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use swc_core::{
+        common::{FileName, SourceMap},
+        ecma::parser::{parse_file_as_program, EsConfig, Syntax},
+    };
+
+    use super::*;
+
+    #[test]
+    fn import_annotations_round_trip() {
+        let chunking_types = [None, Some("parallel"), Some("isolated"), Some("weak")];
+        let transitions = [None, Some("client"), Some("next-edge")];
+        let flags = [None, Some(None), Some(Some("true")), Some(Some("false"))];
+        for chunking_type in chunking_types {
+            for transition in transitions {
+                for no_cache in flags {
+                    let mut annotations = ImportAnnotations::default();
+                    if let Some(chunking_type) = chunking_type {
+                        annotations
+                            .insert(ANNOTATION_CHUNKING_TYPE.clone(), Some(chunking_type.into()));
+                    }
+                    if let Some(transition) = transition {
+                        annotations.insert(ANNOTATION_TRANSITION.clone(), Some(transition.into()));
+                    }
+                    if let Some(no_cache) = no_cache {
+                        annotations.insert(ANNOTATION_NO_CACHE.clone(), no_cache.map(Into::into));
+                    }
+
+                    let string = annotations.to_string();
+                    let parsed = ImportAnnotations::parse(&string).unwrap();
+                    assert_eq!(parsed, annotations, "{string}");
+                    assert_eq!(parsed.to_string(), string);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn import_annotations_display() {
+        let mut annotations = ImportAnnotations::default();
+        assert_eq!(annotations.to_string(), "{}");
+        annotations.insert(ANNOTATION_CHUNKING_TYPE.clone(), Some("parallel".into()));
+        annotations.insert(ANNOTATION_NO_CACHE.clone(), None);
+        assert_eq!(
+            annotations.to_string(),
+            "{ chunking-type: parallel; no-cache }"
+        );
+
+        assert!(ImportAnnotations::parse("chunking-type: parallel").is_err());
+        assert!(ImportAnnotations::parse("{ a; ; b }").is_err());
+    }
+
+    #[test]
+    fn import_attributes() {
+        let cm = SourceMap::default();
+        let src = "import a from \"./a.json\" with { type: \"json\" };\nimport b from \"./b\";\n";
+        let fm = cm.new_source_file(FileName::Anon, src.into());
+        let program = parse_file_as_program(
+            &fm,
+            Syntax::Es(EsConfig {
+                import_attributes: true,
+                ..Default::default()
+            }),
+            EsVersion::latest(),
+            None,
+            &mut vec![],
+        )
+        .unwrap();
+
+        let imports = ImportMap::analyze(&program, None);
+        let attributes = imports
+            .references()
+            .map(|r| (r.module_path.to_string(), r.attributes.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            attributes[0],
+            (
+                "./a.json".to_string(),
+                "with { type: \"json\" }".to_string()
+            )
+        );
+        assert_eq!(attributes[2], ("./b".to_string(), "with { }".to_string()));
+    }
+
+    #[test]
+    fn type_attribute_spans() {
+        let cm = SourceMap::default();
+        let src = "import data from \"./x.js\" with { type: \"json\" };\nimport b from \"./b\";\n";
+        let fm = cm.new_source_file(FileName::Anon, src.into());
+        let program = parse_file_as_program(
+            &fm,
+            Syntax::Es(EsConfig {
+                import_attributes: true,
+                ..Default::default()
+            }),
+            EsVersion::latest(),
+            None,
+            &mut vec![],
+        )
+        .unwrap();
+
+        let imports = ImportMap::analyze(&program, None);
+        let spans = imports
+            .references()
+            .map(|r| {
+                r.type_attribute_span.map(|span| {
+                    &src[(span.lo - fm.start_pos).to_usize()..(span.hi - fm.start_pos).to_usize()]
+                })
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            spans,
+            vec![Some("type: \"json\""), Some("type: \"json\""), None, None]
+        );
+    }
+
+    #[test]
+    fn reference_spans() {
+        let cm = SourceMap::default();
+        let src = "import a from \"./a\";\nimport { b } from \"./b\";\n";
+        let fm = cm.new_source_file(FileName::Anon, src.into());
+        let program = parse_file_as_program(
+            &fm,
+            Default::default(),
+            EsVersion::latest(),
+            None,
+            &mut vec![],
+        )
+        .unwrap();
+
+        let imports = ImportMap::analyze(&program, None);
+        let spans = imports
+            .references()
+            .map(|r| {
+                &src[(r.span.lo - fm.start_pos).to_usize()..(r.span.hi - fm.start_pos).to_usize()]
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            spans,
+            vec![
+                "import a from \"./a\";",
+                "import a from \"./a\";",
+                "import { b } from \"./b\";",
+                "import { b } from \"./b\";",
+            ]
+        );
+    }
+
+    #[test]
+    fn resource_hint_annotations() {
+        let cm = SourceMap::default();
+        let src = "\"TURBOPACK { prefetch }\";\nimport a from \"./a\";\n\"TURBOPACK { preload: \
+                   true }\";\nimport b from \"./b\";\nimport c from \"./c\";\n";
+        let fm = cm.new_source_file(FileName::Anon, src.into());
+        let program = parse_file_as_program(
+            &fm,
+            Default::default(),
+            EsVersion::latest(),
+            None,
+            &mut vec![],
+        )
+        .unwrap();
+
+        let imports = ImportMap::analyze(&program, None);
+        let hints = imports
+            .references()
+            .map(|r| (r.module_path.to_string(), r.annotations.resource_hint()))
+            .collect::<IndexMap<_, _>>();
+        assert_eq!(
+            hints,
+            IndexMap::from([
+                ("./a".to_string(), Some(ImportResourceHint::Prefetch)),
+                ("./b".to_string(), Some(ImportResourceHint::Preload)),
+                ("./c".to_string(), None),
+            ])
+        );
+    }
+
+    #[test]
+    fn remote_annotations() {
+        let remote = |annotations: &str| ImportAnnotations::parse(annotations).unwrap().remote();
+
+        assert_eq!(
+            remote("{ remote: app1/Button }"),
+            Some(ImportRemote {
+                container: "app1".to_string(),
+                scope: "default".to_string(),
+                module: "./Button".to_string(),
+            })
+        );
+        assert_eq!(
+            remote("{ remote: app1/ui/Button; remote-scope: legacy }"),
+            Some(ImportRemote {
+                container: "app1".to_string(),
+                scope: "legacy".to_string(),
+                module: "./ui/Button".to_string(),
+            })
+        );
+        assert_eq!(
+            remote("{ remote: app1 }").map(|remote| remote.module),
+            Some(".".to_string())
+        );
+        assert_eq!(remote("{}"), None);
+        assert_eq!(remote("{ remote }"), None);
+        assert_eq!(remote("{ remote: /Button }"), None);
+    }
+
+    #[test]
+    fn conditions_annotations() {
+        let conditions =
+            |annotations: &str| ImportAnnotations::parse(annotations).unwrap().conditions();
+
+        assert_eq!(
+            conditions("{ conditions: worker, browser }"),
+            Some(vec!["worker".to_string(), "browser".to_string()])
+        );
+        assert_eq!(
+            conditions("{ conditions: worker }"),
+            Some(vec!["worker".to_string()])
+        );
+        assert_eq!(conditions("{ conditions }"), None);
+        assert_eq!(conditions("{}"), None);
+    }
+}
@@ -6,6 +6,12 @@ use swc_core::{
     },
 };
 
+/// Checks if the program contains a top level await, i.e. an `await` or a
+/// `for await` outside of function and class bodies.
+pub fn detect_top_level_await(program: &Program) -> bool {
+    has_top_level_await(program).is_some()
+}
+
 /// Checks if the program contains a top level await, if it does it will returns
 /// the span of the first await.
 pub(crate) fn has_top_level_await(m: &Program) -> Option<Span> {
@@ -34,10 +40,16 @@ impl Visit for TopLevelAwaitVisitor {
         }
     }
 
+    fn visit_for_of_stmt(&mut self, n: &ForOfStmt) {
+        if n.is_await && self.top_level_await_span.is_none() {
+            self.top_level_await_span = Some(n.span);
+        }
+        n.visit_children_with(self);
+    }
+
     // prevent non top level items from visiting their children
     noop_visit_type!();
     noop!(visit_arrow_expr, ArrowExpr);
-    noop!(visit_constructor, Constructor);
     noop!(visit_function, Function);
 
     fn visit_getter_prop(&mut self, n: &GetterProp) {
@@ -48,26 +60,45 @@ impl Visit for TopLevelAwaitVisitor {
         n.key.visit_children_with(self);
     }
 
-    fn visit_class_prop(&mut self, n: &ClassProp) {
-        n.key.visit_children_with(self);
+    // Only the decorators and the superclass are evaluated outside of the
+    // class body.
+    fn visit_class(&mut self, n: &Class) {
         n.decorators.visit_children_with(self);
-        if n.is_static {
-            n.value.visit_children_with(self);
-        }
+        n.super_class.visit_children_with(self);
     }
+}
 
-    fn visit_private_prop(&mut self, n: &PrivateProp) {
-        n.decorators.visit_children_with(self);
-        if n.is_static {
-            n.value.visit_children_with(self);
-        }
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use swc_core::common::SourceMap;
+
+    use super::*;
+    use crate::references::esm::base::tests::parse;
+
+    fn detect(src: &str) -> bool {
+        let cm = Arc::new(SourceMap::default());
+        detect_top_level_await(&parse(&cm, &format!("export {{}};\n{src}")))
     }
 
-    fn visit_auto_accessor(&mut self, n: &AutoAccessor) {
-        n.key.visit_children_with(self);
-        n.decorators.visit_children_with(self);
-        if n.is_static {
-            n.value.visit_children_with(self);
-        }
+    #[test]
+    fn top_level_await() {
+        assert!(detect("await x;"));
+        assert!(detect("if (a) { const b = await x; }"));
+        assert!(detect("for await (const a of x) {}"));
+        assert!(detect("class A extends (await x) {}"));
+    }
+
+    #[test]
+    fn nested_await() {
+        assert!(!detect("async function f() { await x; }"));
+        assert!(!detect(
+            "const f = async () => { for await (const a of x) {} };"
+        ));
+        assert!(!detect("class A { async m() { await x; } }"));
+        assert!(!detect("class A { static a = await x; }"));
+        assert!(!detect("const o = { async m() { await x; } };"));
+        assert!(!detect("export {};"));
     }
 }
@@ -1,6 +1,8 @@
 use turbo_tasks::Vc;
 use turbopack_core::chunk::ChunkingContext;
 
+use crate::{magic_identifier, references::esm::EsmNamespaceShape};
+
 /// [`EcmascriptChunkingContext`] must be implemented by [`ChunkingContext`]
 /// implementors that want to operate on [`EcmascriptChunk`]s.
 #[turbo_tasks::value_trait]
@@ -10,4 +12,213 @@ pub trait EcmascriptChunkingContext: ChunkingContext {
     fn has_react_refresh(self: Vc<Self>) -> Vc<bool> {
         Vc::cell(false)
     }
+
+    /// Whether generated imports should count their evaluation in the global
+    /// `__turbopack_import_coverage__` registry. This is meant for coverage
+    /// tooling in development and tests and must stay disabled for production
+    /// output.
+    fn import_coverage_instrumentation(self: Vc<Self>) -> Vc<bool> {
+        Vc::cell(false)
+    }
+
+    /// Whether generated imports should be preceded by a comment naming the
+    /// request and the importing module, e.g.
+    /// `/* import ./foo from app/page.js */`. This is meant for debugging the
+    /// generated code.
+    fn import_debug_comments(self: Vc<Self>) -> Vc<bool> {
+        Vc::cell(false)
+    }
+
+    /// The shape of the namespace objects bound by namespace imports.
+    fn esm_namespace_shape(self: Vc<Self>) -> Vc<EsmNamespaceShape> {
+        EsmNamespaceShape::default().cell()
+    }
+
+    /// Whether async modules should await their async dependencies only after
+    /// all hoisted imports have been evaluated. Without this barrier, the
+    /// order of the await relative to synchronous import side effects depends
+    /// on the order in which the code generation is applied.
+    fn esm_evaluation_barrier(self: Vc<Self>) -> Vc<bool> {
+        Vc::cell(false)
+    }
+
+    /// Whether imported modules should be bound to short idents derived from a
+    /// hash of their readable ident, see [ReferencedAsset::get_stable_ident].
+    /// The readable idents are easier to debug, so this is meant for
+    /// production output.
+    ///
+    /// [ReferencedAsset::get_stable_ident]: crate::references::esm::base::ReferencedAsset::get_stable_ident
+    fn stable_import_idents(self: Vc<Self>) -> Vc<bool> {
+        Vc::cell(false)
+    }
+
+    /// Whether imports of modules that import the importing module back,
+    /// directly or transitively, should check at runtime that exports aren't
+    /// read before the imported module finished evaluating. Detecting the
+    /// cycles traverses the module graph, so this is meant for development.
+    fn esm_cycle_checks(self: Vc<Self>) -> Vc<bool> {
+        Vc::cell(false)
+    }
+
+    /// Whether named imports of internal modules should read the export with
+    /// `__turbopack_esm_import__(id, "name")` on every access. Reassignments
+    /// of `let` exports in the exporting module are then observable as
+    /// required by the spec, without relying on the namespace object
+    /// exposing its exports through getters.
+    fn esm_live_bindings(self: Vc<Self>) -> Vc<bool> {
+        Vc::cell(false)
+    }
+
+    /// The number of milliseconds after which async modules warn that their
+    /// async dependencies haven't resolved yet. This is meant for diagnosing
+    /// async modules that hang. Without a timeout, the async dependency
+    /// handler is called without options.
+    fn async_dependency_timeout_ms(self: Vc<Self>) -> Vc<Option<u32>> {
+        Vc::cell(None)
+    }
+
+    /// Whether async loaders pass the path of the chunk containing the module
+    /// they load to its import, e.g. `__turbopack_import__(id, "chunk.js")`,
+    /// so the runtime doesn't have to discover it, e.g. for resource hints.
+    /// Finding the chunk chunks the loaded chunk group while generating the
+    /// loader.
+    fn import_target_chunks(self: Vc<Self>) -> Vc<bool> {
+        Vc::cell(false)
+    }
+
+    /// The maximum size in bytes of JSON modules whose default import is
+    /// inlined into the importing module as an object literal, instead of
+    /// importing the module at runtime. Without a size, JSON modules are
+    /// always imported at runtime.
+    fn inline_json_max_size(self: Vc<Self>) -> Vc<Option<u32>> {
+        Vc::cell(None)
+    }
+
+    /// Whether the default import of a JSON module annotated with
+    /// `chunking-type: eager` is inlined into the importing module, whatever
+    /// its size. This is the only kind of eager import that can be inlined:
+    /// ECMAScript modules can't be, so eager imports of anything but JSON
+    /// modules are imported like parallel ones either way.
+    fn inline_eager_json_imports(self: Vc<Self>) -> Vc<bool> {
+        Vc::cell(false)
+    }
+
+    /// The names of the runtime functions called by generated imports.
+    fn runtime_function_names(self: Vc<Self>) -> Vc<RuntimeFunctionNames> {
+        RuntimeFunctionNames::default().cell()
+    }
+
+    /// The markers that delimit the hoisted statements of the modules of this
+    /// chunking context.
+    fn hoisting_markers(self: Vc<Self>) -> Vc<HoistingMarkers> {
+        HoistingMarkers::default().cell()
+    }
+}
+
+/// The names of the runtime functions generated imports call. Overriding them
+/// allows embedding the output in environments where the runtime globals are
+/// renamed.
+#[turbo_tasks::value(shared, serialization = "auto_for_input")]
+#[derive(Debug, Clone, Hash, PartialOrd, Ord)]
+pub struct RuntimeFunctionNames {
+    /// Imports a module by its id, defaults to `__turbopack_import__`.
+    pub import: String,
+    /// Imports a module by its id without the module cache, defaults to
+    /// `__turbopack_import_uncached__`.
+    pub import_uncached: String,
+    /// Imports the namespace object of a module by its id for a namespace
+    /// import, defaults to `__turbopack_import_namespace__`.
+    pub import_namespace: String,
+    /// Reads an export of a module by its id for a live binding, defaults to
+    /// `__turbopack_esm_import__`.
+    pub esm_import: String,
+    /// Imports an ESM external, defaults to `__turbopack_external_import__`.
+    pub external_import: String,
+    /// Requires an external, defaults to `__turbopack_external_require__`.
+    pub external_require: String,
+    /// Binds an external that's required on the first access to one of its
+    /// exports, defaults to `__turbopack_external_lazy__`.
+    pub external_lazy: String,
+    /// Imports a module exposed by a module federation remote container,
+    /// defaults to `__turbopack_federation_import__`. The container has to be
+    /// loaded as a global with its name.
+    pub federation_import: String,
+    /// Requires a Node.js builtin by its name without the `node:` protocol,
+    /// defaults to `__turbopack_require_builtin__`.
+    pub require_builtin: String,
+    /// Prepares awaiting the async dependencies of an async module, defaults
+    /// to `__turbopack_handle_async_dependencies__`.
+    pub handle_async_dependencies: String,
+    /// Registers a prefetch resource hint, defaults to
+    /// `__turbopack_register_prefetch__`.
+    pub register_prefetch: String,
+    /// Registers a preload resource hint, defaults to
+    /// `__turbopack_register_preload__`.
+    pub register_preload: String,
+    /// Prefetches the chunks of a module when the browser is idle, without
+    /// blocking evaluation, defaults to `__turbopack_prefetch__`.
+    pub prefetch: String,
+    /// The module factories by module id, defaults to
+    /// `__turbopack_modules__`.
+    pub modules: String,
+}
+
+impl Default for RuntimeFunctionNames {
+    fn default() -> Self {
+        Self {
+            import: "__turbopack_import__".to_string(),
+            import_uncached: "__turbopack_import_uncached__".to_string(),
+            import_namespace: "__turbopack_import_namespace__".to_string(),
+            esm_import: "__turbopack_esm_import__".to_string(),
+            external_import: "__turbopack_external_import__".to_string(),
+            external_require: "__turbopack_external_require__".to_string(),
+            external_lazy: "__turbopack_external_lazy__".to_string(),
+            federation_import: "__turbopack_federation_import__".to_string(),
+            require_builtin: "__turbopack_require_builtin__".to_string(),
+            handle_async_dependencies: "__turbopack_handle_async_dependencies__".to_string(),
+            register_prefetch: "__turbopack_register_prefetch__".to_string(),
+            register_preload: "__turbopack_register_preload__".to_string(),
+            prefetch: "__turbopack_prefetch__".to_string(),
+            modules: "__turbopack_modules__".to_string(),
+        }
+    }
+}
+
+/// The markers that end the regions of hoisted statements in a module, see
+/// [HoistingAnchor]. They are string literal statements that are part of the
+/// code, so code generation only recognizes the markers of its own chunking
+/// context. Chunking contexts with different magic identifier prefixes don't
+/// see each other's regions when they run in the same process.
+///
+/// [HoistingAnchor]: crate::references::esm::hoisting::HoistingAnchor
+#[turbo_tasks::value(shared, serialization = "auto_for_input")]
+#[derive(Debug, Clone, Hash, PartialOrd, Ord)]
+pub struct HoistingMarkers {
+    /// Ends the region of setup that runs before the imports.
+    pub prologue: String,
+    /// Ends the region of the import bindings.
+    pub imports: String,
+}
+
+impl HoistingMarkers {
+    /// The markers for magic identifiers starting with `magic_prefix`, see
+    /// [magic_identifier::mangle_with_prefix].
+    pub fn new(magic_prefix: &str) -> Self {
+        Self {
+            prologue: magic_identifier::mangle_with_prefix(
+                magic_prefix,
+                "ecmascript prologue location",
+            ),
+            imports: magic_identifier::mangle_with_prefix(
+                magic_prefix,
+                "ecmascript hoisting location",
+            ),
+        }
+    }
+}
+
+impl Default for HoistingMarkers {
+    fn default() -> Self {
+        Self::new("__TURBOPACK__")
+    }
 }
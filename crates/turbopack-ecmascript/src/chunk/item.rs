@@ -37,6 +37,7 @@ impl EcmascriptChunkItemContent {
         async_module_options: Vc<OptionAsyncModuleOptions>,
     ) -> Result<Vc<Self>> {
         let refresh = *chunking_context.has_react_refresh().await?;
+        let live_bindings = *chunking_context.esm_live_bindings().await?;
         let externals = *chunking_context
             .environment()
             .supports_commonjs_externals()
@@ -49,21 +50,30 @@ impl EcmascriptChunkItemContent {
             inner_code: content.inner_code.clone(),
             source_map: content.source_map,
             options: if content.is_esm {
+                // ES modules with value exports get the CommonJS wrapper, which
+                // needs `module` and `exports`.
+                let is_commonjs = matches!(&async_module, Some(opts) if opts.is_commonjs);
                 EcmascriptChunkItemOptions {
                     strict: true,
                     refresh,
                     externals,
                     async_module,
+                    live_bindings,
+                    async_import: content.async_import,
+                    module: is_commonjs,
+                    exports: is_commonjs,
                     ..Default::default()
                 }
             } else {
-                if async_module.is_some() {
+                if matches!(&async_module, Some(opts) if !opts.is_commonjs) {
                     bail!("CJS module can't be async.");
                 }
 
                 EcmascriptChunkItemOptions {
                     refresh,
                     externals,
+                    async_module,
+                    async_import: content.async_import,
                     // These things are not available in ESM
                     module: true,
                     exports: true,
@@ -84,6 +94,12 @@ impl EcmascriptChunkItemContent {
             "r: __turbopack_require__",
             "f: __turbopack_require_context__",
             "i: __turbopack_import__",
+            "I: __turbopack_import_namespace__",
+            "C: __turbopack_import_uncached__",
+            "h: __turbopack_register_prefetch__",
+            "H: __turbopack_register_preload__",
+            "F: __turbopack_prefetch__",
+            "o: __turbopack_federation_import__",
             "s: __turbopack_esm__",
             "v: __turbopack_export_value__",
             "n: __turbopack_export_namespace__",
@@ -91,6 +107,7 @@ impl EcmascriptChunkItemContent {
             "M: __turbopack_modules__",
             "l: __turbopack_load__",
             "j: __turbopack_dynamic__",
+            "N: __turbopack_reexport_namespace__",
             "p: __turbopack_lookup__",
             "P: __turbopack_resolve_absolute_path__",
             "U: __turbopack_relative_url__",
@@ -105,10 +122,18 @@ impl EcmascriptChunkItemContent {
         if this.options.externals {
             args.push("x: __turbopack_external_require__");
             args.push("y: __turbopack_external_import__");
+            args.push("X: __turbopack_external_lazy__");
+            args.push("b: __turbopack_require_builtin__");
         }
         if this.options.refresh {
             args.push("k: __turbopack_refresh__");
         }
+        if this.options.live_bindings {
+            args.push("E: __turbopack_esm_import__");
+        }
+        if this.options.async_import {
+            args.push("A: __turbopack_async_import__");
+        }
         if this.options.module {
             args.push("m: module");
         }
@@ -135,20 +160,46 @@ impl EcmascriptChunkItemContent {
             code += "\n";
         }
 
-        if this.options.async_module.is_some() {
-            code += "__turbopack_async_module__(async (__turbopack_handle_async_dependencies__, \
-                     __turbopack_async_result__) => { try {\n";
+        match &this.options.async_module {
+            Some(opts) if opts.is_commonjs => {
+                // `__turbopack_async_module__` replaces `module.exports` with
+                // the async module promise, but CommonJS code assigns to it. The
+                // final value is passed on to resolve the promise with.
+                code += "__turbopack_async_module__(async \
+                         (__turbopack_handle_async_dependencies__, __turbopack_async_result__) => \
+                         { var __turbopack_async_promise__ = module.exports; module.exports = \
+                         exports; try {\n";
+            }
+            Some(_) => {
+                code += "__turbopack_async_module__(async \
+                         (__turbopack_handle_async_dependencies__, __turbopack_async_result__) => \
+                         { try {\n";
+            }
+            None => {}
         }
 
         code.push_source(&this.inner_code, this.source_map);
 
-        if let Some(opts) = &this.options.async_module {
-            write!(
-                code,
-                "__turbopack_async_result__();\n}} catch(e) {{ __turbopack_async_result__(e); }} \
-                 }}, {});",
-                opts.has_top_level_await
-            )?;
+        match &this.options.async_module {
+            Some(opts) if opts.is_commonjs => {
+                write!(
+                    code,
+                    "var __turbopack_cjs_exports__ = module.exports;\nmodule.exports = \
+                     __turbopack_async_promise__;\n__turbopack_async_result__(undefined, \
+                     __turbopack_cjs_exports__);\n}} catch(e) {{ module.exports = \
+                     __turbopack_async_promise__; __turbopack_async_result__(e); }} }}, {});",
+                    opts.has_top_level_await
+                )?;
+            }
+            Some(opts) => {
+                write!(
+                    code,
+                    "__turbopack_async_result__();\n}} catch(e) {{ __turbopack_async_result__(e); \
+                     }} }}, {});",
+                    opts.has_top_level_await
+                )?;
+            }
+            None => {}
         }
 
         if this.options.this {
@@ -186,6 +237,12 @@ pub struct EcmascriptChunkItemOptions {
     /// Whether this chunk item's module factory should include
     /// `__turbopack_wasm__` to load WebAssembly.
     pub wasm: bool,
+    /// Whether this chunk item's module factory should include
+    /// `__turbopack_esm_import__` to read live bindings of named imports.
+    pub live_bindings: bool,
+    /// Whether this chunk item's module factory should include
+    /// `__turbopack_async_import__` to dynamically import async modules.
+    pub async_import: bool,
     pub placeholder_for_future_extensions: (),
 }
 
@@ -268,3 +325,68 @@ pub struct EcmascriptChunkItemsChunk(Vec<Vc<Box<dyn EcmascriptChunkItem>>>);
 
 #[turbo_tasks::value(transparent)]
 pub struct EcmascriptChunkItems(pub(super) Vec<Vc<Box<dyn EcmascriptChunkItem>>>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn module_factory_code(async_module: AsyncModuleOptions, esm: bool) -> String {
+        crate::register();
+        turbo_tasks_testing::VcStorage::with(async move {
+            let content = EcmascriptChunkItemContent {
+                inner_code: Rope::from("body();\n".to_string()),
+                options: EcmascriptChunkItemOptions {
+                    strict: esm,
+                    module: !esm,
+                    exports: !esm,
+                    async_module: Some(async_module),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+            .cell();
+            let code = content.module_factory().await.unwrap();
+            code.source_code().to_str().unwrap().into_owned()
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn esm_async_module_wrapper() {
+        let code = module_factory_code(
+            AsyncModuleOptions {
+                has_top_level_await: true,
+                is_commonjs: false,
+            },
+            true,
+        )
+        .await;
+        assert!(code.contains(
+            "__turbopack_async_module__(async (__turbopack_handle_async_dependencies__, \
+             __turbopack_async_result__) => { try {\nbody();\n__turbopack_async_result__();\n} \
+             catch(e) { __turbopack_async_result__(e); } }, true);"
+        ));
+    }
+
+    #[tokio::test]
+    async fn commonjs_async_module_wrapper() {
+        let code = module_factory_code(
+            AsyncModuleOptions {
+                has_top_level_await: false,
+                is_commonjs: true,
+            },
+            false,
+        )
+        .await;
+        assert!(code.contains("m: module, e: exports"));
+        assert!(code.contains(
+            "__turbopack_async_module__(async (__turbopack_handle_async_dependencies__, \
+             __turbopack_async_result__) => { var __turbopack_async_promise__ = module.exports; \
+             module.exports = exports; try {\nbody();\nvar __turbopack_cjs_exports__ = \
+             module.exports;\nmodule.exports = \
+             __turbopack_async_promise__;\n__turbopack_async_result__(undefined, \
+             __turbopack_cjs_exports__);\n} catch(e) { module.exports = \
+             __turbopack_async_promise__; __turbopack_async_result__(e); } }, false);"
+        ));
+    }
+}
@@ -26,13 +26,16 @@ use turbopack_core::{
 pub use self::{
     chunk_type::EcmascriptChunkType,
     content::EcmascriptChunkContent,
-    context::EcmascriptChunkingContext,
+    context::{EcmascriptChunkingContext, HoistingMarkers, RuntimeFunctionNames},
     data::EcmascriptChunkData,
     item::{
         EcmascriptChunkItem, EcmascriptChunkItemContent, EcmascriptChunkItemExt,
         EcmascriptChunkItemOptions,
     },
-    placeable::{EcmascriptChunkPlaceable, EcmascriptChunkPlaceables, EcmascriptExports},
+    placeable::{
+        EcmascriptChunkPlaceable, EcmascriptChunkPlaceables, EcmascriptExports,
+        OptionCssModuleClassNames, OptionEcmascriptChunkPlaceable,
+    },
 };
 
 #[turbo_tasks::value]
@@ -24,6 +24,16 @@ pub trait EcmascriptChunkPlaceable: ChunkableModule + Module + Asset {
     fn is_marked_as_side_effect_free(self: Vc<Self>) -> Vc<bool> {
         is_marked_as_side_effect_free(self.ident().path())
     }
+    /// Whether the module exports the JSON value of its content, which allows
+    /// inlining the value into importing modules.
+    fn is_json(self: Vc<Self>) -> Vc<bool> {
+        Vc::cell(false)
+    }
+    /// The class names exported by a CSS module, or `None` when the module
+    /// isn't one. Imports of a CSS module bind the map of these class names.
+    fn css_module_class_names(self: Vc<Self>) -> Vc<OptionCssModuleClassNames> {
+        Vc::cell(None)
+    }
 }
 
 #[turbo_tasks::value]
@@ -170,6 +180,14 @@ pub async fn is_marked_as_side_effect_free(path: Vc<FileSystemPath>) -> Result<V
     Ok(Vc::cell(false))
 }
 
+#[turbo_tasks::value(transparent)]
+pub struct OptionEcmascriptChunkPlaceable(Option<Vc<Box<dyn EcmascriptChunkPlaceable>>>);
+
+/// The class names exported by a CSS module, see
+/// [EcmascriptChunkPlaceable::css_module_class_names].
+#[turbo_tasks::value(transparent)]
+pub struct OptionCssModuleClassNames(Option<Vec<String>>);
+
 #[turbo_tasks::value(transparent)]
 pub struct EcmascriptChunkPlaceables(Vec<Vc<Box<dyn EcmascriptChunkPlaceable>>>);
 
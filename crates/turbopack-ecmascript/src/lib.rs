@@ -41,7 +41,7 @@ pub use references::{AnalyzeEcmascriptModuleResult, TURBOPACK_HELPER};
 use serde::{Deserialize, Serialize};
 pub use static_code::StaticEcmascriptCode;
 use swc_core::{
-    common::GLOBALS,
+    common::{comments::Comments, GLOBALS},
     ecma::{
         codegen::{text_writer::JsWriter, Emitter},
         visit::{VisitMutWith, VisitMutWithPath},
@@ -67,7 +67,7 @@ use turbopack_core::{
     reference::ModuleReferences,
     reference_type::InnerAssets,
     resolve::{
-        find_context_file, origin::ResolveOrigin, package_json, parse::Request,
+        find_context_file, options::ImportMap, origin::ResolveOrigin, package_json, parse::Request,
         FindContextFileResult, ModulePart,
     },
     source::Source,
@@ -75,13 +75,20 @@ use turbopack_core::{
 };
 
 use self::{
-    chunk::{EcmascriptChunkItemContent, EcmascriptChunkType, EcmascriptExports},
+    chunk::{EcmascriptChunkItemContent, EcmascriptChunkType, EcmascriptExports, HoistingMarkers},
     code_gen::{CodeGen, CodeGenerateableWithAsyncModuleInfo, CodeGenerateables, VisitorFactory},
     tree_shake::asset::EcmascriptModulePartAsset,
 };
 use crate::{
     chunk::EcmascriptChunkPlaceable,
-    references::{analyse_ecmascript_module, async_module::OptionAsyncModule},
+    references::{
+        analyse_ecmascript_module,
+        async_module::OptionAsyncModule,
+        esm::{
+            hoisting::{ignore_list_hoisted_stmts, take_import_comments},
+            EsmAsyncAssetReference,
+        },
+    },
     transform::remove_shebang,
 };
 
@@ -129,6 +136,12 @@ pub struct EcmascriptOptions {
     /// External imports should used `__turbopack_import__` instead of
     /// `__turbopack_require__` and become async module references.
     pub import_externals: bool,
+    /// Unknown `chunking-type` import annotations fail the build instead of
+    /// emitting a warning.
+    pub strict_import_annotations: bool,
+    /// A browser-style import map ESM imports are resolved with, see
+    /// [resolve::browser_import_map].
+    pub import_map: Option<Vc<ImportMap>>,
 }
 
 #[turbo_tasks::value(serialization = "auto_for_input")]
@@ -686,7 +699,7 @@ impl EcmascriptChunkItem for ModuleChunkItem {
         let async_module_options = this
             .module
             .get_async_module()
-            .module_options(async_module_info);
+            .module_options(async_module_info, false);
 
         // TODO check if we need to pass async_module_info at all
         let content = this
@@ -707,6 +720,9 @@ pub struct EcmascriptModuleContent {
     pub inner_code: Rope,
     pub source_map: Option<Vc<Box<dyn GenerateSourceMap>>>,
     pub is_esm: bool,
+    /// Whether the module dynamically imports an async module, which is
+    /// awaited with `__turbopack_async_import__`.
+    pub async_import: bool,
 }
 
 #[turbo_tasks::value_impl]
@@ -725,8 +741,12 @@ impl EcmascriptModuleContent {
         async_module_info: Option<Vc<AsyncModuleInfo>>,
     ) -> Result<Vc<Self>> {
         let mut code_gens = Vec::new();
+        let mut async_import = false;
         for r in references.await?.iter() {
             let r = r.resolve().await?;
+            if let Some(r) = Vc::try_resolve_downcast_type::<EsmAsyncAssetReference>(r).await? {
+                async_import |= *r.is_target_async().await?;
+            }
             if let Some(code_gen) =
                 Vc::try_resolve_sidecast::<Box<dyn CodeGenerateableWithAsyncModuleInfo>>(r).await?
             {
@@ -766,6 +786,7 @@ impl EcmascriptModuleContent {
             }
         }
 
+        let hoisting_markers = chunking_context.hoisting_markers().await?;
         gen_content_with_visitors(
             parsed,
             ident,
@@ -773,6 +794,8 @@ impl EcmascriptModuleContent {
             visitors,
             root_visitors,
             source_map,
+            &hoisting_markers,
+            async_import,
         )
         .await
     }
@@ -791,11 +814,18 @@ impl EcmascriptModuleContent {
             Vec::new(),
             Vec::new(),
             OptionSourceMap::none(),
+            &HoistingMarkers::default(),
+            false,
         )
         .await
     }
 }
 
+/// Applies the code generation `visitors` at their AST paths, e.g. to rewrite
+/// import bindings, and then the `root_visitors` to the whole program. Root
+/// visitors can rely on that order, e.g. to check whether an import binding
+/// is used.
+#[allow(clippy::too_many_arguments)]
 async fn gen_content_with_visitors(
     parsed: Vc<ParseResult>,
     ident: Vc<AssetIdent>,
@@ -806,6 +836,8 @@ async fn gen_content_with_visitors(
     )>,
     root_visitors: Vec<&dyn VisitorFactory>,
     original_src_map: Vc<OptionSourceMap>,
+    hoisting_markers: &HoistingMarkers,
+    async_import: bool,
 ) -> Result<Vc<EcmascriptModuleContent>> {
     let parsed = parsed.await?;
 
@@ -819,6 +851,7 @@ async fn gen_content_with_visitors(
     } = &*parsed
     {
         let mut program = program.clone();
+        let mut import_comments = Vec::new();
 
         GLOBALS.set(globals, || {
             if !visitors.is_empty() {
@@ -830,6 +863,8 @@ async fn gen_content_with_visitors(
             for visitor in root_visitors {
                 program.visit_mut_with(&mut visitor.create());
             }
+            import_comments = take_import_comments(&mut program, source_map);
+            ignore_list_hoisted_stmts(&mut program, source_map, hoisting_markers);
             program.visit_mut_with(&mut swc_core::ecma::transforms::base::hygiene::hygiene());
             program.visit_mut_with(&mut swc_core::ecma::transforms::base::fixer::fixer(None));
 
@@ -845,6 +880,9 @@ async fn gen_content_with_visitors(
         let mut mappings = vec![];
 
         let comments = comments.consumable();
+        for (pos, comment) in import_comments {
+            comments.add_leading(pos, comment);
+        }
 
         let mut emitter = Emitter {
             cfg: swc_core::ecma::codegen::Config::default(),
@@ -863,6 +901,7 @@ async fn gen_content_with_visitors(
             source_map: Some(Vc::upcast(srcmap)),
             is_esm: eval_context.is_esm()
                 || specified_module_type == SpecifiedModuleType::EcmaScript,
+            async_import,
         }
         .cell())
     } else {
@@ -875,6 +914,7 @@ async fn gen_content_with_visitors(
             .into(),
             source_map: None,
             is_esm: false,
+            async_import: false,
         }
         .cell())
     }
@@ -7,9 +7,17 @@ use once_cell::sync::Lazy;
 use regex::{Captures, Regex, Replacer};
 
 pub fn mangle(content: &str) -> String {
-    let mut r = "__TURBOPACK__".to_string();
+    mangle_with_prefix("__TURBOPACK__", content)
+}
+
+/// Like [mangle], but starts the identifier with `prefix` instead of
+/// `__TURBOPACK__`. Only identifiers with the default prefix can be decoded
+/// with [unmangle].
+pub fn mangle_with_prefix(prefix: &str, content: &str) -> String {
+    let mut r = prefix.to_string();
     let mut hex_mode = false;
-    for c in content.chars() {
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
         if matches!(c, '0'..='9' | 'A'..='Z' | 'a'..='z' | ' ') {
             if hex_mode {
                 r.push('$');
@@ -20,7 +28,9 @@ pub fn mangle(content: &str) -> String {
             } else {
                 r.push(c);
             }
-        } else if c == '_' && (!r.ends_with('_') || hex_mode) {
+        } else if c == '_' && (!r.ends_with('_') || hex_mode) && chars.peek() != Some(&' ') {
+            // An underscore followed by a space would read as a space followed
+            // by an underscore, so it's hex encoded instead.
             if hex_mode {
                 r.push('$');
                 hex_mode = false;
@@ -33,7 +43,7 @@ pub fn mangle(content: &str) -> String {
                 r.push('$');
                 hex_mode = true;
             }
-            write!(r, "{0:2x}", c as u8).unwrap();
+            write!(r, "{0:02x}", c as u8).unwrap();
         } else {
             if !hex_mode {
                 r.push('$');
@@ -49,20 +59,28 @@ pub fn mangle(content: &str) -> String {
     r
 }
 
-/// Decodes a magic identifier into a string.
-pub fn unmangle(identifier: &str) -> String {
-    static DECODE_REGEX: Lazy<Regex> =
-        Lazy::new(|| Regex::new(r"^__TURBOPACK__([a-zA-Z0-9_$]+)__$").unwrap());
+static DECODE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^__TURBOPACK__([a-zA-Z0-9_$]*)__$").unwrap());
+
+/// Returns true if `identifier` has the shape of a magic identifier created by
+/// [mangle].
+pub fn is_magic(identifier: &str) -> bool {
+    DECODE_REGEX.is_match(identifier)
+}
 
-    let Some(captures) = DECODE_REGEX.captures(identifier) else {
-        return identifier.to_string();
-    };
+/// Decodes a magic identifier into the descriptor it was created from.
+///
+/// Returns `None` if `identifier` is not a magic identifier or its encoding is
+/// malformed.
+pub fn unmangle(identifier: &str) -> Option<String> {
+    let captures = DECODE_REGEX.captures(identifier)?;
 
     let content = captures.get(1).unwrap().as_str();
 
     enum Mode {
         Text,
         Underscore,
+        HexStart,
         Hex,
         LongHex,
     }
@@ -73,7 +91,7 @@ pub fn unmangle(identifier: &str) -> String {
         match mode {
             Mode::Text => match char {
                 '_' => mode = Mode::Underscore,
-                '$' => mode = Mode::Hex,
+                '$' => mode = Mode::HexStart,
                 c => output.push(c),
             },
             Mode::Underscore => match char {
@@ -83,7 +101,7 @@ pub fn unmangle(identifier: &str) -> String {
                 }
                 '$' => {
                     output.push('_');
-                    mode = Mode::Hex;
+                    mode = Mode::HexStart;
                 }
                 c => {
                     output.push('_');
@@ -91,46 +109,50 @@ pub fn unmangle(identifier: &str) -> String {
                     mode = Mode::Text;
                 }
             },
+            Mode::HexStart => match char {
+                // `$$` encodes a literal `$`.
+                '$' => {
+                    output.push('$');
+                    mode = Mode::Text;
+                }
+                '_' => mode = Mode::LongHex,
+                c => {
+                    buffer.push(c);
+                    mode = Mode::Hex;
+                }
+            },
             Mode::Hex => {
                 if buffer.len() == 2 {
-                    if let Ok(byte) = u8::from_str_radix(&buffer, 16) {
-                        output.push(byte as char);
-                    }
+                    let byte = u8::from_str_radix(&buffer, 16).ok()?;
+                    output.push(byte as char);
                     buffer.clear();
                 }
                 match char {
-                    '_' => {
-                        debug_assert!(buffer.is_empty());
-                        mode = Mode::LongHex;
-                    }
-                    '$' => {
-                        debug_assert!(buffer.is_empty());
-                        mode = Mode::Text;
-                    }
-                    c => {
-                        buffer.push(c);
-                    }
+                    '_' if buffer.is_empty() => mode = Mode::LongHex,
+                    '$' if buffer.is_empty() => mode = Mode::Text,
+                    '_' | '$' => return None,
+                    c => buffer.push(c),
                 }
             }
-            Mode::LongHex => {
-                debug_assert!(char != '_');
-                match char {
-                    '$' => {
-                        if let Ok(code) = u32::from_str_radix(&buffer, 16) {
-                            output.push(std::char::from_u32(code).unwrap());
-                        }
-                        buffer.clear();
-                        mode = Mode::Text;
-                    }
-                    c => {
-                        buffer.push(c);
-                    }
+            Mode::LongHex => match char {
+                '$' => {
+                    let code = u32::from_str_radix(&buffer, 16).ok()?;
+                    output.push(std::char::from_u32(code)?);
+                    buffer.clear();
+                    mode = Mode::Text;
                 }
-            }
+                '_' => return None,
+                c => buffer.push(c),
+            },
         }
     }
-    debug_assert!(matches!(mode, Mode::Text));
-    output
+    match mode {
+        Mode::Text => {}
+        // A trailing underscore is followed directly by the closing `__`.
+        Mode::Underscore => output.push('_'),
+        Mode::HexStart | Mode::Hex | Mode::LongHex => return None,
+    }
+    Some(output)
 }
 
 /// Decode all magic identifiers in a string.
@@ -142,7 +164,9 @@ pub fn unmangle_identifiers<T: Display>(text: &str, magic: impl Fn(String) -> T)
 
     impl<T: Fn(String) -> O, O: Display> Replacer for Rep<T, O> {
         fn replace_append(&mut self, caps: &Captures<'_>, dst: &mut String) {
-            write!(dst, "{}", self.0(unmangle(caps.get(0).unwrap().as_str()))).unwrap();
+            let identifier = caps.get(0).unwrap().as_str();
+            let decoded = unmangle(identifier).unwrap_or_else(|| identifier.to_string());
+            write!(dst, "{}", self.0(decoded)).unwrap();
         }
     }
 
@@ -174,31 +198,154 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_with_prefix() {
+        assert_eq!(
+            mangle_with_prefix("__NEXT__", "Hello World"),
+            "__NEXT__Hello__World__"
+        );
+        assert_eq!(
+            mangle_with_prefix("__TURBOPACK__", "Hello/World"),
+            mangle("Hello/World")
+        );
+    }
+
     #[test]
     fn test_decode() {
-        assert_eq!(unmangle("__TURBOPACK__Hello__World__"), "Hello World");
-        assert_eq!(unmangle("__TURBOPACK__Hello_World__"), "Hello_World");
-        assert_eq!(unmangle("__TURBOPACK__Hello_$5f$World__"), "Hello__World");
-        assert_eq!(unmangle("__TURBOPACK__Hello_$5f$_World__"), "Hello___World");
-        assert_eq!(unmangle("__TURBOPACK__Hello$2f$World__"), "Hello/World");
         assert_eq!(
-            unmangle("__TURBOPACK__Hello$2f2f2f$World__"),
-            "Hello///World"
+            unmangle("__TURBOPACK__Hello__World__").as_deref(),
+            Some("Hello World")
         );
-        assert_eq!(unmangle("__TURBOPACK__Hello$2f$_World__"), "Hello/_World");
-        assert_eq!(unmangle("__TURBOPACK__Hello_$2f$_World__"), "Hello_/_World");
         assert_eq!(
-            unmangle("__TURBOPACK__Hello$_1f600$World__"),
-            "Hello😀World"
+            unmangle("__TURBOPACK__Hello_World__").as_deref(),
+            Some("Hello_World")
         );
         assert_eq!(
-            unmangle("__TURBOPACK__Hello$2f_1f600$$2f$World__"),
-            "Hello/😀/World"
+            unmangle("__TURBOPACK__Hello_$5f$World__").as_deref(),
+            Some("Hello__World")
         );
         assert_eq!(
-            unmangle("__TURBOPACK__Hello$_1f600$$_1f600$World__"),
-            "Hello😀😀World"
+            unmangle("__TURBOPACK__Hello_$5f$_World__").as_deref(),
+            Some("Hello___World")
         );
+        assert_eq!(
+            unmangle("__TURBOPACK__Hello$2f$World__").as_deref(),
+            Some("Hello/World")
+        );
+        assert_eq!(
+            unmangle("__TURBOPACK__Hello$2f2f2f$World__").as_deref(),
+            Some("Hello///World")
+        );
+        assert_eq!(
+            unmangle("__TURBOPACK__Hello$2f$_World__").as_deref(),
+            Some("Hello/_World")
+        );
+        assert_eq!(
+            unmangle("__TURBOPACK__Hello_$2f$_World__").as_deref(),
+            Some("Hello_/_World")
+        );
+        assert_eq!(
+            unmangle("__TURBOPACK__Hello$_1f600$World__").as_deref(),
+            Some("Hello😀World")
+        );
+        assert_eq!(
+            unmangle("__TURBOPACK__Hello$2f_1f600$$2f$World__").as_deref(),
+            Some("Hello/😀/World")
+        );
+        assert_eq!(
+            unmangle("__TURBOPACK__Hello$_1f600$$_1f600$World__").as_deref(),
+            Some("Hello😀😀World")
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        assert_eq!(unmangle("Hello"), None);
+        assert_eq!(unmangle("__TURBOPACK__Hello"), None);
+        assert_eq!(unmangle("__TURBOPACK__Hello$2__"), None);
+        assert_eq!(unmangle("__TURBOPACK__Hello$zz$__"), None);
+        assert_eq!(unmangle("__TURBOPACK__Hello$_d800$__"), None);
+    }
+
+    #[test]
+    fn test_is_magic() {
+        assert!(is_magic("__TURBOPACK__Hello__World__"));
+        assert!(is_magic(&mangle("")));
+        assert!(!is_magic("Hello"));
+        assert!(!is_magic("__TURBOPACK__Hello-World__"));
+    }
+
+    /// Generates descriptors from a fixed seed so failures are reproducible.
+    fn arbitrary_descriptors() -> impl Iterator<Item = String> {
+        const CHARS: &[char] = &[
+            'a',
+            'Z',
+            '0',
+            '9',
+            ' ',
+            '_',
+            '$',
+            '/',
+            '.',
+            '-',
+            '\0',
+            '\t',
+            '\n',
+            '\u{7f}',
+            'é',
+            'ÿ',
+            'ā',
+            'ß',
+            '中',
+            '文',
+            '😀',
+            '\u{10ffff}',
+        ];
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        (0..2000).map(move |_| {
+            let len = next() % 12;
+            (0..len)
+                .map(|_| CHARS[(next() % CHARS.len() as u64) as usize])
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for descriptor in [
+            "",
+            " ",
+            "_",
+            "__",
+            "$",
+            "$$",
+            " leading and trailing ",
+            "_leading and trailing_",
+            "module evaluation",
+            "imported module ./foo/bar.js",
+            "über straße",
+            "日本語 モジュール",
+            "😀 $ 😀_",
+        ] {
+            let mangled = mangle(descriptor);
+            assert!(is_magic(&mangled), "{mangled}");
+            assert_eq!(unmangle(&mangled).as_deref(), Some(descriptor), "{mangled}");
+        }
+        for descriptor in arbitrary_descriptors() {
+            let mangled = mangle(&descriptor);
+            assert!(is_magic(&mangled), "{descriptor:?} -> {mangled}");
+            assert_eq!(
+                unmangle(&mangled).as_deref(),
+                Some(descriptor.as_str()),
+                "{mangled}"
+            );
+        }
     }
 
     #[test]
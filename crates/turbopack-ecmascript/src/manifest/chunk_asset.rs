@@ -3,16 +3,20 @@ use turbo_tasks::{Value, Vc};
 use turbopack_core::{
     asset::{Asset, AssetContent},
     chunk::{
-        availability_info::AvailabilityInfo, ChunkableModule, ChunkingContext, ChunkingContextExt,
+        availability_info::AvailabilityInfo,
+        chunk_group::{make_chunk_group, MakeChunkGroupResult},
+        ChunkItemExt, ChunkableModule, ChunkingContext, ChunkingContextExt,
     },
     ident::AssetIdent,
     module::Module,
-    output::OutputAssets,
+    output::{OutputAsset, OutputAssets},
     reference::{ModuleReferences, SingleOutputAssetReference},
 };
 
 use super::chunk_item::ManifestChunkItem;
-use crate::chunk::{EcmascriptChunkPlaceable, EcmascriptChunkingContext, EcmascriptExports};
+use crate::chunk::{
+    EcmascriptChunk, EcmascriptChunkPlaceable, EcmascriptChunkingContext, EcmascriptExports,
+};
 
 #[turbo_tasks::function]
 fn modifier() -> Vc<String> {
@@ -59,6 +63,39 @@ impl ManifestAsyncModule {
             .chunk_group_assets(Vc::upcast(this.inner), Value::new(this.availability_info)))
     }
 
+    /// The path of the chunk of [ManifestAsyncModule::chunks] containing the
+    /// module, relative to the output root. Chunking contexts generate the
+    /// assets of a chunk group in the order of its chunks, which is how the
+    /// chunk is matched with its asset.
+    #[turbo_tasks::function]
+    pub(super) async fn module_chunk_path(self: Vc<Self>) -> Result<Vc<Option<String>>> {
+        let this = self.await?;
+        let chunking_context = Vc::upcast::<Box<dyn ChunkingContext>>(this.chunking_context);
+        let MakeChunkGroupResult { chunks, .. } = make_chunk_group(
+            chunking_context,
+            [Vc::upcast(this.inner)],
+            this.availability_info,
+        )
+        .await?;
+        let module_id = this.inner.as_chunk_item(chunking_context).id().await?;
+        let assets = self.chunks().await?;
+        let output_root = chunking_context.output_root().await?;
+        for (&chunk, &asset) in chunks.iter().zip(assets.iter()) {
+            let Some(chunk) = Vc::try_resolve_downcast_type::<EcmascriptChunk>(chunk).await? else {
+                continue;
+            };
+            for &(chunk_item, _) in chunk.await?.content.await?.chunk_items.iter() {
+                if chunk_item.id().await? == module_id {
+                    let path = asset.ident().path().await?;
+                    return Ok(Vc::cell(
+                        output_root.get_path_to(&path).map(|path| path.to_string()),
+                    ));
+                }
+            }
+        }
+        Ok(Vc::cell(None))
+    }
+
     #[turbo_tasks::function]
     pub async fn manifest_chunks(self: Vc<Self>) -> Result<Vc<OutputAssets>> {
         let this = self.await?;
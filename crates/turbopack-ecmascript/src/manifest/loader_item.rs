@@ -181,6 +181,20 @@ impl EcmascriptChunkItem for ManifestLoaderChunkItem {
             .id()
             .await?;
 
+        // Chunking contexts can have the import name the chunk containing the module,
+        // see [EcmascriptChunkingContext::import_target_chunks].
+        let target_chunk = if *manifest.chunking_context.import_target_chunks().await? {
+            this.manifest.module_chunk_path().await?.clone_value()
+        } else {
+            None
+        };
+        let import_args = match target_chunk {
+            Some(chunk_path) => {
+                format!("{}, {}", StringifyJs(dynamic_id), StringifyJs(&chunk_path))
+            }
+            None => StringifyJs(dynamic_id).to_string(),
+        };
+
         // This is the code that will be executed when the dynamic import is reached.
         // It will load the manifest chunk, which will load all the chunks needed by
         // the dynamic import, and finally we'll be able to import the module we're
@@ -196,7 +210,7 @@ impl EcmascriptChunkItem for ManifestLoaderChunkItem {
                     }}).then((chunks) => {{
                         return Promise.all(chunks.map((chunk) => __turbopack_load__(chunk)));
                     }}).then(() => {{
-                        return __turbopack_import__({dynamic_id});
+                        return __turbopack_import__({import_args});
                     }});
                 }});
             "#,
@@ -207,7 +221,7 @@ impl EcmascriptChunkItem for ManifestLoaderChunkItem {
                     .collect::<Vec<_>>()
             ),
             item_id = StringifyJs(item_id),
-            dynamic_id = StringifyJs(dynamic_id),
+            import_args = import_args,
         )?;
 
         Ok(EcmascriptChunkItemContent {
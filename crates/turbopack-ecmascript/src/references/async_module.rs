@@ -1,20 +1,39 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use futures::{stream, StreamExt, TryStreamExt};
 use indexmap::IndexSet;
 use serde::{Deserialize, Serialize};
 use swc_core::{
-    common::DUMMY_SP,
-    ecma::ast::{ArrayLit, ArrayPat, Expr, Ident, Program},
+    common::{Span, DUMMY_SP},
+    ecma::{
+        ast::{
+            ArrayLit, ArrayPat, Decl, Expr, Ident, ImportSpecifier, Lit, ModuleDecl, ModuleItem,
+            Program, Stmt,
+        },
+        utils::find_pat_ids,
+    },
     quote,
 };
 use turbo_tasks::{trace::TraceRawVcs, TryFlatJoinIterExt, TryJoinIterExt, Vc};
-use turbopack_core::chunk::{AsyncModuleInfo, ChunkableModule};
+use turbopack_core::{
+    chunk::{AsyncModuleInfo, ChunkableModuleReference},
+    issue::{analyze::AnalyzeIssue, IssueExt, IssueSeverity, StyledString},
+};
 
-use super::esm::base::ReferencedAsset;
+use super::{esm::base::ReferencedAsset, issue_source};
 use crate::{
-    chunk::{EcmascriptChunkPlaceable, EcmascriptChunkingContext},
+    chunk::{
+        EcmascriptChunkPlaceable, EcmascriptChunkingContext, EcmascriptExports, HoistingMarkers,
+        RuntimeFunctionNames,
+    },
     code_gen::{CodeGenerateableWithAsyncModuleInfo, CodeGeneration},
     create_visitor,
-    references::esm::{base::insert_hoisted_stmt, EsmAssetReference},
+    parse::ParseResult,
+    references::esm::{
+        base::{runtime_function, set_var_decl_span},
+        hoisting::{insert_hoisted_stmt, insert_stmts_after_hoisting_location},
+        EsmAssetReference, EsmAssetReferences,
+    },
+    EcmascriptModuleAsset,
 };
 
 /// Information needed for generating the async module wrapper for
@@ -22,6 +41,11 @@ use crate::{
 #[derive(PartialEq, Eq, Default, Debug, Clone, Serialize, Deserialize, TraceRawVcs)]
 pub struct AsyncModuleOptions {
     pub has_top_level_await: bool,
+    /// Whether the module has CommonJS or value exports, e.g. an ES module
+    /// exporting with `__turbopack_export_value__`. Its wrapper then keeps
+    /// `module.exports` pointing at the exports object while the module body
+    /// runs, and resolves the async module with its final value.
+    pub is_commonjs: bool,
 }
 
 /// Option<[AsyncModuleOptions]>.
@@ -47,6 +71,9 @@ pub struct AsyncModule {
     pub references: IndexSet<Vc<EsmAssetReference>>,
     pub has_top_level_await: bool,
     pub import_externals: bool,
+    /// Whether externals are imported with static `import` declarations,
+    /// which makes them async like imported externals.
+    pub esm_externals: bool,
 }
 
 /// Option<[AsyncModule]>.
@@ -61,12 +88,31 @@ impl OptionAsyncModule {
         Vc::cell(None)
     }
 
+    /// Returns the options for the async module wrapper, see
+    /// [AsyncModule::module_options]. Without an async module, the module
+    /// isn't analyzed as ESM and can't be async.
+    ///
+    /// Without `async_module_info`, it's unknown whether the referenced
+    /// modules are async. Unless the module is async by itself, it's then
+    /// treated as sync, or an error is returned when `require_known` is set.
     #[turbo_tasks::function]
     pub async fn module_options(
         self: Vc<Self>,
         async_module_info: Option<Vc<AsyncModuleInfo>>,
+        require_known: bool,
     ) -> Result<Vc<OptionAsyncModuleOptions>> {
         if let Some(async_module) = &*self.await? {
+            if require_known
+                && !is_async_status_known(
+                    *async_module.is_self_async().await?,
+                    async_module_info.is_some(),
+                )
+            {
+                bail!(
+                    "the async status of the module can't be determined without the async module \
+                     info of its chunk item"
+                );
+            }
             return Ok(async_module.module_options(async_module_info));
         }
 
@@ -74,92 +120,298 @@ impl OptionAsyncModule {
     }
 }
 
+/// The number of references [AsyncModule::get_async_idents] checks at once.
+const ASYNC_IDENTS_CONCURRENCY: usize = 64;
+
+/// Whether it's known if a module is async. A module that is async by itself
+/// is known to be async, otherwise it depends on its references, which are
+/// listed in the async module info.
+fn is_async_status_known(is_self_async: bool, has_async_module_info: bool) -> bool {
+    is_self_async || has_async_module_info
+}
+
+/// What makes a module async. Both need the async module wrapper, but they
+/// need different code inside of it: top-level await is awaited by the module
+/// body itself, while async dependencies are awaited by the async dependency
+/// handler, see [add_async_dependency_handler].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AsyncModuleKind {
+    /// The module uses top-level await, but none of its dependencies is async.
+    /// The wrapper is emitted in its top-level await form, without a
+    /// dependency handler.
+    TopLevelAwait,
+    /// Some dependencies of the module are async, but it doesn't use
+    /// top-level await itself.
+    AsyncDependencies,
+    /// The module uses top-level await and has async dependencies.
+    TopLevelAwaitAndAsyncDependencies,
+}
+
+impl AsyncModuleKind {
+    /// The kind of an async module, or [None] for a sync module.
+    fn new(has_top_level_await: bool, has_async_dependencies: bool) -> Option<Self> {
+        match (has_top_level_await, has_async_dependencies) {
+            (false, false) => None,
+            (true, false) => Some(AsyncModuleKind::TopLevelAwait),
+            (false, true) => Some(AsyncModuleKind::AsyncDependencies),
+            (true, true) => Some(AsyncModuleKind::TopLevelAwaitAndAsyncDependencies),
+        }
+    }
+
+    fn has_top_level_await(self) -> bool {
+        matches!(
+            self,
+            AsyncModuleKind::TopLevelAwait | AsyncModuleKind::TopLevelAwaitAndAsyncDependencies
+        )
+    }
+
+    /// Whether the async dependency handler has to await dependencies.
+    fn awaits_dependencies(self) -> bool {
+        matches!(
+            self,
+            AsyncModuleKind::AsyncDependencies | AsyncModuleKind::TopLevelAwaitAndAsyncDependencies
+        )
+    }
+}
+
+/// Whether externals are loaded asynchronously, either through
+/// `import_externals` or a static `import` declaration in an environment that
+/// supports ESM externals.
+fn externals_are_async(import_externals: bool, esm_externals: bool) -> bool {
+    import_externals || esm_externals
+}
+
+/// What a reference imports, as far as the async status of the importing
+/// module is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AsyncReferenceTarget {
+    /// An external, which is async when externals are loaded asynchronously.
+    External,
+    /// A module, which is async when its chunk item is listed in the async
+    /// module info.
+    Module { is_async: bool },
+    /// A module of a module federation remote container, which is always
+    /// async.
+    Remote,
+    /// Anything else, e.g. a Node.js builtin or an ignored module, which is
+    /// never async.
+    Other,
+}
+
+/// Whether a reference to `target` makes the importing module async, see
+/// [EsmAssetReference::contributes_to_async].
+pub(crate) fn is_async_reference(
+    target: AsyncReferenceTarget,
+    import_externals: bool,
+    esm_externals: bool,
+) -> bool {
+    match target {
+        AsyncReferenceTarget::External => externals_are_async(import_externals, esm_externals),
+        AsyncReferenceTarget::Module { is_async } => is_async,
+        AsyncReferenceTarget::Remote => true,
+        AsyncReferenceTarget::Other => false,
+    }
+}
+
 #[turbo_tasks::value(transparent)]
 struct AsyncModuleIdents(IndexSet<String>);
 
+/// The [ReferencedAsset]s of all references of an [AsyncModule], in order.
+#[turbo_tasks::value(transparent)]
+struct ReferencedAssets(Vec<Vc<ReferencedAsset>>);
+
 #[turbo_tasks::value_impl]
 impl AsyncModule {
+    /// The ESM references the async wrapper considers, in the order they were
+    /// added.
+    #[turbo_tasks::function]
+    pub fn references(&self) -> Vc<EsmAssetReferences> {
+        Vc::cell(self.references.iter().copied().collect())
+    }
+
+    /// Resolves the referenced asset of every reference at once, for
+    /// [AsyncModule::external_references].
+    #[turbo_tasks::function]
+    async fn referenced_assets(&self) -> Result<Vc<ReferencedAssets>> {
+        Ok(Vc::cell(
+            self.references
+                .iter()
+                .map(|r| r.get_referenced_asset().resolve())
+                .try_join()
+                .await?,
+        ))
+    }
+
+    /// The references to externals, which make the module async when
+    /// externals are loaded asynchronously, see [externals_are_async].
+    #[turbo_tasks::function]
+    async fn external_references(self: Vc<Self>) -> Result<Vc<EsmAssetReferences>> {
+        let this = self.await?;
+        Ok(Vc::cell(
+            self.referenced_assets()
+                .await?
+                .iter()
+                .zip(this.references.iter())
+                .map(|(&referenced_asset, &reference)| async move {
+                    Ok(referenced_asset.await?.is_external().then_some(reference))
+                })
+                .try_flat_join()
+                .await?,
+        ))
+    }
+
+    /// The references to modules of remote containers. Their bindings are
+    /// awaited in the module body, like a top-level await.
+    #[turbo_tasks::function]
+    async fn remote_references(self: Vc<Self>) -> Result<Vc<EsmAssetReferences>> {
+        let this = self.await?;
+        Ok(Vc::cell(
+            self.referenced_assets()
+                .await?
+                .iter()
+                .zip(this.references.iter())
+                .map(|(&referenced_asset, &reference)| async move {
+                    Ok(referenced_asset.await?.is_remote().then_some(reference))
+                })
+                .try_flat_join()
+                .await?,
+        ))
+    }
+
+    /// Whether the module body awaits, either with a top-level await or the
+    /// bindings of remote modules, see [AsyncModule::remote_references].
+    #[turbo_tasks::function]
+    async fn awaits_in_body(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?.has_top_level_await || !self.remote_references().await?.is_empty(),
+        ))
+    }
+
     #[turbo_tasks::function]
     async fn get_async_idents(
-        &self,
+        self: Vc<Self>,
         chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
         async_module_info: Vc<AsyncModuleInfo>,
     ) -> Result<Vc<AsyncModuleIdents>> {
-        let async_module_info = async_module_info.await?;
+        let this = &*self.await?;
 
-        let reference_idents = self
-            .references
-            .iter()
-            .map(|r| async {
-                let referenced_asset = r.get_referenced_asset().await?;
-                Ok(match &*referenced_asset {
-                    ReferencedAsset::OriginalReferenceTypeExternal(_) => {
-                        if self.import_externals {
-                            referenced_asset.get_ident().await?
-                        } else {
-                            None
-                        }
-                    }
-                    ReferencedAsset::Some(placeable) => {
-                        let chunk_item = placeable
-                            .as_chunk_item(Vc::upcast(chunking_context))
-                            .resolve()
-                            .await?;
-                        if async_module_info
-                            .referenced_async_modules
-                            .contains(&chunk_item)
-                        {
-                            referenced_asset.get_ident().await?
-                        } else {
-                            None
-                        }
-                    }
-                    ReferencedAsset::None => None,
-                })
-            })
-            .try_flat_join()
+        // The references are checked in order, with a bounded number of them in
+        // flight, so modules with many imports don't flood the scheduler.
+        let reference_idents: Vec<Option<String>> =
+            stream::iter(this.references.iter().map(|&reference| async move {
+                if !*reference
+                    .contributes_to_async(chunking_context, async_module_info, this.esm_externals)
+                    .await?
+                {
+                    return Ok(None);
+                }
+                // Unchunked imports don't bind their ident.
+                if reference
+                    .chunking_type_for(Vc::upcast(chunking_context))
+                    .await?
+                    .is_none()
+                {
+                    return Ok(None);
+                }
+                // Remote modules are already awaited where they're bound.
+                if reference.get_referenced_asset().await?.is_remote() {
+                    return Ok(None);
+                }
+                // Dropped imports don't bind their ident, see
+                // [EsmAssetReference::is_unused_and_side_effect_free].
+                if *reference.is_unused_and_side_effect_free().await? {
+                    return Ok(None);
+                }
+                anyhow::Ok(
+                    reference
+                        .get_referenced_ident(chunking_context)
+                        .await?
+                        .clone_value(),
+                )
+            }))
+            .buffered(ASYNC_IDENTS_CONCURRENCY)
+            .try_collect()
             .await?;
 
-        Ok(Vc::cell(IndexSet::from_iter(reference_idents)))
+        Ok(Vc::cell(reference_idents.into_iter().flatten().collect()))
     }
 
     #[turbo_tasks::function]
-    pub(crate) async fn is_self_async(&self) -> Result<Vc<bool>> {
-        if self.has_top_level_await {
+    pub(crate) async fn is_self_async(self: Vc<Self>) -> Result<Vc<bool>> {
+        let this = self.await?;
+        if *self.awaits_in_body().await? {
             return Ok(Vc::cell(true));
         }
 
         Ok(Vc::cell(
-            self.import_externals
-                && self
-                    .references
-                    .iter()
-                    .map(|r| async {
-                        let referenced_asset = r.get_referenced_asset().await?;
-                        Ok(matches!(
-                            &*referenced_asset,
-                            ReferencedAsset::OriginalReferenceTypeExternal(_)
-                        ))
-                    })
-                    .try_join()
-                    .await?
-                    .iter()
-                    .any(|&b| b),
+            externals_are_async(this.import_externals, this.esm_externals)
+                && !self.external_references().await?.is_empty(),
         ))
     }
 
-    /// Returns
+    /// Whether the module is async, either by itself or because it imports
+    /// async modules. The latter are listed in the `async_module_info` of the
+    /// chunk item, which only contains async chunk items referenced by this
+    /// module.
+    #[turbo_tasks::function]
+    pub async fn is_async(
+        self: Vc<Self>,
+        async_module_info: Option<Vc<AsyncModuleInfo>>,
+    ) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            AsyncModuleKind::new(
+                *self.awaits_in_body().await?,
+                *self.has_async_dependencies(async_module_info).await?,
+            )
+            .is_some(),
+        ))
+    }
+
+    /// Whether the module has async dependencies: async externals, or
+    /// referenced modules that are async, as listed in the async module info.
+    #[turbo_tasks::function]
+    async fn has_async_dependencies(
+        self: Vc<Self>,
+        async_module_info: Option<Vc<AsyncModuleInfo>>,
+    ) -> Result<Vc<bool>> {
+        let this = self.await?;
+        if externals_are_async(this.import_externals, this.esm_externals)
+            && !self.external_references().await?.is_empty()
+        {
+            return Ok(Vc::cell(true));
+        }
+
+        Ok(Vc::cell(match async_module_info {
+            Some(async_module_info) => {
+                !async_module_info.await?.referenced_async_modules.is_empty()
+            }
+            None => false,
+        }))
+    }
+
+    /// Returns the options for the async module wrapper, or `None` if the
+    /// module isn't async, see [AsyncModuleKind].
     #[turbo_tasks::function]
     pub async fn module_options(
         self: Vc<Self>,
         async_module_info: Option<Vc<AsyncModuleInfo>>,
     ) -> Result<Vc<OptionAsyncModuleOptions>> {
-        if async_module_info.is_none() {
+        let this = self.await?;
+        let Some(kind) = AsyncModuleKind::new(
+            *self.awaits_in_body().await?,
+            *self.has_async_dependencies(async_module_info).await?,
+        ) else {
             return Ok(Vc::cell(None));
-        }
+        };
+
+        let is_commonjs = matches!(
+            &*this.placeable.get_exports().await?,
+            EcmascriptExports::CommonJs | EcmascriptExports::Value
+        );
 
         Ok(Vc::cell(Some(AsyncModuleOptions {
-            has_top_level_await: self.await?.has_top_level_await,
+            has_top_level_await: kind.has_top_level_await(),
+            is_commonjs,
         })))
     }
 }
@@ -174,14 +426,49 @@ impl CodeGenerateableWithAsyncModuleInfo for AsyncModule {
     ) -> Result<Vc<CodeGeneration>> {
         let mut visitors = Vec::new();
 
+        if *self.is_async(async_module_info).await? {
+            report_reserved_top_level_bindings(
+                self.await?.placeable,
+                &*chunking_context.runtime_function_names().await?,
+            )
+            .await?;
+        }
+
         if let Some(async_module_info) = async_module_info {
             let async_idents = self
                 .get_async_idents(chunking_context, async_module_info)
                 .await?;
 
-            if !async_idents.is_empty() {
+            // A module that is only async because of its top-level await gets
+            // the wrapper, see [AsyncModule::module_options], but there are no
+            // dependencies to await.
+            let kind =
+                AsyncModuleKind::new(*self.awaits_in_body().await?, !async_idents.is_empty());
+            if kind.is_some_and(AsyncModuleKind::awaits_dependencies) {
+                let barrier = *chunking_context.esm_evaluation_barrier().await?;
+                let names = chunking_context.runtime_function_names().await?;
+                let markers = chunking_context.hoisting_markers().await?;
+                let timeout_ms = *chunking_context.async_dependency_timeout_ms().await?;
+                // The await stands in for all imports, so it's attributed to the first one.
+                let span = match self.await?.references.first() {
+                    Some(reference) => reference.await?.span(),
+                    None => None,
+                };
+                let is_commonjs = matches!(
+                    &*self.await?.placeable.get_exports().await?,
+                    EcmascriptExports::CommonJs | EcmascriptExports::Value
+                );
                 visitors.push(create_visitor!(visit_mut_program(program: &mut Program) {
-                    add_async_dependency_handler(program, &async_idents);
+                    add_async_dependency_handler(
+                        program,
+                        &async_idents,
+                        barrier,
+                        is_commonjs,
+                        timeout_ms,
+                        span,
+                        &names,
+                        &markers,
+                    );
                 }));
             }
         }
@@ -190,27 +477,144 @@ impl CodeGenerateableWithAsyncModuleInfo for AsyncModule {
     }
 }
 
-fn add_async_dependency_handler(program: &mut Program, idents: &IndexSet<String>) {
+/// The names the async module wrapper binds around the module body. The async
+/// dependency handler it calls is named by [RuntimeFunctionNames] instead.
+const RESERVED_NAMES: [&str; 4] = [
+    "__turbopack_async_dependencies__",
+    "__turbopack_async_promise__",
+    "__turbopack_cjs_exports__",
+    "__turbopack_async_result__",
+];
+
+/// The bindings declared at the top level of `program` whose names the async
+/// module wrapper binds or calls, see [RESERVED_NAMES]. They would shadow or
+/// overwrite the wrapper's bindings.
+fn reserved_top_level_bindings(program: &Program, names: &RuntimeFunctionNames) -> Vec<Ident> {
+    fn decl_idents(decl: &Decl) -> Vec<Ident> {
+        match decl {
+            Decl::Var(var) => var
+                .decls
+                .iter()
+                .flat_map(|decl| find_pat_ids(&decl.name))
+                .collect(),
+            Decl::Fn(function) => vec![function.ident.clone()],
+            Decl::Class(class) => vec![class.ident.clone()],
+            _ => vec![],
+        }
+    }
+
+    let idents = match program {
+        Program::Module(module) => module
+            .body
+            .iter()
+            .flat_map(|item| match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => decl_idents(decl),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => decl_idents(&export.decl),
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => import
+                    .specifiers
+                    .iter()
+                    .map(|specifier| match specifier {
+                        ImportSpecifier::Named(named) => named.local.clone(),
+                        ImportSpecifier::Default(default) => default.local.clone(),
+                        ImportSpecifier::Namespace(namespace) => namespace.local.clone(),
+                    })
+                    .collect(),
+                _ => vec![],
+            })
+            .collect::<Vec<_>>(),
+        Program::Script(script) => script
+            .body
+            .iter()
+            .flat_map(|stmt| match stmt {
+                Stmt::Decl(decl) => decl_idents(decl),
+                _ => vec![],
+            })
+            .collect(),
+    };
+    idents
+        .into_iter()
+        .filter(|ident| {
+            RESERVED_NAMES.contains(&&*ident.sym) || *ident.sym == *names.handle_async_dependencies
+        })
+        .collect()
+}
+
+/// Reports the top level bindings of an async module that conflict with its
+/// wrapper, see [reserved_top_level_bindings].
+async fn report_reserved_top_level_bindings(
+    placeable: Vc<Box<dyn EcmascriptChunkPlaceable>>,
+    names: &RuntimeFunctionNames,
+) -> Result<()> {
+    let Some(module) = Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(placeable).await?
+    else {
+        return Ok(());
+    };
+    let ParseResult::Ok { program, .. } = &*module.parse().await? else {
+        return Ok(());
+    };
+    let source = module.await?.source;
+    for ident in reserved_top_level_bindings(program, names) {
+        AnalyzeIssue {
+            code: None,
+            category: Vc::cell("analyze".to_string()),
+            message: StyledString::Text(format!(
+                "The top level declaration of `{}` conflicts with the binding of the same name in \
+                 the async module wrapper. Rename it.",
+                ident.sym
+            ))
+            .cell(),
+            source_ident: source.ident(),
+            severity: IssueSeverity::Error.into(),
+            source: Some(issue_source(source, ident.span)),
+            title: Vc::cell("reserved turbopack name".to_string()),
+        }
+        .cell()
+        .emit();
+    }
+    Ok(())
+}
+
+/// Builds the two statements awaiting the async dependencies `idents`: the
+/// call of the async dependency handler, and the await reassigning `idents`
+/// to the resolved dependencies. They aren't inserted anywhere, see
+/// [add_async_dependency_handler] for where the async module places them.
+///
+/// With `timeout_ms`, the handler is passed `{ timeout: timeout_ms }` and
+/// warns when the dependencies haven't resolved in time.
+pub fn build_async_dependency_stmts(
+    idents: &IndexSet<String>,
+    timeout_ms: Option<u32>,
+    names: &RuntimeFunctionNames,
+) -> [Stmt; 2] {
     let idents = idents
         .iter()
         .map(|ident| Ident::new(ident.clone().into(), DUMMY_SP))
         .collect::<Vec<_>>();
 
-    let stmt = quote!(
-        "var __turbopack_async_dependencies__ = __turbopack_handle_async_dependencies__($deps);"
-            as Stmt,
-        deps: Expr = Expr::Array(ArrayLit {
-            span: DUMMY_SP,
-            elems: idents
-                .iter()
-                .map(|ident| { Some(Expr::Ident(ident.clone()).into()) })
-                .collect(),
-        }),
-    );
-
-    insert_hoisted_stmt(program, stmt);
+    let handle = runtime_function(&names.handle_async_dependencies);
+    let deps = Expr::Array(ArrayLit {
+        span: DUMMY_SP,
+        elems: idents
+            .iter()
+            .map(|ident| Some(Expr::Ident(ident.clone()).into()))
+            .collect(),
+    });
+    let handle_stmt = match timeout_ms {
+        Some(timeout_ms) => quote!(
+            "var __turbopack_async_dependencies__ = $handle($deps, { timeout: $timeout });"
+                as Stmt,
+            handle = handle,
+            deps: Expr = deps,
+            timeout: Expr = Expr::Lit(Lit::Num((timeout_ms as f64).into())),
+        ),
+        None => quote!(
+            "var __turbopack_async_dependencies__ = $handle($deps);" as Stmt,
+            handle = handle,
+            deps: Expr = deps,
+        ),
+    };
 
-    let stmt = quote!(
+    let await_stmt = quote!(
         "($deps = __turbopack_async_dependencies__.then ? (await \
          __turbopack_async_dependencies__)() : __turbopack_async_dependencies__);" as Stmt,
         deps: AssignTarget = ArrayPat {
@@ -224,5 +628,417 @@ fn add_async_dependency_handler(program: &mut Program, idents: &IndexSet<String>
         }.into(),
     );
 
-    insert_hoisted_stmt(program, stmt);
+    [handle_stmt, await_stmt]
+}
+
+/// Adds the statements awaiting the async dependencies `idents` to the
+/// program.
+///
+/// The code generation of an [AsyncModule] runs after the one of all
+/// references of the module, so the statements are hoisted after all import
+/// bindings, external and internal ones, which stay in source order.
+///
+/// With `barrier`, the statements are placed directly after the hoisting
+/// location instead of before it. They then run after every hoisted import,
+/// so all synchronous import side effects happen before the await.
+///
+/// With `is_commonjs`, the async module promise is put back into
+/// `module.exports` for the duration of the await, so modules requiring this
+/// one in the meantime see the promise instead of the partial exports. The
+/// value of `module.exports` before the await is restored afterwards.
+///
+/// The statements are built by [build_async_dependency_stmts], which is also
+/// passed `timeout_ms`.
+///
+/// Without `idents`, there's nothing to await, so nothing is added.
+#[allow(clippy::too_many_arguments)]
+fn add_async_dependency_handler(
+    program: &mut Program,
+    idents: &IndexSet<String>,
+    barrier: bool,
+    is_commonjs: bool,
+    timeout_ms: Option<u32>,
+    span: Option<Span>,
+    names: &RuntimeFunctionNames,
+    markers: &HoistingMarkers,
+) {
+    if idents.is_empty() {
+        return;
+    }
+
+    let [mut handle_stmt, mut await_stmt] = build_async_dependency_stmts(idents, timeout_ms, names);
+
+    set_var_decl_span(&mut handle_stmt, span);
+    if let (Some(span), Stmt::Expr(expr)) = (span, &mut await_stmt) {
+        expr.span = span;
+    }
+
+    let mut stmts = vec![handle_stmt];
+    if is_commonjs {
+        stmts.push(quote!(
+            "var __turbopack_cjs_exports__ = module.exports;" as Stmt
+        ));
+        stmts.push(quote!(
+            "module.exports = __turbopack_async_promise__;" as Stmt
+        ));
+        stmts.push(await_stmt);
+        stmts.push(quote!(
+            "module.exports = __turbopack_cjs_exports__;" as Stmt
+        ));
+    } else {
+        stmts.push(await_stmt);
+    }
+
+    if barrier {
+        insert_stmts_after_hoisting_location(program, stmts, markers);
+    } else {
+        for stmt in stmts {
+            insert_hoisted_stmt(program, stmt, markers);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use swc_core::{
+        common::SourceMap,
+        ecma::codegen::{text_writer::JsWriter, Emitter, Node},
+    };
+
+    use super::*;
+    use crate::references::esm::base::tests::{parse, to_js};
+
+    /// Simulates an import whose code generation is applied after the async
+    /// module's.
+    fn add_side_effect_import(program: &mut Program) {
+        insert_hoisted_stmt(
+            program,
+            quote!("__turbopack_import__(\"./side-effect.js\");" as Stmt),
+            &Default::default(),
+        );
+    }
+
+    #[test]
+    fn side_effect_imports_run_before_await_with_barrier() {
+        let cm = Arc::new(SourceMap::default());
+        let idents = IndexSet::from(["dep".to_string()]);
+
+        let mut program = parse(&cm, "local();");
+        add_async_dependency_handler(
+            &mut program,
+            &idents,
+            true,
+            false,
+            None,
+            None,
+            &Default::default(),
+            &Default::default(),
+        );
+        add_side_effect_import(&mut program);
+        let js = to_js(&program, &cm);
+        let import = js.find("./side-effect.js").unwrap();
+        assert!(import < js.find("await").unwrap());
+        assert!(js.find("await").unwrap() < js.find("local()").unwrap());
+
+        let mut program = parse(&cm, "local();");
+        add_async_dependency_handler(
+            &mut program,
+            &idents,
+            false,
+            false,
+            None,
+            None,
+            &Default::default(),
+            &Default::default(),
+        );
+        add_side_effect_import(&mut program);
+        let js = to_js(&program, &cm);
+        assert!(js.find("./side-effect.js").unwrap() > js.find("await").unwrap());
+    }
+
+    #[test]
+    fn runtime_function_names() {
+        let cm = Arc::new(SourceMap::default());
+        let idents = IndexSet::from(["dep".to_string()]);
+        let names = RuntimeFunctionNames {
+            handle_async_dependencies: "rtHandleAsyncDependencies".to_string(),
+            ..Default::default()
+        };
+
+        let mut program = parse(&cm, "local();");
+        add_async_dependency_handler(
+            &mut program,
+            &idents,
+            false,
+            false,
+            None,
+            None,
+            &names,
+            &Default::default(),
+        );
+        let js = to_js(&program, &cm);
+        assert!(js.contains("=rtHandleAsyncDependencies([dep])"));
+        assert!(!js.contains("__turbopack_handle_async_dependencies__"));
+    }
+
+    #[test]
+    fn async_dependency_timeout() {
+        let cm = Arc::new(SourceMap::default());
+        let idents = IndexSet::from(["dep".to_string()]);
+        let handler = |timeout_ms| {
+            let mut program = parse(&cm, "");
+            add_async_dependency_handler(
+                &mut program,
+                &idents,
+                false,
+                false,
+                timeout_ms,
+                None,
+                &Default::default(),
+                &Default::default(),
+            );
+            to_js(&program, &cm)
+        };
+
+        assert!(handler(None).contains(
+            "var __turbopack_async_dependencies__=__turbopack_handle_async_dependencies__([dep]);"
+        ));
+        assert!(handler(Some(250)).contains(
+            "var __turbopack_async_dependencies__=__turbopack_handle_async_dependencies__([dep],\
+             {timeout:250});"
+        ));
+    }
+
+    #[test]
+    fn commonjs_restores_promise_during_await() {
+        let cm = Arc::new(SourceMap::default());
+        let idents = IndexSet::from(["dep".to_string()]);
+
+        let mut program = parse(&cm, "local();");
+        add_async_dependency_handler(
+            &mut program,
+            &idents,
+            true,
+            true,
+            None,
+            None,
+            &Default::default(),
+            &Default::default(),
+        );
+        let js = to_js(&program, &cm);
+        let save = js
+            .find("var __turbopack_cjs_exports__=module.exports;")
+            .unwrap();
+        let restore = js
+            .find("module.exports=__turbopack_async_promise__;")
+            .unwrap();
+        let await_ = js.find("await").unwrap();
+        let reset = js
+            .find("module.exports=__turbopack_cjs_exports__;")
+            .unwrap();
+        assert!(save < restore && restore < await_ && await_ < reset);
+        assert!(reset < js.find("local()").unwrap());
+
+        let mut program = parse(&cm, "local();");
+        add_async_dependency_handler(
+            &mut program,
+            &idents,
+            true,
+            false,
+            None,
+            None,
+            &Default::default(),
+            &Default::default(),
+        );
+        assert!(!to_js(&program, &cm).contains("module.exports"));
+    }
+
+    #[test]
+    fn references_contributing_to_async() {
+        use AsyncReferenceTarget::*;
+
+        // Externals only when they are loaded asynchronously.
+        assert!(is_async_reference(External, true, false));
+        assert!(is_async_reference(External, false, true));
+        assert!(!is_async_reference(External, false, false));
+
+        // Modules when their chunk item is listed as async, regardless of how
+        // externals are loaded.
+        assert!(is_async_reference(Module { is_async: true }, false, false));
+        assert!(!is_async_reference(Module { is_async: false }, true, true));
+
+        // Builtins and ignored modules never.
+        assert!(!is_async_reference(Other, true, true));
+    }
+
+    #[test]
+    fn no_handler_without_async_dependencies() {
+        let cm = Arc::new(SourceMap::default());
+        for is_commonjs in [false, true] {
+            let mut program = parse(&cm, "local();");
+            add_async_dependency_handler(
+                &mut program,
+                &IndexSet::new(),
+                false,
+                is_commonjs,
+                None,
+                None,
+                &Default::default(),
+                &Default::default(),
+            );
+            assert_eq!(to_js(&program, &cm), to_js(&parse(&cm, "local();"), &cm));
+        }
+    }
+
+    #[test]
+    fn async_dependency_stmts() {
+        let stmt_to_js = |stmt: &Stmt| {
+            let cm = Arc::new(SourceMap::default());
+            let mut bytes = Vec::new();
+            let mut emitter = Emitter {
+                cfg: swc_core::ecma::codegen::Config::default().with_minify(true),
+                cm: cm.clone(),
+                comments: None,
+                wr: JsWriter::new(cm, "\n", &mut bytes, None),
+            };
+            stmt.emit_with(&mut emitter).unwrap();
+            String::from_utf8(bytes).unwrap()
+        };
+        let idents = IndexSet::from(["a".to_string(), "b".to_string()]);
+
+        let stmts = build_async_dependency_stmts(&idents, None, &Default::default());
+        assert_eq!(
+            stmts.iter().map(stmt_to_js).collect::<Vec<_>>(),
+            vec![
+                "var __turbopack_async_dependencies__=__turbopack_handle_async_dependencies__([a,\
+                 b]);",
+                "([a,b]=__turbopack_async_dependencies__.then?(await \
+                 __turbopack_async_dependencies__)():__turbopack_async_dependencies__);",
+            ]
+        );
+
+        let [handle_stmt, _] =
+            build_async_dependency_stmts(&idents, Some(250), &Default::default());
+        assert_eq!(
+            stmt_to_js(&handle_stmt),
+            "var __turbopack_async_dependencies__=__turbopack_handle_async_dependencies__([a,b],\
+             {timeout:250});"
+        );
+    }
+
+    #[test]
+    fn esm_externals_are_async() {
+        assert!(externals_are_async(false, true));
+        assert!(externals_are_async(true, false));
+        assert!(!externals_are_async(false, false));
+    }
+
+    #[test]
+    fn handler_is_hoisted_after_import_bindings() {
+        let cm = Arc::new(SourceMap::default());
+        let idents = IndexSet::from(["ext".to_string(), "dep".to_string()]);
+
+        let mut program = parse(&cm, "local();");
+        insert_hoisted_stmt(
+            &mut program,
+            quote!("var ext = __turbopack_external_import__(\"ext\");" as Stmt),
+            &Default::default(),
+        );
+        insert_hoisted_stmt(
+            &mut program,
+            quote!("var dep = __turbopack_import__(\"./dep.js\");" as Stmt),
+            &Default::default(),
+        );
+        add_async_dependency_handler(
+            &mut program,
+            &idents,
+            false,
+            false,
+            None,
+            None,
+            &Default::default(),
+            &Default::default(),
+        );
+        let js = to_js(&program, &cm);
+        let handler = js.find("__turbopack_handle_async_dependencies__").unwrap();
+        assert!(js.find("__turbopack_external_import__").unwrap() < handler);
+        assert!(js.find("./dep.js").unwrap() < handler);
+        assert!(handler < js.find("await").unwrap());
+    }
+
+    #[test]
+    fn detects_reserved_top_level_bindings() {
+        let cm = Arc::new(SourceMap::default());
+        let program = parse(
+            &cm,
+            r#"
+import { __turbopack_async_result__ } from "./a.js";
+var __turbopack_async_dependencies__ = 1;
+export function __turbopack_async_promise__() {}
+function f() { var __turbopack_handle_async_dependencies__; }
+var { unrelated } = {};
+"#,
+        );
+        let bindings = |program: &Program, names: &RuntimeFunctionNames| {
+            reserved_top_level_bindings(program, names)
+                .into_iter()
+                .map(|ident| ident.sym.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            bindings(&program, &Default::default()),
+            vec![
+                "__turbopack_async_result__",
+                "__turbopack_async_dependencies__",
+                "__turbopack_async_promise__",
+            ]
+        );
+
+        let program = parse(
+            &cm,
+            r#"
+var __turbopack_handle_async_dependencies__;
+var handleAsync;
+"#,
+        );
+        let names = RuntimeFunctionNames {
+            handle_async_dependencies: "handleAsync".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(bindings(&program, &names), vec!["handleAsync"]);
+
+        assert!(bindings(&parse(&cm, "var a = 1;"), &Default::default()).is_empty());
+    }
+
+    #[test]
+    fn async_status_is_known() {
+        // Known to be async by itself, regardless of its references.
+        assert!(is_async_status_known(true, false));
+        // Known to be sync or async through the async module info.
+        assert!(is_async_status_known(false, true));
+        // Unknown without the async module info.
+        assert!(!is_async_status_known(false, false));
+    }
+
+    #[test]
+    fn async_module_kinds() {
+        let kind = AsyncModuleKind::new(true, false).unwrap();
+        assert_eq!(kind, AsyncModuleKind::TopLevelAwait);
+        assert!(kind.has_top_level_await());
+        assert!(!kind.awaits_dependencies());
+
+        let kind = AsyncModuleKind::new(false, true).unwrap();
+        assert_eq!(kind, AsyncModuleKind::AsyncDependencies);
+        assert!(!kind.has_top_level_await());
+        assert!(kind.awaits_dependencies());
+
+        let kind = AsyncModuleKind::new(true, true).unwrap();
+        assert_eq!(kind, AsyncModuleKind::TopLevelAwaitAndAsyncDependencies);
+        assert!(kind.has_top_level_await());
+        assert!(kind.awaits_dependencies());
+
+        assert_eq!(AsyncModuleKind::new(false, false), None);
+    }
 }
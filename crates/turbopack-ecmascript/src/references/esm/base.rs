@@ -1,65 +1,372 @@
-use anyhow::{anyhow, bail, Result};
-use lazy_static::lazy_static;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    future::Future,
+    hash::Hash,
+    sync::Arc,
+};
+
+use anyhow::{bail, Result};
+use indexmap::{indexmap, IndexMap, IndexSet};
 use swc_core::{
-    common::DUMMY_SP,
-    ecma::ast::{self, Expr, ExprStmt, Ident, Lit, ModuleItem, Program, Script, Stmt},
-    quote,
+    common::{BytePos, SourceMap, Span, SyntaxContext, DUMMY_SP},
+    ecma::{
+        ast::{self, Decl, Expr, ExprStmt, Ident, ModuleItem, Program, Stmt},
+        codegen::{text_writer::JsWriter, Emitter},
+        visit::{visit_obj_and_computed, AstParentKind, Visit, VisitMut, VisitWith},
+    },
+    quote, quote_expr,
 };
-use turbo_tasks::{Value, ValueToString, Vc};
+use turbo_tasks::{ReadRef, TryJoinIterExt, Value, ValueToString, Vc};
+use turbo_tasks_fs::{FileContent, FileJsonContent, FileSystemPath};
+use turbo_tasks_hash::{encode_hex, hash_xxh3_hash64};
 use turbopack_core::{
+    asset::Asset,
     chunk::{
-        ChunkItemExt, ChunkableModule, ChunkableModuleReference, ChunkingContext, ChunkingType,
-        ChunkingTypeOption, ModuleId,
+        AsyncModuleInfo, ChunkItemExt, ChunkableModule, ChunkableModuleReference, ChunkingContext,
+        ChunkingType, ChunkingTypeOption, ModuleId, ModuleIds, OptionModuleId,
     },
-    issue::{IssueSeverity, IssueSource},
-    module::Module,
-    reference::ModuleReference,
+    context::AssetContext,
+    ident::AssetIdent,
+    issue::{analyze::AnalyzeIssue, IssueExt, IssueSeverity, IssueSource, StyledString},
+    module::{Module, Modules},
+    reference::{primary_referenced_modules, ModuleReference},
     reference_type::EcmaScriptModulesReferenceSubType,
     resolve::{
+        find_context_file,
+        options::ImportMap,
         origin::{ResolveOrigin, ResolveOriginExt},
         parse::Request,
-        ModulePart, ModuleResolveResult, ModuleResolveResultItem,
+        FindContextFileResult, ModulePart, ModuleResolveResult, ModuleResolveResultItem,
+        RequestKey,
     },
 };
 
 use crate::{
-    analyzer::imports::ImportAnnotations,
-    chunk::{EcmascriptChunkPlaceable, EcmascriptChunkingContext},
-    code_gen::{CodeGenerateable, CodeGeneration},
+    analyzer::imports::{ImportAnnotations, ImportAttributes, ImportRemote, ImportResourceHint},
+    chunk::{
+        EcmascriptChunkPlaceable, EcmascriptChunkingContext, EcmascriptExports, HoistingMarkers,
+        OptionEcmascriptChunkPlaceable, RuntimeFunctionNames,
+    },
+    code_gen::{CodeGenerateable, CodeGeneration, VisitorFactory},
     create_visitor, magic_identifier,
-    references::util::{request_to_string, throw_module_not_found_expr},
+    references::{
+        async_module::{is_async_reference, AsyncReferenceTarget},
+        esm::{
+            data_url::data_url_module_stmt,
+            export::EsmExport,
+            external::{
+                esm_external_import_decl, external_interop_needed, external_package_name,
+                external_stmt, node_builtin_stmt, remote_stmt, unsupported_external_message,
+                NODE_PROTOCOL,
+            },
+            external_check::check_externals_for_environment,
+            hoisting::{
+                hoisting_anchor, import_comment, import_comment_stmt, import_comment_text,
+                insert_hoisted_import, insert_hoisted_stmt,
+            },
+            import_metrics::{emit_generated_import, GeneratedImportKind},
+            inline_json::{inlinable_json, inline_json_stmt},
+            request_transform::RequestTransform,
+        },
+        util::{request_to_string, throw_module_not_found_expr_with_origin},
+    },
     resolve::esm_resolve,
+    EcmascriptModuleAsset,
 };
 
+/// What a reference resolves to, for code generation. Equality and hashing
+/// are by content, so it can key maps: the same placeable, or the same
+/// request of an external, builtin or data URL, compares equal. Placeables
+/// are compared as [Vc]s, so they have to be resolved first.
 #[turbo_tasks::value]
+#[derive(Clone, Hash)]
 pub enum ReferencedAsset {
     Some(Vc<Box<dyn EcmascriptChunkPlaceable>>),
     OriginalReferenceTypeExternal(String),
+    /// A Node.js builtin external requested with the `node:` protocol, e.g.
+    /// `node:fs`. It's required by its name without the protocol, which older
+    /// runtimes don't understand.
+    NodeBuiltin(String),
+    /// A `data:` URL, whose module is evaluated inline.
+    DataUrl(String),
+    /// A JSON module whose value is inlined into the importing module, see
+    /// [EsmAssetReference::inlined_referenced_asset].
+    InlineJson(Vc<FileJsonContent>),
+    /// A module exposed by a module federation remote container, requested
+    /// by a `remote` annotation. It's loaded at runtime, so it's not resolved.
+    Remote {
+        container: String,
+        scope: String,
+        module: String,
+    },
     None,
 }
 
 impl ReferencedAsset {
+    /// Whether this is an external that's loaded with
+    /// `__turbopack_external_require__` or `__turbopack_external_import__`.
+    /// Node.js builtins are always required synchronously and don't count.
+    pub fn is_external(&self) -> bool {
+        matches!(self, ReferencedAsset::OriginalReferenceTypeExternal(_))
+    }
+
+    /// Whether this is a module of a module federation remote container,
+    /// which is always imported asynchronously.
+    pub fn is_remote(&self) -> bool {
+        matches!(self, ReferencedAsset::Remote { .. })
+    }
+
+    fn from_remote(remote: ImportRemote) -> Self {
+        let ImportRemote {
+            container,
+            scope,
+            module,
+        } = remote;
+        ReferencedAsset::Remote {
+            container,
+            scope,
+            module,
+        }
+    }
+
     pub async fn get_ident(&self) -> Result<Option<String>> {
         Ok(match self {
             ReferencedAsset::Some(asset) => Some(Self::get_ident_from_placeable(asset).await?),
-            ReferencedAsset::OriginalReferenceTypeExternal(request) => {
+            ReferencedAsset::OriginalReferenceTypeExternal(request)
+            | ReferencedAsset::NodeBuiltin(request) => {
                 Some(magic_identifier::mangle(&format!("external {}", request)))
             }
-            ReferencedAsset::None => None,
+            // Identical data URLs evaluate to the same statement, which is
+            // only inserted once, so they share a binding.
+            ReferencedAsset::DataUrl(url) => Some(magic_identifier::mangle(&format!(
+                "inline module {}",
+                encode_hex(hash_xxh3_hash64(url))
+            ))),
+            ReferencedAsset::Remote {
+                container, module, ..
+            } => Some(magic_identifier::mangle(&format!(
+                "remote {container}/{module}"
+            ))),
+            // Inlined JSON is bound to the ident of its module.
+            ReferencedAsset::InlineJson(_) | ReferencedAsset::None => None,
         })
     }
 
+    /// Like [ReferencedAsset::get_ident], but mangles a short hash of the ident
+    /// instead of the path, see [stable_ident].
+    pub async fn get_stable_ident(&self) -> Result<Option<String>> {
+        Ok(self.get_ident().await?.as_deref().map(stable_ident))
+    }
+
     pub(crate) async fn get_ident_from_placeable(
         asset: &Vc<Box<dyn EcmascriptChunkPlaceable>>,
     ) -> Result<String> {
-        let path = asset.ident().to_string().await?;
-        Ok(magic_identifier::mangle(&format!(
-            "imported module {}",
-            path
-        )))
+        Ok(imported_module_ident(
+            &imported_module_name(*asset, None).await?,
+            0,
+        ))
+    }
+}
+
+/// The name of `placeable` when it's imported as `part`. Without a `part`,
+/// the part of the module itself is used, if any.
+///
+/// The part is part of the name, so importing different parts of the same
+/// module doesn't bind them to the same ident.
+async fn imported_module_name(
+    placeable: Vc<Box<dyn EcmascriptChunkPlaceable>>,
+    part: Option<Vc<ModulePart>>,
+) -> Result<String> {
+    let ident = placeable.ident().await?;
+    let Some(part) = part.or(ident.part) else {
+        return Ok(placeable.ident().to_string().await?.clone_value());
+    };
+    let path = AssetIdent::new(Value::new(AssetIdent {
+        part: None,
+        ..ident.clone_value()
+    }))
+    .to_string()
+    .await?;
+    Ok(module_name_with_part(&path, &part.to_string().await?))
+}
+
+fn module_name_with_part(path: &str, part: &str) -> String {
+    format!("{path} (part {part})")
+}
+
+/// Creates the ident for an imported module with the name `path`, see
+/// [imported_module_name]. `index` counts the distinct modules with the same
+/// name imported before it.
+fn imported_module_ident(path: &str, index: usize) -> String {
+    if index == 0 {
+        magic_identifier::mangle(&format!("imported module {}", path))
+    } else {
+        magic_identifier::mangle(&format!("imported module {} #{}", path, index + 1))
+    }
+}
+
+/// Creates a short ident from a hash of the readable ident `ident`.
+///
+/// Module paths are relative to the root of their file system, so the ident
+/// doesn't depend on where the project is located on disk.
+pub(crate) fn stable_ident(ident: &str) -> String {
+    magic_identifier::mangle(&format!("module {}", encode_hex(hash_xxh3_hash64(ident))))
+}
+
+/// Converts `ident` to a [stable_ident] if the chunking context asks for it.
+pub(crate) async fn import_ident(
+    ident: Option<String>,
+    chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+) -> Result<Option<String>> {
+    if *chunking_context.stable_import_idents().await? {
+        Ok(ident.as_deref().map(stable_ident))
+    } else {
+        Ok(ident)
+    }
+}
+
+/// Assigns an ident to each distinct module in `modules`, given in import
+/// order together with their path.
+///
+/// Distinct modules can have the same path, e.g. virtual modules. The first
+/// of them keeps the plain ident and later ones get a numbered suffix, so the
+/// idents stay deterministic.
+fn disambiguate_imported_module_idents<T: Copy + Eq + Hash>(
+    modules: impl IntoIterator<Item = (String, T)>,
+) -> IndexMap<T, String> {
+    let mut modules_per_path = HashMap::<String, usize>::new();
+    let mut idents = IndexMap::new();
+    for (path, module) in modules {
+        if idents.contains_key(&module) {
+            continue;
+        }
+        let index = modules_per_path.entry(path.clone()).or_default();
+        idents.insert(module, imported_module_ident(&path, *index));
+        *index += 1;
+    }
+    idents
+}
+
+/// A module imported as an optional part.
+type ImportedModule = (
+    Vc<Box<dyn EcmascriptChunkPlaceable>>,
+    Option<Vc<ModulePart>>,
+);
+
+#[turbo_tasks::value(transparent)]
+struct ImportedModuleIdents(IndexMap<ImportedModule, String>);
+
+/// The idents of all modules imported by `origin`, see
+/// [disambiguate_imported_module_idents].
+#[turbo_tasks::function]
+async fn imported_module_idents(
+    origin: Vc<Box<dyn ResolveOrigin>>,
+) -> Result<Vc<ImportedModuleIdents>> {
+    let Some(module) = Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(origin).await? else {
+        return Ok(Vc::cell(IndexMap::new()));
+    };
+    let mut modules = Vec::new();
+    for reference in module.analyze().await?.references.await?.iter() {
+        let Some(reference) =
+            Vc::try_resolve_downcast_type::<EsmAssetReference>(*reference).await?
+        else {
+            continue;
+        };
+        for part in reference.await?.parts() {
+            if let ReferencedAsset::Some(placeable) =
+                &*reference.get_part_referenced_asset(part).await?
+            {
+                let imported_module = EsmAssetReference::imported_module(*placeable, part).await?;
+                modules.push((
+                    imported_module_name(imported_module.0, imported_module.1).await?,
+                    imported_module,
+                ));
+            }
+        }
+    }
+    Ok(Vc::cell(disambiguate_imported_module_idents(modules)))
+}
+
+/// What an import binding is emitted for: the imported module and part,
+/// imported with a chunking type. References with the same key emit the same
+/// binding, as they bind the same ident.
+type ImportBindingKey = (ImportedModule, Option<ChunkingType>);
+
+/// The reference emitting an import binding, and whether the binding is a
+/// namespace import, because any of the references with its key is one.
+type ImportBindingOwner = (Vc<EsmAssetReference>, bool);
+
+#[turbo_tasks::value(transparent)]
+struct ImportBindingOwners(IndexMap<ImportBindingKey, ImportBindingOwner>);
+
+/// The reference of `origin` emitting every import binding when chunked by
+/// `chunking_context`, see [first_binding_owners]. A plain and a namespace
+/// import of the same module share one binding, which is then bound to the
+/// namespace object.
+#[turbo_tasks::function]
+async fn import_binding_owners(
+    origin: Vc<Box<dyn ResolveOrigin>>,
+    chunking_context: Vc<Box<dyn ChunkingContext>>,
+) -> Result<Vc<ImportBindingOwners>> {
+    let Some(module) = Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(origin).await? else {
+        return Ok(Vc::cell(IndexMap::new()));
+    };
+    let mut references = Vec::new();
+    for reference in module.analyze().await?.references.await?.iter() {
+        if let Some(reference) =
+            Vc::try_resolve_downcast_type::<EsmAssetReference>(*reference).await?
+        {
+            references.push(reference);
+        }
+    }
+    let keys = binding_keys_of_references(references, chunking_context).await?;
+    let mut namespace_keys = HashSet::new();
+    for &(key, reference) in &keys {
+        if reference.await?.namespace_import {
+            namespace_keys.insert(key);
+        }
+    }
+    Ok(Vc::cell(
+        first_binding_owners(keys)
+            .into_iter()
+            .map(|(key, owner)| (key, (owner, namespace_keys.contains(&key))))
+            .collect(),
+    ))
+}
+
+/// The [ImportBindingKey]s of every reference when chunked by
+/// `chunking_context`, in the order of the references.
+async fn binding_keys_of_references(
+    references: impl IntoIterator<Item = Vc<EsmAssetReference>>,
+    chunking_context: Vc<Box<dyn ChunkingContext>>,
+) -> Result<Vec<(ImportBindingKey, Vc<EsmAssetReference>)>> {
+    let mut keys = Vec::new();
+    for reference in references {
+        let reference = reference.resolve().await?;
+        for key in reference.binding_keys(chunking_context).await? {
+            keys.push((key, reference));
+        }
+    }
+    Ok(keys)
+}
+
+/// Keeps the first reference for every key. Later references with the same
+/// key don't emit a binding of their own, they use the one of the first.
+fn first_binding_owners<K: Hash + Eq, R>(
+    references: impl IntoIterator<Item = (K, R)>,
+) -> IndexMap<K, R> {
+    let mut owners = IndexMap::new();
+    for (key, reference) in references {
+        owners.entry(key).or_insert(reference);
     }
+    owners
 }
 
+/// Option<String> of the ident a referenced asset is bound to.
+#[turbo_tasks::value(transparent)]
+pub struct ReferencedAssetIdent(Option<String>);
+
 #[turbo_tasks::value_impl]
 impl ReferencedAsset {
     #[turbo_tasks::function]
@@ -67,6 +374,11 @@ impl ReferencedAsset {
         // TODO handle multiple keyed results
         for (_key, result) in resolve_result.await?.primary.iter() {
             match result {
+                ModuleResolveResultItem::OriginalReferenceTypeExternal(request)
+                    if request.starts_with(NODE_PROTOCOL) =>
+                {
+                    return Ok(ReferencedAsset::NodeBuiltin(request.clone()).cell());
+                }
                 ModuleResolveResultItem::OriginalReferenceTypeExternal(request) => {
                     return Ok(
                         ReferencedAsset::OriginalReferenceTypeExternal(request.clone()).cell(),
@@ -86,17 +398,161 @@ impl ReferencedAsset {
         }
         Ok(ReferencedAsset::cell(ReferencedAsset::None))
     }
+
+    /// Like [ReferencedAsset::from_resolve_result], but a `data:` URL
+    /// `request` that resolves to itself is a [ReferencedAsset::DataUrl],
+    /// whose module is evaluated inline. Externals that only have a name
+    /// starting with `data:` stay externals.
+    #[turbo_tasks::function]
+    pub async fn from_request_and_resolve_result(
+        request: Vc<Request>,
+        resolve_result: Vc<ModuleResolveResult>,
+    ) -> Result<Vc<Self>> {
+        let referenced_asset = Self::from_resolve_result(resolve_result);
+        let Request::Uri {
+            protocol,
+            remainder,
+        } = &*request.await?
+        else {
+            return Ok(referenced_asset);
+        };
+        if protocol != "data:" {
+            return Ok(referenced_asset);
+        }
+        Ok(match &*referenced_asset.await? {
+            ReferencedAsset::OriginalReferenceTypeExternal(url)
+                if *url == format!("{protocol}{remainder}") =>
+            {
+                ReferencedAsset::DataUrl(url.clone()).cell()
+            }
+            _ => referenced_asset,
+        })
+    }
+
+    /// The placeable of an internal module, or [None] for externals, inline
+    /// modules and unresolved requests.
+    #[turbo_tasks::function]
+    pub async fn as_placeable(self: Vc<Self>) -> Result<Vc<OptionEcmascriptChunkPlaceable>> {
+        Ok(Vc::cell(match &*self.await? {
+            ReferencedAsset::Some(placeable) => Some(*placeable),
+            _ => None,
+        }))
+    }
+
+    /// Whether importing this asset needs the ESM interop, which synthesizes a
+    /// `default` export from the CommonJS `module.exports`.
+    ///
+    /// Externals are looked up in the `node_modules` directories of
+    /// `lookup_path` and its parents. Externals that aren't found as a
+    /// package, e.g. Node.js builtins, need the interop.
+    #[turbo_tasks::function]
+    pub async fn interop_needed(
+        self: Vc<Self>,
+        lookup_path: Vc<FileSystemPath>,
+    ) -> Result<Vc<bool>> {
+        Ok(Vc::cell(match &*self.await? {
+            ReferencedAsset::Some(asset) => matches!(
+                &*asset.get_exports().await?,
+                EcmascriptExports::CommonJs | EcmascriptExports::Value
+            ),
+            ReferencedAsset::OriginalReferenceTypeExternal(request) => {
+                let mut package_json = None;
+                if let Some(package_name) = external_package_name(request) {
+                    let names = Vc::cell(vec![format!("node_modules/{package_name}/package.json")]);
+                    if let FindContextFileResult::Found(path, _) =
+                        &*find_context_file(lookup_path, names).await?
+                    {
+                        if let FileJsonContent::Content(content) = &*path.read_json().await? {
+                            package_json = Some(content.clone());
+                        }
+                    }
+                }
+                external_interop_needed(request, package_json.as_ref())
+            }
+            ReferencedAsset::NodeBuiltin(_) => true,
+            ReferencedAsset::DataUrl(_)
+            | ReferencedAsset::InlineJson(_)
+            | ReferencedAsset::Remote { .. }
+            | ReferencedAsset::None => false,
+        }))
+    }
+}
+
+/// Determines how an [EsmAssetReference] treats resolutions that end up
+/// outside of the project root, i. e. outside of the root of the file system
+/// the importing module is located in.
+#[turbo_tasks::value(serialization = "auto_for_input")]
+#[derive(Default, Debug, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum OutOfRootPolicy {
+    /// Resolving outside of the project root is allowed silently.
+    #[default]
+    Allow,
+    /// Resolving outside of the project root emits a warning issue.
+    Warn,
+}
+
+/// The shape of the namespace object bound by a namespace import
+/// (`import * as ns from "..."`).
+#[turbo_tasks::value(shared, serialization = "auto_for_input")]
+#[derive(Default, Debug, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum EsmNamespaceShape {
+    /// The namespace object is frozen as required by the spec. Exports are
+    /// still exposed through getters, so live bindings keep working. Only the
+    /// namespace objects of modules with static ESM exports are frozen, and
+    /// the runtime leaves the promises of async modules alone.
+    Frozen,
+    /// The namespace object is left extensible, which avoids the cost of
+    /// freezing it.
+    #[default]
+    Extensible,
 }
 
 #[turbo_tasks::value]
-#[derive(Hash, Debug)]
+#[derive(Hash, Clone, Debug)]
 pub struct EsmAssetReference {
     pub origin: Vc<Box<dyn ResolveOrigin>>,
     pub request: Vc<Request>,
     pub annotations: ImportAnnotations,
     pub issue_source: Option<Vc<IssueSource>>,
-    pub export_name: Option<Vc<ModulePart>>,
+    /// The parts of the module that are imported. Each part is resolved to a
+    /// module of its own and bound to an ident of its own. Without parts, the
+    /// whole module is imported.
+    pub export_names: Vec<Vc<ModulePart>>,
     pub import_externals: bool,
+    pub out_of_root_policy: OutOfRootPolicy,
+    /// Whether the reference was created for a namespace import.
+    pub namespace_import: bool,
+    /// Whether invalid import annotations fail the build. Otherwise they emit
+    /// an issue and are ignored.
+    pub strict_annotations: bool,
+    /// The import attributes of the import, e.g. `with { type: "json" }`.
+    pub import_attributes: Option<Vc<ImportAttributes>>,
+    /// Where mismatches of the `type` import attribute are reported, usually
+    /// the `type` attribute itself. Falls back to `issue_source`.
+    pub type_attribute_issue_source: Option<Vc<IssueSource>>,
+    /// Whether bindings of the import are used in the importing module. When
+    /// the import is ignored, they are bound to an empty namespace object.
+    pub bindings_used: bool,
+    /// Rewrites the request before it's resolved.
+    pub request_transform: Option<Vc<Box<dyn RequestTransform>>>,
+    /// The package export conditions to resolve the request with, e.g.
+    /// `worker`, instead of the ones of the resolve options. Without them,
+    /// the conditions of the resolve options are used.
+    pub conditions: Option<Vec<String>>,
+    /// A browser-style import map consulted when resolving the request, see
+    /// [crate::resolve::browser_import_map]. Its entries take precedence over
+    /// the import map of the resolve options.
+    pub import_map: Option<Vc<ImportMap>>,
+    /// Whether the importing module imports nothing but the default export of
+    /// the request, possibly besides evaluating it. A JSON module imported
+    /// this way can be inlined, see
+    /// [EsmAssetReference::inlined_referenced_asset].
+    pub default_import_only: bool,
+    /// The span of the import in the original module, or [DUMMY_SP] when the
+    /// reference wasn't created for an import statement. `issue_source` is
+    /// created from the same span.
+    #[turbo_tasks(trace_ignore)]
+    pub span: Span,
 }
 
 /// A list of [EsmAssetReference]s
@@ -104,9 +560,78 @@ pub struct EsmAssetReference {
 pub struct EsmAssetReferences(Vec<Vc<EsmAssetReference>>);
 
 impl EsmAssetReference {
-    fn get_origin(&self) -> Vc<Box<dyn ResolveOrigin>> {
+    /// The start of the import in the original source as an empty span, if
+    /// it is known. Code generated for the import is mapped to it. Spanning
+    /// the whole import would also move the import's trailing comments into
+    /// the generated code.
+    pub fn span(&self) -> Option<Span> {
+        (!self.span.is_dummy()).then(|| self.span.shrink_to_lo())
+    }
+
+    /// The imported parts, or a single [None] when the whole module is
+    /// imported.
+    fn parts(&self) -> Vec<Option<Vc<ModulePart>>> {
+        if self.export_names.is_empty() {
+            vec![None]
+        } else {
+            self.export_names.iter().copied().map(Some).collect()
+        }
+    }
+
+    /// Whether the chunks of the imported module are prefetched when the
+    /// browser is idle, as requested by the `parallel-prefetch` chunking
+    /// type. Unlike the `preload` annotation, this doesn't block evaluation.
+    fn prefetches_when_idle(&self) -> bool {
+        self.annotations.chunking_type() == Some(PARALLEL_PREFETCH_CHUNKING_TYPE)
+    }
+
+    /// The type of the `type` import attribute, if any.
+    async fn attribute_type(&self) -> Result<Option<String>> {
+        Ok(match self.import_attributes {
+            Some(import_attributes) => import_attributes.await?.ty().map(str::to_string),
+            None => None,
+        })
+    }
+
+    /// `placeable` together with the part it's imported as, both resolved so
+    /// they can be compared.
+    async fn imported_module(
+        placeable: Vc<Box<dyn EcmascriptChunkPlaceable>>,
+        part: Option<Vc<ModulePart>>,
+    ) -> Result<ImportedModule> {
+        let part = match part {
+            Some(part) => Some(part.resolve().await?),
+            None => None,
+        };
+        Ok((placeable.resolve().await?, part))
+    }
+
+    /// The keys of the import bindings this reference emits when chunked by
+    /// `chunking_context`, one for every imported part resolving to a module
+    /// in the module graph. Unchunked references don't emit bindings.
+    async fn binding_keys(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn ChunkingContext>>,
+    ) -> Result<Vec<ImportBindingKey>> {
+        let this = self.await?;
+        let chunking_type = *self.chunking_type_in_context(chunking_context).await?;
+        if chunking_type.is_none() {
+            return Ok(Vec::new());
+        }
+        let mut keys = Vec::new();
+        for part in this.parts() {
+            if let ReferencedAsset::Some(asset) = *self.get_part_referenced_asset(part).await? {
+                keys.push((Self::imported_module(asset, part).await?, chunking_type));
+            }
+        }
+        Ok(keys)
+    }
+
+    /// The origin to resolve from, with the transition `transition` or, if
+    /// there's none, the one of the `transition` annotation applied.
+    fn get_origin(&self, transition: Option<&str>) -> Vc<Box<dyn ResolveOrigin>> {
         let mut origin = self.origin;
-        if let Some(transition) = self.annotations.transition() {
+        if let Some(transition) = effective_transition(transition, self.annotations.transition()) {
             origin = origin.with_transition(transition.to_string());
         }
         origin
@@ -115,6 +640,9 @@ impl EsmAssetReference {
 
 #[turbo_tasks::value_impl]
 impl EsmAssetReference {
+    /// Creates a reference importing the part `export_name` of the module, or
+    /// the whole module without a part. See
+    /// [EsmAssetReference::new_with_export_names] to import several parts.
     #[turbo_tasks::function]
     pub fn new(
         origin: Vc<Box<dyn ResolveOrigin>>,
@@ -123,232 +651,3026 @@ impl EsmAssetReference {
         annotations: Value<ImportAnnotations>,
         export_name: Option<Vc<ModulePart>>,
         import_externals: bool,
+    ) -> Vc<Self> {
+        Self::new_with_export_names(
+            origin,
+            request,
+            issue_source,
+            annotations,
+            export_name.into_iter().collect(),
+            import_externals,
+        )
+    }
+
+    /// Creates a reference importing the parts `export_names` of the module.
+    /// Every part is resolved to a module of its own and bound separately.
+    #[turbo_tasks::function]
+    pub fn new_with_export_names(
+        origin: Vc<Box<dyn ResolveOrigin>>,
+        request: Vc<Request>,
+        issue_source: Option<Vc<IssueSource>>,
+        annotations: Value<ImportAnnotations>,
+        export_names: Vec<Vc<ModulePart>>,
+        import_externals: bool,
     ) -> Vc<Self> {
         Self::cell(EsmAssetReference {
             origin,
             request,
             issue_source,
             annotations: annotations.into_value(),
-            export_name,
+            export_names,
             import_externals,
+            out_of_root_policy: OutOfRootPolicy::default(),
+            namespace_import: false,
+            strict_annotations: false,
+            import_attributes: None,
+            type_attribute_issue_source: None,
+            bindings_used: false,
+            request_transform: None,
+            conditions: None,
+            import_map: None,
+            default_import_only: false,
+            span: DUMMY_SP,
         })
     }
 
+    /// Creates a reference importing `request` from `origin` with the defaults
+    /// of the common case: no issue source, default annotations, the whole
+    /// module instead of a part of it, and externals loaded with
+    /// `__turbopack_external_require__` instead of being imported.
     #[turbo_tasks::function]
-    pub(crate) fn get_referenced_asset(self: Vc<Self>) -> Vc<ReferencedAsset> {
-        ReferencedAsset::from_resolve_result(self.resolve_reference())
+    pub fn simple(origin: Vc<Box<dyn ResolveOrigin>>, request: Vc<Request>) -> Vc<Self> {
+        Self::new(origin, request, None, Default::default(), None, false)
     }
-}
 
-#[turbo_tasks::value_impl]
-impl ModuleReference for EsmAssetReference {
+    /// Returns a copy of this reference that requests `request` instead. All
+    /// other fields are preserved, so this can be used to redirect imports,
+    /// e.g. for dependency migrations.
     #[turbo_tasks::function]
-    async fn resolve_reference(&self) -> Result<Vc<ModuleResolveResult>> {
-        let ty = Value::new(match &self.export_name {
-            Some(part) => EcmaScriptModulesReferenceSubType::ImportPart(*part),
-            None => EcmaScriptModulesReferenceSubType::Import,
-        });
+    pub async fn with_request(self: Vc<Self>, request: Vc<Request>) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.request = request;
+        Ok(this.cell())
+    }
 
-        Ok(esm_resolve(
-            self.get_origin().resolve().await?,
-            self.request,
-            ty,
-            IssueSeverity::Error.cell(),
-            self.issue_source,
-        ))
+    /// Returns a copy of this reference with the given import attributes.
+    #[turbo_tasks::function]
+    pub async fn with_import_attributes(
+        self: Vc<Self>,
+        import_attributes: Vc<ImportAttributes>,
+    ) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.import_attributes = Some(import_attributes);
+        Ok(this.cell())
     }
-}
 
-#[turbo_tasks::value_impl]
-impl ValueToString for EsmAssetReference {
+    /// Returns a copy of this reference that reports mismatches of the `type`
+    /// import attribute at `issue_source` instead of at the whole import.
     #[turbo_tasks::function]
-    async fn to_string(&self) -> Result<Vc<String>> {
-        Ok(Vc::cell(format!(
-            "import {} {}",
-            self.request.to_string().await?,
-            self.annotations
-        )))
+    pub async fn with_type_attribute_issue_source(
+        self: Vc<Self>,
+        issue_source: Vc<IssueSource>,
+    ) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.type_attribute_issue_source = Some(issue_source);
+        Ok(this.cell())
     }
-}
 
-#[turbo_tasks::value_impl]
-impl ChunkableModuleReference for EsmAssetReference {
+    /// Returns a copy of this reference carrying the span of the import,
+    /// given as the `lo` and `hi` byte positions.
     #[turbo_tasks::function]
-    fn chunking_type(&self) -> Result<Vc<ChunkingTypeOption>> {
-        Ok(Vc::cell(
-            if let Some(chunking_type) = self.annotations.chunking_type() {
-                match chunking_type {
-                    "parallel" => Some(ChunkingType::ParallelInheritAsync),
-                    "none" => None,
-                    _ => return Err(anyhow!("unknown chunking_type: {}", chunking_type)),
-                }
-            } else {
-                Some(ChunkingType::ParallelInheritAsync)
-            },
-        ))
+    pub async fn with_span(self: Vc<Self>, lo: u32, hi: u32) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.span = Span::new(BytePos(lo), BytePos(hi), Default::default());
+        Ok(this.cell())
     }
-}
 
-#[turbo_tasks::value_impl]
-impl CodeGenerateable for EsmAssetReference {
+    /// Returns a copy of this reference that is marked as a namespace import.
     #[turbo_tasks::function]
-    async fn code_generation(
+    pub async fn with_namespace_import(self: Vc<Self>) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.namespace_import = true;
+        Ok(this.cell())
+    }
+
+    /// Returns a copy of this reference that fails on invalid import
+    /// annotations instead of emitting an issue, e.g. to enforce valid
+    /// annotations in CI.
+    #[turbo_tasks::function]
+    pub async fn with_strict_annotations(self: Vc<Self>) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.strict_annotations = true;
+        Ok(this.cell())
+    }
+
+    /// Returns a copy of this reference that is marked as having used
+    /// bindings.
+    #[turbo_tasks::function]
+    pub async fn with_bindings_used(self: Vc<Self>) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.bindings_used = true;
+        Ok(this.cell())
+    }
+
+    /// Returns a copy of this reference that resolves its request rewritten
+    /// by `transform`.
+    #[turbo_tasks::function]
+    pub async fn with_request_transform(
         self: Vc<Self>,
-        chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
-    ) -> Result<Vc<CodeGeneration>> {
-        let mut visitors = Vec::new();
+        transform: Vc<Box<dyn RequestTransform>>,
+    ) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.request_transform = Some(transform);
+        Ok(this.cell())
+    }
 
-        let this = &*self.await?;
-        let chunking_type = self.chunking_type().await?;
-        let resolved = self.resolve_reference().await?;
+    /// Returns a copy of this reference that resolves its request with the
+    /// package export `conditions` instead of the ones of the resolve
+    /// options.
+    #[turbo_tasks::function]
+    pub async fn with_conditions(self: Vc<Self>, conditions: Vec<String>) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.conditions = Some(conditions);
+        Ok(this.cell())
+    }
 
-        // Insert code that throws immediately at time of import if a request is
-        // unresolvable
-        if resolved.is_unresolveable_ref() {
-            let request = request_to_string(this.request).await?.to_string();
-            visitors.push(create_visitor!(visit_mut_program(program: &mut Program) {
-                insert_hoisted_stmt(program, Stmt::Expr(ExprStmt {
-                        expr: Box::new(throw_module_not_found_expr(
-                          &request
-                        )),
-                        span: DUMMY_SP,
-                    }));
-            }));
+    /// Returns a copy of this reference that resolves its request with
+    /// `import_map`, e.g. to remap bare specifiers to URLs. Specifiers mapped
+    /// to URLs are loaded as externals.
+    #[turbo_tasks::function]
+    pub async fn with_import_map(self: Vc<Self>, import_map: Vc<ImportMap>) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.import_map = Some(import_map);
+        Ok(this.cell())
+    }
 
-            return Ok(CodeGeneration { visitors }.into());
-        }
+    /// Returns a copy of this reference that is marked as importing only the
+    /// default export, see [EsmAssetReference::default_import_only].
+    #[turbo_tasks::function]
+    pub async fn with_default_import_only(self: Vc<Self>) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.default_import_only = true;
+        Ok(this.cell())
+    }
 
-        // only chunked references can be imported
-        if chunking_type.is_some() {
-            let referenced_asset = self.get_referenced_asset().await?;
-            let import_externals = this.import_externals;
-            if let Some(ident) = referenced_asset.get_ident().await? {
-                match &*referenced_asset {
-                    ReferencedAsset::Some(asset) => {
-                        let id = asset
-                            .as_chunk_item(Vc::upcast(chunking_context))
-                            .id()
-                            .await?;
-                        visitors.push(create_visitor!(visit_mut_program(program: &mut Program) {
-                            let stmt = quote!(
-                                "var $name = __turbopack_import__($id);" as Stmt,
-                                name = Ident::new(ident.clone().into(), DUMMY_SP),
-                                id: Expr = Expr::Lit(match &*id {
-                                    ModuleId::String(s) => s.clone().into(),
-                                    ModuleId::Number(n) => (*n as f64).into(),
-                                })
-                            );
-                            insert_hoisted_stmt(program, stmt);
-                        }));
-                    }
-                    ReferencedAsset::OriginalReferenceTypeExternal(request) => {
-                        if !*chunking_context
-                            .environment()
-                            .supports_commonjs_externals()
-                            .await?
-                        {
-                            bail!(
-                                "the chunking context does not support external modules (request: \
-                                 {})",
-                                request
-                            );
-                        }
-                        let request = request.clone();
-                        visitors.push(create_visitor!(visit_mut_program(program: &mut Program) {
-                            // TODO Technically this should insert a ESM external, but we don't support that yet
-                            let stmt = if import_externals {
-                                quote!(
-                                    "var $name = __turbopack_external_import__($id);" as Stmt,
-                                    name = Ident::new(ident.clone().into(), DUMMY_SP),
-                                    id: Expr = Expr::Lit(request.clone().into())
-                                )
-                            } else {
-                                quote!(
-                                    "var $name = __turbopack_external_require__($id, true);" as Stmt,
-                                    name = Ident::new(ident.clone().into(), DUMMY_SP),
-                                    id: Expr = Expr::Lit(request.clone().into())
-                                )
-                            };
-                            insert_hoisted_stmt(program, stmt);
-                        }));
+    /// Returns a copy of this reference using the given [OutOfRootPolicy].
+    #[turbo_tasks::function]
+    pub async fn with_out_of_root_policy(
+        self: Vc<Self>,
+        policy: Value<OutOfRootPolicy>,
+    ) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.out_of_root_policy = policy.into_value();
+        Ok(this.cell())
+    }
+
+    /// Resolves the reference like [ModuleReference::resolve_reference], but
+    /// with the transition `transition` instead of the one of the
+    /// `transition` annotation, if given.
+    ///
+    /// With several parts, the results of all parts are merged, see
+    /// [merge_part_results].
+    #[turbo_tasks::function]
+    pub async fn resolve_reference_with_transition(
+        self: Vc<Self>,
+        transition: Option<String>,
+    ) -> Result<Vc<ModuleResolveResult>> {
+        let this = self.await?;
+        let result = if this.export_names.len() <= 1 {
+            self.resolve_part(this.export_names.first().copied(), transition)
+        } else {
+            let results = this
+                .export_names
+                .iter()
+                .map(|&part| {
+                    let transition = transition.clone();
+                    async move {
+                        Ok((
+                            part.to_string().await?.clone_value(),
+                            self.resolve_part(Some(part), transition).await?,
+                        ))
                     }
-                    ReferencedAsset::None => {}
+                })
+                .try_join()
+                .await?;
+            merge_part_results(
+                results
+                    .iter()
+                    .map(|(part, result)| (part.as_str(), &**result)),
+            )
+            .cell()
+        };
+
+        if let Some(ty) = this.attribute_type().await? {
+            if let Some(module) = module_with_conflicting_type(&ty, result).await? {
+                AnalyzeIssue {
+                    code: None,
+                    category: Vc::cell("resolve".to_string()),
+                    message: StyledString::Text(import_attribute_type_mismatch_message(
+                        &this.request.to_string().await?,
+                        &ty,
+                        &module.ident().to_string().await?,
+                    ))
+                    .cell(),
+                    source_ident: AssetIdent::from_path(this.origin.origin_path()),
+                    severity: IssueSeverity::Error.into(),
+                    source: this.type_attribute_issue_source.or(this.issue_source),
+                    title: Vc::cell("import attribute type mismatch".to_string()),
                 }
+                .cell()
+                .emit();
             }
         }
 
-        Ok(CodeGeneration { visitors }.into())
-    }
-}
+        if matches!(this.out_of_root_policy, OutOfRootPolicy::Warn) {
+            if let Some(module) = out_of_root_module(this.origin, result).await? {
+                AnalyzeIssue {
+                    code: None,
+                    category: Vc::cell("resolve".to_string()),
+                    message: StyledString::Text(format!(
+                        "The request {} resolves to {}, which is outside of the project root.",
+                        this.request.to_string().await?,
+                        module.ident().to_string().await?
+                    ))
+                    .cell(),
+                    source_ident: AssetIdent::from_path(this.origin.origin_path()),
+                    severity: IssueSeverity::Warning.into(),
+                    source: this.issue_source,
+                    title: Vc::cell("import outside of the project root".to_string()),
+                }
+                .cell()
+                .emit();
+            }
+        }
 
-lazy_static! {
-    static ref ESM_HOISTING_LOCATION: &'static str = Box::leak(Box::new(magic_identifier::mangle(
-        "ecmascript hoisting location"
-    )));
-}
+        Ok(result)
+    }
 
-pub(crate) fn insert_hoisted_stmt(program: &mut Program, stmt: Stmt) {
-    match program {
-        Program::Module(ast::Module { body, .. }) => {
-            let pos = body.iter().position(|item| {
-                if let ModuleItem::Stmt(Stmt::Expr(ExprStmt {
-                    expr: box Expr::Lit(Lit::Str(s)),
-                    ..
-                })) = item
-                {
-                    &*s.value == *ESM_HOISTING_LOCATION
-                } else {
-                    false
-                }
-            });
-            if let Some(pos) = pos {
-                let has_stmt = body[0..pos].iter().any(|item| {
-                    if let ModuleItem::Stmt(item_stmt) = item {
-                        stmt == *item_stmt
-                    } else {
-                        false
-                    }
-                });
-                if !has_stmt {
-                    body.insert(pos, ModuleItem::Stmt(stmt));
-                }
-            } else {
-                body.splice(
-                    0..0,
-                    [
-                        ModuleItem::Stmt(stmt),
-                        ModuleItem::Stmt(Stmt::Expr(ExprStmt {
-                            expr: Box::new(Expr::Lit(Lit::Str((*ESM_HOISTING_LOCATION).into()))),
-                            span: DUMMY_SP,
-                        })),
-                    ],
-                );
+    /// Resolves the request for the single part `part`, or for the whole
+    /// module without a part.
+    #[turbo_tasks::function]
+    async fn resolve_part(
+        &self,
+        part: Option<Vc<ModulePart>>,
+        transition: Option<String>,
+    ) -> Result<Vc<ModuleResolveResult>> {
+        // Remote modules are loaded from their container at runtime, there's
+        // nothing to include in the output.
+        if self.annotations.remote().is_some() {
+            return Ok(ModuleResolveResult {
+                primary: indexmap! { RequestKey::default() => ModuleResolveResultItem::Ignore },
+                affecting_sources: Vec::new(),
             }
+            .cell());
         }
-        Program::Script(Script { body, .. }) => {
-            let pos = body.iter().position(|item| {
-                if let Stmt::Expr(ExprStmt {
-                    expr: box Expr::Lit(Lit::Str(s)),
-                    ..
-                }) = item
-                {
-                    &*s.value == *ESM_HOISTING_LOCATION
-                } else {
-                    false
-                }
-            });
-            if let Some(pos) = pos {
-                body.insert(pos, stmt);
-            } else {
-                body.insert(
-                    0,
-                    Stmt::Expr(ExprStmt {
-                        expr: Box::new(Expr::Lit(Lit::Str((*ESM_HOISTING_LOCATION).into()))),
-                        span: DUMMY_SP,
-                    }),
-                );
-                body.insert(0, stmt);
+        let attribute_type = self.attribute_type().await?;
+        let request = match self.request_transform {
+            Some(transform) => transform.transform(self.request),
+            None => self.request,
+        };
+        Ok(esm_resolve(
+            self.get_origin(transition.as_deref()).resolve().await?,
+            request,
+            Value::new(import_sub_type(part, attribute_type.as_deref())),
+            self.conditions.clone(),
+            self.import_map,
+            IssueSeverity::Error.cell(),
+            self.issue_source,
+        ))
+    }
+
+    /// The asset of the first imported part, or of the whole module without
+    /// parts.
+    #[turbo_tasks::function]
+    pub async fn get_referenced_asset(self: Vc<Self>) -> Result<Vc<ReferencedAsset>> {
+        if let Some(remote) = self.await?.annotations.remote() {
+            return Ok(ReferencedAsset::from_remote(remote).cell());
+        }
+        Ok(ReferencedAsset::from_request_and_resolve_result(
+            self.await?.request,
+            self.resolve_reference(),
+        ))
+    }
+
+    /// The asset of the whole module, regardless of the imported parts.
+    #[turbo_tasks::function]
+    pub(crate) async fn get_whole_module_asset(self: Vc<Self>) -> Result<Vc<ReferencedAsset>> {
+        let this = self.await?;
+        if let Some(remote) = this.annotations.remote() {
+            return Ok(ReferencedAsset::from_remote(remote).cell());
+        }
+        Ok(ReferencedAsset::from_request_and_resolve_result(
+            this.request,
+            self.resolve_part(None, None),
+        ))
+    }
+
+    /// The chunking type of this reference when it's chunked by
+    /// `chunking_context`, regardless of whether the referenced module is
+    /// inlined. References without a `chunking-type` annotation aren't
+    /// chunked when `chunking_context` doesn't chunk unannotated references,
+    /// see [ChunkingContext::chunk_unannotated_references]. Annotations always
+    /// take precedence.
+    #[turbo_tasks::function]
+    pub(crate) async fn chunking_type_in_context(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn ChunkingContext>>,
+    ) -> Result<Vc<ChunkingTypeOption>> {
+        Ok(Vc::cell(chunking_type_in_context(
+            self.await?.annotations.chunking_type(),
+            *self.chunking_type().await?,
+            *chunking_context.chunk_unannotated_references().await?,
+        )))
+    }
+
+    /// The asset code generation binds. It's the referenced asset, except for
+    /// JSON modules that are inlined as [ReferencedAsset::InlineJson]: when
+    /// the chunking context sets an
+    /// [inline_json_max_size](EcmascriptChunkingContext::inline_json_max_size),
+    /// the module isn't larger, and only its default export is imported, see
+    /// [inlinable_json]. Imports annotated with `chunking-type: eager` are
+    /// inlined regardless of the size when the chunking context
+    /// [inlines eager JSON
+    /// imports](EcmascriptChunkingContext::inline_eager_json_imports).
+    /// Live bindings and imports that don't evaluate the module, e.g. weak
+    /// ones, read it at runtime, so they aren't inlined.
+    #[turbo_tasks::function]
+    async fn inlined_referenced_asset(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    ) -> Result<Vc<ReferencedAsset>> {
+        let referenced_asset = self.get_referenced_asset();
+        let max_size = if self.await?.annotations.chunking_type() == Some(EAGER_CHUNKING_TYPE)
+            && *chunking_context.inline_eager_json_imports().await?
+        {
+            u32::MAX
+        } else if let Some(max_size) = *chunking_context.inline_json_max_size().await? {
+            max_size
+        } else {
+            return Ok(referenced_asset);
+        };
+        if !self.await?.default_import_only || *chunking_context.esm_live_bindings().await? {
+            return Ok(referenced_asset);
+        }
+        if !matches!(
+            *self
+                .chunking_type_in_context(Vc::upcast(chunking_context))
+                .await?,
+            Some(ChunkingType::Parallel | ChunkingType::ParallelInheritAsync)
+        ) {
+            return Ok(referenced_asset);
+        }
+        let ReferencedAsset::Some(placeable) = *referenced_asset.await? else {
+            return Ok(referenced_asset);
+        };
+        if !*placeable.is_json().await? {
+            return Ok(referenced_asset);
+        }
+        let content = placeable.content().file_content();
+        let FileContent::Content(file) = &*content.await? else {
+            return Ok(referenced_asset);
+        };
+        let json = content.parse_json();
+        if let FileJsonContent::Content(value) = &*json.await? {
+            if inlinable_json(file.content().len(), max_size, value) {
+                return Ok(ReferencedAsset::InlineJson(json).cell());
             }
         }
+        Ok(referenced_asset)
+    }
+
+    /// The asset the part `part` resolves to, see [EsmAssetReference::parts].
+    #[turbo_tasks::function]
+    pub(crate) async fn get_part_referenced_asset(
+        self: Vc<Self>,
+        part: Option<Vc<ModulePart>>,
+    ) -> Result<Vc<ReferencedAsset>> {
+        if let Some(remote) = self.await?.annotations.remote() {
+            return Ok(ReferencedAsset::from_remote(remote).cell());
+        }
+        Ok(ReferencedAsset::from_request_and_resolve_result(
+            self.await?.request,
+            self.resolve_part(part, None),
+        ))
+    }
+
+    /// The ident the referenced asset is bound to in the importing module.
+    /// Unlike [ReferencedAsset::get_ident], it doesn't collide with the idents
+    /// of other modules imported with the same path.
+    ///
+    /// With several parts, it's the ident of the first part, see
+    /// [EsmAssetReference::get_part_referenced_ident].
+    #[turbo_tasks::function]
+    pub(crate) async fn get_referenced_ident(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    ) -> Result<Vc<ReferencedAssetIdent>> {
+        let part = self.await?.export_names.first().copied();
+        Ok(self.get_part_referenced_ident(part, chunking_context))
+    }
+
+    /// The ident the asset of the part `part` is bound to in the importing
+    /// module.
+    #[turbo_tasks::function]
+    pub(crate) async fn get_part_referenced_ident(
+        self: Vc<Self>,
+        part: Option<Vc<ModulePart>>,
+        chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    ) -> Result<Vc<ReferencedAssetIdent>> {
+        let ident = self
+            .get_readable_part_referenced_ident(part)
+            .await?
+            .clone_value();
+        Ok(Vc::cell(import_ident(ident, chunking_context).await?))
+    }
+
+    /// The readable form of [EsmAssetReference::get_part_referenced_ident].
+    #[turbo_tasks::function]
+    async fn get_readable_part_referenced_ident(
+        self: Vc<Self>,
+        part: Option<Vc<ModulePart>>,
+    ) -> Result<Vc<ReferencedAssetIdent>> {
+        let referenced_asset = self.get_part_referenced_asset(part).await?;
+        if let ReferencedAsset::Some(placeable) = &*referenced_asset {
+            let this = self.await?;
+            let imported_module = EsmAssetReference::imported_module(*placeable, part).await?;
+            let idents = imported_module_idents(this.origin).await?;
+            if let Some(ident) = idents.get(&imported_module) {
+                return Ok(Vc::cell(Some(ident.clone())));
+            }
+            let name = imported_module_name(imported_module.0, imported_module.1).await?;
+            return Ok(Vc::cell(Some(imported_module_ident(&name, 0))));
+        }
+        if let ReferencedAsset::None = &*referenced_asset {
+            let this = self.await?;
+            if this.bindings_used && !*self.is_unresolveable().await? {
+                return Ok(Vc::cell(Some(magic_identifier::mangle(&format!(
+                    "ignored module {}",
+                    request_to_string(this.request).await?
+                )))));
+            }
+        }
+        Ok(Vc::cell(referenced_asset.get_ident().await?))
+    }
+
+    /// The id of the chunk item of the referenced module, as used by code
+    /// generation for non-async imports. It's [None] when the reference
+    /// doesn't resolve to a chunkable ecmascript module.
+    #[turbo_tasks::function]
+    pub async fn referenced_module_id(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    ) -> Result<Vc<OptionModuleId>> {
+        let ReferencedAsset::Some(asset) = &*self.get_referenced_asset().await? else {
+            return Ok(Vc::cell(None));
+        };
+        Ok(Vc::cell(Some(
+            asset.as_chunk_item(Vc::upcast(chunking_context)).id(),
+        )))
+    }
+
+    /// The statements the code generation of this reference inserts into the
+    /// importing module, printed, see [preview_code_generation]. This allows
+    /// inspecting the generated imports without a program to apply them to.
+    #[turbo_tasks::function]
+    pub async fn preview_statements(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    ) -> Result<Vc<Vec<String>>> {
+        let code_generation = self.code_generation(chunking_context).await?;
+        let markers = chunking_context.hoisting_markers().await?;
+        Ok(Vc::cell(preview_code_generation(
+            &code_generation,
+            &markers,
+        )))
+    }
+
+    /// Whether the modules this reference resolves to import `module`, directly
+    /// or transitively. For the importing module, this means the import is
+    /// part of an import cycle.
+    #[turbo_tasks::function]
+    pub async fn is_in_cycle_with(self: Vc<Self>, module: Vc<Box<dyn Module>>) -> Result<Vc<bool>> {
+        let module = module.resolve().await?;
+        let imported = resolved_modules(self.resolve_reference().primary_modules()).await?;
+        Ok(Vc::cell(
+            reaches(imported, module, |module| {
+                resolved_modules(primary_referenced_modules(module))
+            })
+            .await?,
+        ))
+    }
+
+    /// Whether the import only matters for its bindings and none of them are
+    /// used: the referenced module is marked as side-effect free, e.g. with
+    /// `"sideEffects": false` in its package.json, and the importing module
+    /// neither uses nor re-exports bindings of this reference. Code
+    /// generation then drops the import, unless its ident is used by another
+    /// import of the same module, see [import_is_emitted].
+    #[turbo_tasks::function]
+    pub async fn is_unused_and_side_effect_free(self: Vc<Self>) -> Result<Vc<bool>> {
+        if self.await?.bindings_used {
+            return Ok(Vc::cell(false));
+        }
+        let ReferencedAsset::Some(placeable) = *self.get_referenced_asset().await? else {
+            return Ok(Vc::cell(false));
+        };
+        if !*placeable.is_marked_as_side_effect_free().await? {
+            return Ok(Vc::cell(false));
+        }
+        Ok(Vc::cell(!*self.is_reexported().await?))
+    }
+
+    /// Whether the importing module re-exports bindings of this reference,
+    /// e.g. with `export { a } from "./a"` or `export * from "./a"`. The
+    /// re-exports are generated after the imports, so they can't be found in
+    /// the program when the import is generated.
+    #[turbo_tasks::function]
+    async fn is_reexported(self: Vc<Self>) -> Result<Vc<bool>> {
+        let this = self.await?;
+        let Some(module) =
+            Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(this.origin).await?
+        else {
+            return Ok(Vc::cell(false));
+        };
+        let EcmascriptExports::EsmExports(exports) = *module.analyze().await?.exports.await? else {
+            return Ok(Vc::cell(false));
+        };
+        let exports = exports.await?;
+        let reference: Vc<Box<dyn ModuleReference>> = Vc::upcast(self.resolve().await?);
+        Ok(Vc::cell(
+            exports.star_exports.contains(&reference)
+                || exports.exports.values().any(|export| match export {
+                    EsmExport::ImportedBinding(r, _) | EsmExport::ImportedNamespace(r) => {
+                        *r == reference
+                    }
+                    _ => false,
+                }),
+        ))
+    }
+
+    /// Returns true when the reference resolves to a module outside of the
+    /// project root, regardless of the [OutOfRootPolicy].
+    #[turbo_tasks::function]
+    pub async fn is_out_of_root(self: Vc<Self>) -> Result<Vc<bool>> {
+        let this = self.await?;
+        Ok(Vc::cell(
+            out_of_root_module(this.origin, self.resolve_reference())
+                .await?
+                .is_some(),
+        ))
+    }
+}
+
+/// The reference sub type the part `part` is resolved with. The `type` import
+/// attribute `attribute_type` only applies to imports of whole modules.
+fn import_sub_type(
+    part: Option<Vc<ModulePart>>,
+    attribute_type: Option<&str>,
+) -> EcmaScriptModulesReferenceSubType {
+    match (part, attribute_type) {
+        (Some(part), _) => EcmaScriptModulesReferenceSubType::ImportPart(part),
+        (None, Some(ty)) => {
+            EcmaScriptModulesReferenceSubType::ImportWithType(Vc::cell(ty.to_string()))
+        }
+        (None, None) => EcmaScriptModulesReferenceSubType::Import,
+    }
+}
+
+/// Merges the results of the parts of one reference, given with the names of
+/// the parts. All parts resolve the same request, so the request keys of each
+/// part are suffixed with its name to keep the results of all parts.
+fn merge_part_results<'a>(
+    results: impl IntoIterator<Item = (&'a str, &'a ModuleResolveResult)>,
+) -> ModuleResolveResult {
+    let mut merged = ModuleResolveResult::unresolveable();
+    for (part, result) in results {
+        let primary = result
+            .primary
+            .iter()
+            .map(|(key, item)| {
+                let request = key.request.as_deref().unwrap_or_default();
+                let key = RequestKey {
+                    request: Some(module_name_with_part(request, part)),
+                    conditions: key.conditions.clone(),
+                };
+                (key, item.clone())
+            })
+            .collect();
+        merged.merge_alternatives(&ModuleResolveResult {
+            primary,
+            affecting_sources: result.affecting_sources.clone(),
+        });
+    }
+    merged
+}
+
+/// The resolved `modules`, so they can be compared.
+async fn resolved_modules(modules: Vc<Modules>) -> Result<Vec<Vc<Box<dyn Module>>>> {
+    modules
+        .await?
+        .iter()
+        .map(|module| module.resolve())
+        .try_join()
+        .await
+}
+
+/// Whether `target` is one of the nodes `start` or reachable from them over
+/// the edges returned by `edges`. Every node is visited at most once, so
+/// cycles in the graph terminate.
+async fn reaches<T, F, Fut>(start: Vec<T>, target: T, mut edges: F) -> Result<bool>
+where
+    T: Copy + Eq + Hash,
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<Vec<T>>>,
+{
+    let mut visited = HashSet::new();
+    let mut queue = start;
+    while let Some(node) = queue.pop() {
+        if node == target {
+            return Ok(true);
+        }
+        if visited.insert(node) {
+            queue.extend(edges(node).await?);
+        }
+    }
+    Ok(false)
+}
+
+/// Wraps the namespace bound by the import statement `stmt` of the module
+/// `id`, which is part of an import cycle, in a proxy. Reading an export
+/// while the module is still evaluating throws an error naming the cycle,
+/// instead of evaluating to `undefined` or throwing a `ReferenceError` for a
+/// local of the imported module. Namespaces of async modules are promises
+/// which are replaced once the module is evaluated, so they aren't wrapped,
+/// and neither is the `undefined` of a weak import.
+fn defer_cyclic_import(stmt: &mut Stmt, id: Expr) {
+    let Stmt::Decl(Decl::Var(var)) = stmt else {
+        return;
+    };
+    for decl in &mut var.decls {
+        let Some(init) = decl.init.take() else {
+            continue;
+        };
+        decl.init = Some(quote_expr!(
+            "((namespace) => typeof namespace !== 'object' || namespace === null ||
+                namespace instanceof Promise ? namespace : new Proxy(namespace, {
+                get(target, key) {
+                    if (
+                        __turbopack_cache__[$id]?.loaded === false &&
+                        Object.prototype.hasOwnProperty.call(target, key)
+                    ) {
+                        const message = 'Circular import: the export \"' + String(key) +
+                            '\" of module ' + $id +
+                            ' was read before the module finished evaluating.';
+                        let value;
+                        try {
+                            value = target[key];
+                        } catch (cause) {
+                            throw new Error(message, { cause });
+                        }
+                        if (value === undefined) throw new Error(message);
+                        return value;
+                    }
+                    return target[key];
+                }
+            }))($init)",
+            id: Expr = id.clone(),
+            init: Expr = *init
+        ));
+    }
+}
+
+/// Applies the visitors of `code_generation` that operate on the whole program
+/// to an empty module and prints each statement they insert. Visitors of
+/// other AST nodes have nothing to visit in an empty module and are skipped.
+fn preview_code_generation(
+    code_generation: &CodeGeneration,
+    markers: &HoistingMarkers,
+) -> Vec<String> {
+    let mut program = Program::Module(ast::Module {
+        span: DUMMY_SP,
+        body: Vec::new(),
+        shebang: None,
+    });
+    for (path, visitor) in &code_generation.visitors {
+        if path.is_empty() {
+            visitor.create().visit_mut_program(&mut program);
+        }
+    }
+    let Program::Module(module) = program else {
+        unreachable!("the visitors replaced the module with a script");
+    };
+    module
+        .body
+        .into_iter()
+        .filter(|item| {
+            !matches!(
+                item,
+                ModuleItem::Stmt(stmt)
+                    if hoisting_anchor(stmt, markers).is_some() || import_comment(stmt).is_some()
+            )
+        })
+        .map(|item| {
+            let cm = Arc::new(SourceMap::default());
+            let mut bytes = Vec::new();
+            let mut emitter = Emitter {
+                cfg: Default::default(),
+                cm: cm.clone(),
+                comments: None,
+                wr: JsWriter::new(cm, "\n", &mut bytes, None),
+            };
+            emitter
+                .emit_module(&ast::Module {
+                    span: DUMMY_SP,
+                    body: vec![item],
+                    shebang: None,
+                })
+                .expect("writing to a Vec doesn't fail");
+            String::from_utf8_lossy(&bytes).trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Returns the first module of the `result` that is located outside of the
+/// root of the file system `origin` is located in.
+async fn out_of_root_module(
+    origin: Vc<Box<dyn ResolveOrigin>>,
+    result: Vc<ModuleResolveResult>,
+) -> Result<Option<Vc<Box<dyn Module>>>> {
+    let root = origin.origin_path().root().await?;
+    for module in result.await?.primary_modules_iter() {
+        if !module.ident().path().await?.is_inside_or_equal_ref(&root) {
+            return Ok(Some(module));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the file extension modules imported with the `type` import
+/// attribute `ty` are expected to have, for the types that are checked.
+fn expected_extension(ty: &str) -> Option<&'static str> {
+    match ty {
+        "json" => Some("json"),
+        "css" => Some("css"),
+        _ => None,
+    }
+}
+
+fn import_attribute_type_mismatch_message(request: &str, ty: &str, module: &str) -> String {
+    format!(
+        "The request {request} is imported with type \"{ty}\", but resolves to {module}. It's \
+         loaded as the type of {module} instead."
+    )
+}
+
+/// Returns a module of `result` that doesn't match the `type` import attribute
+/// `ty`.
+async fn module_with_conflicting_type(
+    ty: &str,
+    result: Vc<ModuleResolveResult>,
+) -> Result<Option<Vc<Box<dyn Module>>>> {
+    let Some(expected) = expected_extension(ty) else {
+        return Ok(None);
+    };
+    for module in result.await?.primary_modules_iter() {
+        if module.ident().path().await?.extension_ref() != Some(expected) {
+            return Ok(Some(module));
+        }
+    }
+    Ok(None)
+}
+
+/// Describes what loading the target of an [EsmAssetReference] costs in terms
+/// of chunk loading.
+#[turbo_tasks::value(serialization = "auto_for_input")]
+#[derive(Debug, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum ReferenceChunkLoad {
+    /// The target is placed in the same chunk group and doesn't trigger an
+    /// additional chunk load.
+    SameChunk,
+    /// The target is placed in a separate chunk group which is loaded when the
+    /// import is evaluated.
+    CrossChunk,
+    /// The target is an external module provided by the environment.
+    External,
+    /// The request can't be resolved.
+    Unresolvable,
+}
+
+/// How the request of an [EsmAssetReference] resolves, by the same
+/// resolution code generation uses.
+#[turbo_tasks::value(serialization = "auto_for_input")]
+#[derive(Debug, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum ResolveStatus {
+    /// A module that's part of the output, including inline `data:` URL
+    /// modules.
+    Internal,
+    /// An external module or a Node.js builtin provided by the environment.
+    External,
+    /// The request can't be resolved.
+    Unresolvable,
+    /// The request resolves, but to nothing that can be imported, e.g. an
+    /// ignored request.
+    Ignored,
+}
+
+impl ResolveStatus {
+    fn from_referenced_asset(asset: &ReferencedAsset) -> Self {
+        match asset {
+            ReferencedAsset::Some(_)
+            | ReferencedAsset::DataUrl(_)
+            | ReferencedAsset::InlineJson(_) => ResolveStatus::Internal,
+            ReferencedAsset::OriginalReferenceTypeExternal(_)
+            | ReferencedAsset::NodeBuiltin(_)
+            | ReferencedAsset::Remote { .. } => ResolveStatus::External,
+            ReferencedAsset::None => ResolveStatus::Ignored,
+        }
+    }
+
+    async fn of(
+        result: Vc<ModuleResolveResult>,
+        referenced_asset: Vc<ReferencedAsset>,
+    ) -> Result<Self> {
+        if result.await?.is_unresolveable_ref() {
+            return Ok(ResolveStatus::Unresolvable);
+        }
+        Ok(Self::from_referenced_asset(&*referenced_asset.await?))
+    }
+}
+
+/// The number of [EsmAssetReference]s per [ReferenceChunkLoad].
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReferenceChunkLoadCounts {
+    pub same_chunk: usize,
+    pub cross_chunk: usize,
+    pub external: usize,
+    pub unresolvable: usize,
+}
+
+impl ReferenceChunkLoadCounts {
+    fn add(&mut self, load: ReferenceChunkLoad) {
+        match load {
+            ReferenceChunkLoad::SameChunk => self.same_chunk += 1,
+            ReferenceChunkLoad::CrossChunk => self.cross_chunk += 1,
+            ReferenceChunkLoad::External => self.external += 1,
+            ReferenceChunkLoad::Unresolvable => self.unresolvable += 1,
+        }
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl EsmAssetReference {
+    /// Whether the request of this reference can't be resolved. Code
+    /// generation replaces such imports with a throw, this allows reporting
+    /// them before.
+    #[turbo_tasks::function]
+    pub async fn is_unresolveable(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.resolve_reference().await?.is_unresolveable_ref(),
+        ))
+    }
+
+    /// How the request of this reference resolves. This allows asserting the
+    /// resolution without running code generation.
+    #[turbo_tasks::function]
+    pub async fn resolve_status(self: Vc<Self>) -> Result<Vc<ResolveStatus>> {
+        if let Some(remote) = self.await?.annotations.remote() {
+            return Ok(
+                ResolveStatus::from_referenced_asset(&ReferencedAsset::from_remote(remote)).cell(),
+            );
+        }
+        Ok(
+            ResolveStatus::of(self.resolve_reference(), self.get_referenced_asset())
+                .await?
+                .cell(),
+        )
+    }
+
+    /// Whether this reference makes the importing module async: it imports an
+    /// external that is loaded asynchronously, a module whose chunk item is
+    /// listed in `async_module_info`, or a module of a remote container. The
+    /// async module wrapper awaits the bindings of these references, except
+    /// for remote modules, which are awaited where they're bound, see
+    /// [AsyncModule].
+    ///
+    /// `esm_externals` is whether the environment of the importing module
+    /// supports ESM externals, see [AsyncModule::esm_externals].
+    ///
+    /// [AsyncModule]: crate::references::async_module::AsyncModule
+    /// [AsyncModule::esm_externals]: crate::references::async_module::AsyncModule::esm_externals
+    #[turbo_tasks::function]
+    pub async fn contributes_to_async(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+        async_module_info: Vc<AsyncModuleInfo>,
+        esm_externals: bool,
+    ) -> Result<Vc<bool>> {
+        let target = match &*self.get_referenced_asset().await? {
+            ReferencedAsset::OriginalReferenceTypeExternal(_) => AsyncReferenceTarget::External,
+            ReferencedAsset::Remote { .. } => AsyncReferenceTarget::Remote,
+            ReferencedAsset::Some(placeable) => {
+                let chunk_item = placeable
+                    .as_chunk_item(Vc::upcast(chunking_context))
+                    .resolve()
+                    .await?;
+                AsyncReferenceTarget::Module {
+                    is_async: async_module_info
+                        .await?
+                        .referenced_async_modules
+                        .contains(&chunk_item),
+                }
+            }
+            ReferencedAsset::NodeBuiltin(_)
+            | ReferencedAsset::DataUrl(_)
+            | ReferencedAsset::InlineJson(_)
+            | ReferencedAsset::None => AsyncReferenceTarget::Other,
+        };
+        Ok(Vc::cell(is_async_reference(
+            target,
+            self.await?.import_externals,
+            esm_externals,
+        )))
+    }
+
+    /// Classifies this reference by whether importing it loads additional
+    /// chunks.
+    #[turbo_tasks::function]
+    pub async fn chunk_load(self: Vc<Self>) -> Result<Vc<ReferenceChunkLoad>> {
+        if *self.is_unresolveable().await? {
+            return Ok(ReferenceChunkLoad::Unresolvable.cell());
+        }
+        if let ReferencedAsset::OriginalReferenceTypeExternal(_)
+        | ReferencedAsset::NodeBuiltin(_)
+        | ReferencedAsset::Remote { .. } = &*self.get_referenced_asset().await?
+        {
+            return Ok(ReferenceChunkLoad::External.cell());
+        }
+        Ok(match *self.chunking_type().await? {
+            Some(ChunkingType::Async) => ReferenceChunkLoad::CrossChunk,
+            _ => ReferenceChunkLoad::SameChunk,
+        }
+        .cell())
+    }
+
+    /// Like [ValueToString::to_string], but qualifies the request with the
+    /// path of the importing module, e.g.
+    /// `import ./x (from app/page.js) {}`. `to_string` leaves the origin out
+    /// as it's part of cache keys.
+    #[turbo_tasks::function]
+    pub async fn to_string_with_origin(self: Vc<Self>) -> Result<Vc<String>> {
+        let this = self.await?;
+        let import_attributes = match this.import_attributes {
+            Some(import_attributes) => Some(import_attributes.await?.to_string()),
+            None => None,
+        };
+        Ok(Vc::cell(describe_import(
+            &this.request.to_string().await?,
+            Some(&this.origin.origin_path().await?.path),
+            &this.annotations,
+            import_attributes.as_deref(),
+        )))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl EsmAssetReferences {
+    /// Counts the references by [ReferenceChunkLoad], giving an estimate of the
+    /// chunk loads importing the module causes.
+    #[turbo_tasks::function]
+    pub async fn chunk_load_counts(self: Vc<Self>) -> Result<Vc<ReferenceChunkLoadCounts>> {
+        let loads = self
+            .await?
+            .iter()
+            .map(|r| async move { Ok(*r.chunk_load().await?) })
+            .try_join()
+            .await?;
+        let mut counts = ReferenceChunkLoadCounts::default();
+        for load in loads {
+            counts.add(load);
+        }
+        Ok(counts.cell())
+    }
+
+    /// The references that are placed in chunks, i.e. that have a chunking
+    /// type.
+    #[turbo_tasks::function]
+    pub async fn chunked(self: Vc<Self>) -> Result<Vc<Self>> {
+        self.filter_by_chunked(true).await
+    }
+
+    /// The references that aren't placed in chunks, e.g. the ones annotated
+    /// with `chunking-type: none`.
+    #[turbo_tasks::function]
+    pub async fn unchunked(self: Vc<Self>) -> Result<Vc<Self>> {
+        self.filter_by_chunked(false).await
+    }
+
+    /// Groups the references by the module they resolve to, so all imports of
+    /// the same module are listed together. Modules are keyed by their ident,
+    /// externals and Node.js builtins by their request, and `data:` URL
+    /// modules by their URL. References that don't resolve to a module, e.g.
+    /// ignored or unresolvable ones, are left out. The groups and the
+    /// references in them are in the order of the references.
+    #[turbo_tasks::function]
+    pub async fn group_by_module(self: Vc<Self>) -> Result<Vc<ReferencesByModule>> {
+        let keyed = self
+            .await?
+            .iter()
+            .map(|&r| async move {
+                let key = match &*r.get_referenced_asset().await? {
+                    ReferencedAsset::Some(placeable) => {
+                        Some(placeable.ident().to_string().await?.clone_value())
+                    }
+                    ReferencedAsset::OriginalReferenceTypeExternal(request)
+                    | ReferencedAsset::NodeBuiltin(request)
+                    | ReferencedAsset::DataUrl(request) => Some(request.clone()),
+                    ReferencedAsset::Remote {
+                        container, module, ..
+                    } => Some(format!("{container}/{module}")),
+                    ReferencedAsset::InlineJson(_) | ReferencedAsset::None => None,
+                };
+                Ok((key, r))
+            })
+            .try_join()
+            .await?;
+        Ok(Vc::cell(group_by_key(keyed)))
+    }
+
+    /// The ids of the modules the references statically import, see
+    /// [EsmAssetReference::referenced_module_id]. A module imported more than
+    /// once is listed once, in the order of its first reference. Externals,
+    /// Node.js builtins and `data:` URL modules have no module id, and
+    /// references that aren't placed in chunks don't import one, so they're
+    /// left out.
+    #[turbo_tasks::function]
+    pub async fn referenced_module_ids(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    ) -> Result<Vc<ModuleIds>> {
+        let ids = self
+            .chunked()
+            .await?
+            .iter()
+            .map(|r| async move {
+                Ok(match *r.referenced_module_id(chunking_context).await? {
+                    Some(id) => Some(id.await?.clone_value()),
+                    None => None,
+                })
+            })
+            .try_join()
+            .await?;
+        Ok(Vc::cell(
+            unique_module_ids(ids)
+                .into_iter()
+                .map(|id| id.cell())
+                .collect(),
+        ))
+    }
+
+    /// Resolves all references at once.
+    #[turbo_tasks::function]
+    pub async fn resolve_all(self: Vc<Self>) -> Result<Vc<ModuleResolveResults>> {
+        let resolved = self
+            .await?
+            .iter()
+            .map(|&r| async move {
+                let result = r.resolve_reference().resolve().await?;
+                Ok((r, result, result.await?.is_unresolveable_ref()))
+            })
+            .try_join()
+            .await?;
+        let (results, unresolvable) = split_unresolvable(resolved);
+        Ok(ModuleResolveResults {
+            results,
+            unresolvable,
+        }
+        .cell())
+    }
+}
+
+impl EsmAssetReferences {
+    async fn filter_by_chunked(self: Vc<Self>, chunked: bool) -> Result<Vc<Self>> {
+        let references = self
+            .await?
+            .iter()
+            .map(|&r| async move { Ok((r, *r.chunking_type().await?)) })
+            .try_join()
+            .await?;
+        Ok(Vc::cell(filter_by_chunked(references, chunked)))
+    }
+}
+
+/// [EsmAssetReference]s grouped by the module they resolve to, see
+/// [EsmAssetReferences::group_by_module].
+#[turbo_tasks::value(transparent)]
+pub struct ReferencesByModule(IndexMap<String, Vec<Vc<EsmAssetReference>>>);
+
+/// Groups `references` by their key in the order they are first seen.
+/// References without a key are left out.
+fn group_by_key<K: Hash + Eq, R>(
+    references: impl IntoIterator<Item = (Option<K>, R)>,
+) -> IndexMap<K, Vec<R>> {
+    let mut groups = IndexMap::<K, Vec<R>>::new();
+    for (key, reference) in references {
+        if let Some(key) = key {
+            groups.entry(key).or_default().push(reference);
+        }
+    }
+    groups
+}
+
+/// The module ids among `ids` without duplicates, in the order they are first
+/// seen. Missing ids are left out.
+fn unique_module_ids(ids: impl IntoIterator<Item = Option<ModuleId>>) -> Vec<ModuleId> {
+    ids.into_iter()
+        .flatten()
+        .collect::<IndexSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// The resolve results of [EsmAssetReferences], see
+/// [EsmAssetReferences::resolve_all].
+#[turbo_tasks::value(shared)]
+pub struct ModuleResolveResults {
+    /// The result of every reference, in the order of the references.
+    pub results: Vec<Vc<ModuleResolveResult>>,
+    /// The references that can't be resolved, in the order of the references.
+    pub unresolvable: Vec<Vc<EsmAssetReference>>,
+}
+
+/// Splits references with their result and whether it's unresolvable into
+/// the results and the unresolvable references, both in order.
+fn split_unresolvable<R, T>(resolved: impl IntoIterator<Item = (R, T, bool)>) -> (Vec<T>, Vec<R>) {
+    let mut results = Vec::new();
+    let mut unresolvable = Vec::new();
+    for (reference, result, is_unresolvable) in resolved {
+        results.push(result);
+        if is_unresolvable {
+            unresolvable.push(reference);
+        }
+    }
+    (results, unresolvable)
+}
+
+/// Keeps the references whose chunking type is set when `chunked` is true,
+/// and the ones without a chunking type otherwise.
+fn filter_by_chunked<T>(
+    references: impl IntoIterator<Item = (T, Option<ChunkingType>)>,
+    chunked: bool,
+) -> Vec<T> {
+    references
+        .into_iter()
+        .filter(|(_, chunking_type)| chunking_type.is_some() == chunked)
+        .map(|(reference, _)| reference)
+        .collect()
+}
+
+#[turbo_tasks::value_impl]
+impl ModuleReference for EsmAssetReference {
+    #[turbo_tasks::function]
+    fn resolve_reference(self: Vc<Self>) -> Vc<ModuleResolveResult> {
+        self.resolve_reference_with_transition(None)
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ValueToString for EsmAssetReference {
+    #[turbo_tasks::function]
+    async fn to_string(&self) -> Result<Vc<String>> {
+        let import_attributes = match self.import_attributes {
+            Some(import_attributes) => Some(import_attributes.await?.to_string()),
+            None => None,
+        };
+        Ok(Vc::cell(describe_import(
+            &self.request.to_string().await?,
+            None,
+            &self.annotations,
+            import_attributes.as_deref(),
+        )))
+    }
+}
+
+/// Describes an import of `request`, qualified with the path of the importing
+/// module when `origin` is given, see
+/// [EsmAssetReference::to_string_with_origin].
+fn describe_import(
+    request: &str,
+    origin: Option<&str>,
+    annotations: &ImportAnnotations,
+    import_attributes: Option<&str>,
+) -> String {
+    let mut string = format!("import {request}");
+    if let Some(origin) = origin {
+        write!(string, " (from {origin})").unwrap();
+    }
+    write!(string, " {annotations}").unwrap();
+    if let Some(import_attributes) = import_attributes {
+        write!(string, " {import_attributes}").unwrap();
+    }
+    string
+}
+
+#[turbo_tasks::value_impl]
+impl ChunkableModuleReference for EsmAssetReference {
+    #[turbo_tasks::function]
+    async fn chunking_type(&self) -> Result<Vc<ChunkingTypeOption>> {
+        let (chunking_type, unknown) =
+            annotated_chunking_type(self.annotations.chunking_type(), self.strict_annotations)?;
+        if let Some(unknown) = unknown {
+            AnalyzeIssue {
+                code: None,
+                category: Vc::cell("analyze".to_string()),
+                message: StyledString::Text(format!(
+                    "The import of {} is annotated with the unknown chunking-type \"{unknown}\", \
+                     it's imported with the default chunking type instead. Known chunking types \
+                     are \"parallel\", \"parallel-prefetch\", \"isolated\", \"weak\", \"eager\" \
+                     and \"none\".",
+                    self.request.to_string().await?
+                ))
+                .cell(),
+                source_ident: AssetIdent::from_path(self.origin.origin_path()),
+                severity: IssueSeverity::Warning.into(),
+                source: self.issue_source,
+                title: Vc::cell("unknown chunking-type annotation".to_string()),
+            }
+            .cell()
+            .emit();
+        }
+        Ok(Vc::cell(chunking_type))
+    }
+
+    /// The [chunking type in the
+    /// context](EsmAssetReference::chunking_type_in_context), except that
+    /// JSON modules inlined into the importing module aren't chunked, see
+    /// [EsmAssetReference::inlined_referenced_asset].
+    #[turbo_tasks::function]
+    async fn chunking_type_for(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn ChunkingContext>>,
+    ) -> Result<Vc<ChunkingTypeOption>> {
+        let chunking_type = self.chunking_type_in_context(chunking_context);
+        if chunking_type.await?.is_none() {
+            return Ok(chunking_type);
+        }
+        if let Some(chunking_context) =
+            Vc::try_resolve_sidecast::<Box<dyn EcmascriptChunkingContext>>(chunking_context).await?
+        {
+            if let ReferencedAsset::InlineJson(_) =
+                *self.inlined_referenced_asset(chunking_context).await?
+            {
+                return Ok(Vc::cell(None));
+            }
+        }
+        Ok(chunking_type)
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl CodeGenerateable for EsmAssetReference {
+    #[turbo_tasks::function]
+    async fn code_generation(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    ) -> Result<Vc<CodeGeneration>> {
+        let mut visitors = Vec::new();
+
+        let this = &*self.await?;
+        let chunking_type = self
+            .chunking_type_in_context(Vc::upcast(chunking_context))
+            .await?;
+        let span = this.span();
+        let markers = chunking_context.hoisting_markers().await?;
+
+        // Insert code that throws immediately at time of import if a request is
+        // unresolvable
+        if *self.is_unresolveable().await? {
+            let request = request_to_string(this.request).await?.to_string();
+            let origin = this.origin.origin_path().await?.path.clone();
+            visitors.push(create_visitor!(visit_mut_program(program: &mut Program) {
+                insert_hoisted_stmt(
+                    program,
+                    module_not_found_stmt(&request, &origin, span),
+                    &markers,
+                );
+            }));
+            emit_generated_import(GeneratedImportKind::Unresolvable);
+
+            return Ok(CodeGeneration { visitors }.into());
+        }
+
+        let resource_hint = this.annotations.resource_hint();
+        if chunking_type.is_none() && resource_hint.is_some() {
+            AnalyzeIssue {
+                code: None,
+                category: Vc::cell("code generation".to_string()),
+                message: StyledString::Text(format!(
+                    "The request {} is annotated with a resource hint, but it isn't chunked \
+                     (chunking-type: none), so there's no chunk to prefetch.",
+                    this.request.to_string().await?
+                ))
+                .cell(),
+                source_ident: AssetIdent::from_path(this.origin.origin_path()),
+                severity: IssueSeverity::Error.into(),
+                source: this.issue_source,
+                title: Vc::cell("resource hint on unchunked import".to_string()),
+            }
+            .cell()
+            .emit();
+        }
+
+        // only chunked references can be imported
+        if chunking_type.is_some() {
+            let referenced_asset = self.inlined_referenced_asset(chunking_context).await?;
+            if this.annotations.chunking_type() == Some(EAGER_CHUNKING_TYPE)
+                && !matches!(*referenced_asset, ReferencedAsset::InlineJson(_))
+            {
+                AnalyzeIssue {
+                    code: None,
+                    category: Vc::cell("analyze".to_string()),
+                    message: StyledString::Text(format!(
+                        "The import of {} is annotated with the chunking-type \"eager\", but the \
+                         module can't be inlined into the importing module. Only the default \
+                         import of a JSON module can be, when the chunking context inlines eager \
+                         imports. It's imported with the \"parallel\" chunking type instead.",
+                        this.request.to_string().await?
+                    ))
+                    .cell(),
+                    source_ident: AssetIdent::from_path(this.origin.origin_path()),
+                    severity: IssueSeverity::Info.into(),
+                    source: this.issue_source,
+                    title: Vc::cell("eager import not inlined".to_string()),
+                }
+                .cell()
+                .emit();
+            }
+            let context = ImportCodeGenContext {
+                chunking_context,
+                chunking_type: *chunking_type,
+                span,
+                markers,
+                names: chunking_context.runtime_function_names().await?,
+                instrument_coverage: *chunking_context.import_coverage_instrumentation().await?,
+            };
+            if let Some(ident) = self
+                .get_referenced_ident(chunking_context)
+                .await?
+                .clone_value()
+            {
+                visitors.extend(match &*referenced_asset {
+                    ReferencedAsset::Some(_) => self.module_import_visitors(&context).await?,
+                    ReferencedAsset::OriginalReferenceTypeExternal(request) => {
+                        self.external_import_visitors(ident, request, &context)
+                            .await?
+                    }
+                    ReferencedAsset::NodeBuiltin(request) => {
+                        self.node_builtin_import_visitors(&ident, request, &context)
+                            .await?
+                    }
+                    ReferencedAsset::DataUrl(url) => {
+                        self.data_url_import_visitors(&ident, url, &context).await?
+                    }
+                    ReferencedAsset::InlineJson(json) => {
+                        // Checked by [EsmAssetReference::inlined_referenced_asset].
+                        let FileJsonContent::Content(value) = &*json.await? else {
+                            bail!("only parsed JSON is inlined");
+                        };
+                        let mut stmt = inline_json_stmt(&ident, value);
+                        set_var_decl_span(&mut stmt, span);
+                        emit_generated_import(GeneratedImportKind::InlineJson);
+                        vec![hoisted_stmt_visitor(stmt, &context)]
+                    }
+                    ReferencedAsset::Remote {
+                        container,
+                        scope,
+                        module,
+                    } => {
+                        let mut stmt =
+                            remote_stmt(&ident, container, scope, module, &context.names);
+                        set_var_decl_span(&mut stmt, span);
+                        emit_generated_import(GeneratedImportKind::Remote);
+                        vec![hoisted_stmt_visitor(stmt, &context)]
+                    }
+                    ReferencedAsset::None => {
+                        emit_generated_import(GeneratedImportKind::Ignored);
+                        vec![hoisted_stmt_visitor(
+                            ignored_import_stmt(&ident, span),
+                            &context,
+                        )]
+                    }
+                });
+            }
+        }
+
+        Ok(CodeGeneration { visitors }.into())
+    }
+}
+
+/// What the code generated for a chunked import depends on, besides the
+/// reference and what it's bound to.
+struct ImportCodeGenContext {
+    chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    chunking_type: Option<ChunkingType>,
+    /// See [EsmAssetReference::span].
+    span: Option<Span>,
+    markers: ReadRef<HoistingMarkers>,
+    names: ReadRef<RuntimeFunctionNames>,
+    instrument_coverage: bool,
+}
+
+/// A visitor of a [CodeGeneration] together with the AST path it visits.
+type CodeGenVisitor = (Vec<AstParentKind>, Box<dyn VisitorFactory>);
+
+/// A visitor hoisting `stmt` into the program, see [insert_hoisted_stmt].
+fn hoisted_stmt_visitor(stmt: Stmt, context: &ImportCodeGenContext) -> CodeGenVisitor {
+    let markers = context.markers.clone();
+    create_visitor!(visit_mut_program(program: &mut Program) {
+        insert_hoisted_stmt(program, stmt.clone(), &markers);
+    })
+}
+
+impl EsmAssetReference {
+    /// The visitors binding every imported part of a module in the module
+    /// graph. An import of a module in a separate chunk group binds its async
+    /// loader instead.
+    async fn module_import_visitors(
+        self: Vc<Self>,
+        context: &ImportCodeGenContext,
+    ) -> Result<Vec<CodeGenVisitor>> {
+        let this = self.await?;
+        let chunking_context = context.chunking_context;
+        let chunking_type = context.chunking_type;
+        let cross_chunk = matches!(chunking_type, Some(ChunkingType::Async));
+        let resource_hint = this.annotations.resource_hint();
+        let prefetch_when_idle = this.prefetches_when_idle();
+        let no_cache = this.annotations.no_cache();
+        let weak = matches!(chunking_type, Some(ChunkingType::Weak));
+        let span = context.span;
+        let instrument_coverage = context.instrument_coverage;
+        let namespace_shape = *chunking_context.esm_namespace_shape().await?;
+        let import_comment = if *chunking_context.import_debug_comments().await? {
+            Some(import_comment_stmt(&import_comment_text(
+                &request_to_string(this.request).await?,
+                &this.origin.origin_path().await?.path,
+            )))
+        } else {
+            None
+        };
+        let cycle_origin = if *chunking_context.esm_cycle_checks().await? {
+            Vc::try_resolve_sidecast::<Box<dyn Module>>(this.origin).await?
+        } else {
+            None
+        };
+        let owners = import_binding_owners(this.origin, Vc::upcast(chunking_context)).await?;
+        let resolved_self = self.resolve().await?;
+        let drop_when_unused = *self.is_unused_and_side_effect_free().await?;
+        let mut visitors = Vec::new();
+        // Every part is a module of its own, bound to an ident of its own.
+        for part in this.parts() {
+            let ReferencedAsset::Some(asset) = *self.get_part_referenced_asset(part).await? else {
+                continue;
+            };
+            // An earlier import of the same module and part already emitted
+            // the binding.
+            let key = (Self::imported_module(asset, part).await?, chunking_type);
+            let namespace_import = match owners.get(&key) {
+                Some(&(owner, _)) if owner != resolved_self => continue,
+                Some(&(_, namespace_import)) => namespace_import,
+                None => this.namespace_import,
+            };
+            let Some(ident) = self
+                .get_part_referenced_ident(part, chunking_context)
+                .await?
+                .clone_value()
+            else {
+                continue;
+            };
+            // Modules in a separate chunk group aren't available in the current
+            // one, so the import binds their async loader instead.
+            let id = if cross_chunk {
+                chunking_context
+                    .async_loader_chunk_item_id(Vc::upcast(asset))
+                    .await?
+            } else {
+                asset
+                    .as_chunk_item(Vc::upcast(chunking_context))
+                    .id()
+                    .await?
+            };
+            if !drop_when_unused {
+                emit_generated_import(if cross_chunk {
+                    GeneratedImportKind::CrossChunk
+                } else {
+                    GeneratedImportKind::Internal
+                });
+            }
+            // Modules with dynamic exports add properties to their namespace
+            // object after evaluation, so it can only be frozen for static ESM
+            // exports. The async loader isn't a namespace object.
+            let namespace = if !namespace_import {
+                None
+            } else if matches!(namespace_shape, EsmNamespaceShape::Frozen)
+                && !cross_chunk
+                && has_static_esm_exports(asset).await?
+            {
+                Some(EsmNamespaceShape::Frozen)
+            } else {
+                Some(EsmNamespaceShape::Extensible)
+            };
+            // The async loader isn't a namespace object.
+            let cyclic = match cycle_origin {
+                Some(origin) if !cross_chunk => *self.is_in_cycle_with(origin).await?,
+                _ => false,
+            };
+            let names = context.names.clone();
+            let markers = context.markers.clone();
+            let import_comment = import_comment.clone();
+            visitors.push(create_visitor!(visit_mut_program(program: &mut Program) {
+                // The bindings of all imports are rewritten to the ident by path
+                // visitors, which run before this one, see
+                // [gen_content_with_visitors].
+                if !import_is_emitted(program, &ident, drop_when_unused) {
+                    return;
+                }
+                let id_expr = Expr::Lit(match &*id {
+                    ModuleId::String(s) => s.clone().into(),
+                    ModuleId::Number(n) => (*n as f64).into(),
+                });
+                if let Some(resource_hint) = resource_hint {
+                    insert_hoisted_stmt(
+                        program,
+                        resource_hint_stmt(resource_hint, id_expr.clone(), &names),
+                        &markers,
+                    );
+                }
+                let mut stmt = esm_import_stmt(
+                    &ident,
+                    id_expr.clone(),
+                    namespace,
+                    no_cache,
+                    weak,
+                    span,
+                    &names,
+                );
+                if cyclic {
+                    defer_cyclic_import(&mut stmt, id_expr.clone());
+                }
+                if let Some(import_comment) = &import_comment {
+                    insert_hoisted_stmt(program, import_comment.clone(), &markers);
+                }
+                insert_hoisted_stmt(program, stmt, &markers);
+                if prefetch_when_idle {
+                    insert_hoisted_stmt(program, prefetch_stmt(id_expr, &names), &markers);
+                }
+                if instrument_coverage {
+                    insert_hoisted_stmt(program, import_coverage_stmt(&id.to_string()), &markers);
+                }
+            }));
+        }
+        Ok(visitors)
+    }
+
+    /// The visitors binding the external `request` to `ident`. When the
+    /// environment supports no externals, the import throws at runtime.
+    async fn external_import_visitors(
+        self: Vc<Self>,
+        ident: String,
+        request: &str,
+        context: &ImportCodeGenContext,
+    ) -> Result<Vec<CodeGenVisitor>> {
+        let this = self.await?;
+        let span = context.span;
+        let markers = context.markers.clone();
+        let environment = context.chunking_context.environment();
+        check_externals_for_environment(this.origin, environment).await?;
+        let esm_externals = *environment.supports_esm_externals().await?;
+        if !esm_externals && !*environment.supports_commonjs_externals().await? {
+            // Fail loading the external at runtime instead of aborting the build,
+            // so the other modules are still generated.
+            AnalyzeIssue {
+                code: None,
+                category: Vc::cell("code generation".to_string()),
+                message: StyledString::Text(unsupported_external_message(request)).cell(),
+                source_ident: AssetIdent::from_path(this.origin.origin_path()),
+                severity: IssueSeverity::Error.into(),
+                source: this.issue_source,
+                title: Vc::cell("external modules not supported".to_string()),
+            }
+            .cell()
+            .emit();
+            let request = request.to_string();
+            let origin = this.origin.origin_path().await?.path.clone();
+            emit_generated_import(GeneratedImportKind::Unresolvable);
+            let visitor = create_visitor!(visit_mut_program(program: &mut Program) {
+                insert_hoisted_stmt(
+                    program,
+                    module_not_found_stmt(&request, &origin, span),
+                    &markers,
+                );
+            });
+            return Ok(vec![visitor]);
+        }
+        let import_externals = this.import_externals;
+        let interop = *self
+            .get_referenced_asset()
+            .interop_needed(this.origin.origin_path().parent())
+            .await?;
+        // Only import statements have a span. Other references, e.g. for free
+        // variables, aren't ESM imports in the original module. Requiring a
+        // CommonJS external loses nothing, only genuine ESM externals are
+        // affected.
+        if !import_externals && !esm_externals && !interop && span.is_some() {
+            AnalyzeIssue {
+                code: None,
+                category: Vc::cell("code generation".to_string()),
+                message: StyledString::Text(format!(
+                    "The external {request} is imported with an ESM import, but it's loaded with \
+                     `__turbopack_external_require__` because import_externals is disabled. Live \
+                     bindings of an ESM external aren't preserved this way. Enable \
+                     import_externals to load it with `__turbopack_external_import__` instead."
+                ))
+                .cell(),
+                source_ident: AssetIdent::from_path(this.origin.origin_path()),
+                severity: IssueSeverity::Warning.into(),
+                source: this.issue_source,
+                title: Vc::cell("ESM external loaded with require".to_string()),
+            }
+            .cell()
+            .emit();
+        }
+        let optional = this.annotations.optional();
+        let lazy = this.annotations.lazy();
+        let instrument_coverage = context.instrument_coverage;
+        let names = context.names.clone();
+        emit_generated_import(if esm_externals || import_externals {
+            GeneratedImportKind::ExternalImport
+        } else {
+            GeneratedImportKind::ExternalRequire
+        });
+        let request = request.to_string();
+        let visitor = create_visitor!(visit_mut_program(program: &mut Program) {
+            match program {
+                Program::Module(module) if esm_externals => {
+                    insert_hoisted_import(
+                        module,
+                        esm_external_import_decl(&ident, &request, span),
+                        &markers,
+                    );
+                }
+                _ => {
+                    let mut stmt = external_stmt(
+                        &ident,
+                        &request,
+                        import_externals,
+                        interop,
+                        optional,
+                        lazy,
+                        &names,
+                    );
+                    set_var_decl_span(&mut stmt, span);
+                    insert_hoisted_stmt(program, stmt, &markers);
+                }
+            }
+            if instrument_coverage {
+                insert_hoisted_stmt(program, import_coverage_stmt(&request), &markers);
+            }
+        });
+        Ok(vec![visitor])
+    }
+
+    /// The visitors binding the Node.js builtin `request` to `ident`.
+    async fn node_builtin_import_visitors(
+        self: Vc<Self>,
+        ident: &str,
+        request: &str,
+        context: &ImportCodeGenContext,
+    ) -> Result<Vec<CodeGenVisitor>> {
+        let this = self.await?;
+        let environment = context.chunking_context.environment();
+        // Missing builtins are reported below, so they aren't checked against
+        // the environment like externals.
+        if !*environment.node_externals().await? {
+            AnalyzeIssue {
+                code: None,
+                category: Vc::cell("code generation".to_string()),
+                message: StyledString::Text(format!(
+                    "The Node.js builtin {request} is imported, but the environment doesn't \
+                     provide Node.js builtins."
+                ))
+                .cell(),
+                source_ident: AssetIdent::from_path(this.origin.origin_path()),
+                severity: IssueSeverity::Error.into(),
+                source: this.issue_source,
+                title: Vc::cell("Node.js builtin not available".to_string()),
+            }
+            .cell()
+            .emit();
+        }
+        emit_generated_import(GeneratedImportKind::NodeBuiltin);
+        let mut stmt = node_builtin_stmt(ident, request, &context.names);
+        set_var_decl_span(&mut stmt, context.span);
+        Ok(vec![hoisted_stmt_visitor(stmt, context)])
+    }
+
+    /// The visitors binding the module of the data URL `url` to `ident`, or
+    /// none when it can't be evaluated inline.
+    async fn data_url_import_visitors(
+        self: Vc<Self>,
+        ident: &str,
+        url: &str,
+        context: &ImportCodeGenContext,
+    ) -> Result<Vec<CodeGenVisitor>> {
+        let this = self.await?;
+        match data_url_module_stmt(ident, url, context.span) {
+            Ok(stmt) => {
+                emit_generated_import(GeneratedImportKind::DataUrl);
+                Ok(vec![hoisted_stmt_visitor(stmt, context)])
+            }
+            Err(err) => {
+                AnalyzeIssue {
+                    code: None,
+                    category: Vc::cell("code generation".to_string()),
+                    message: StyledString::Text(format!(
+                        "The data URL can't be evaluated inline: {err}"
+                    ))
+                    .cell(),
+                    source_ident: AssetIdent::from_path(this.origin.origin_path()),
+                    severity: IssueSeverity::Error.into(),
+                    source: this.issue_source,
+                    title: Vc::cell("unsupported data URL import".to_string()),
+                }
+                .cell()
+                .emit();
+                Ok(Vec::new())
+            }
+        }
+    }
+}
+
+/// The transition to resolve with: an explicitly given transition takes
+/// precedence over the one of the `transition` annotation.
+fn effective_transition<'a>(
+    explicit: Option<&'a str>,
+    annotation: Option<&'a str>,
+) -> Option<&'a str> {
+    explicit.or(annotation)
+}
+
+const EAGER_CHUNKING_TYPE: &str = "eager";
+const PARALLEL_PREFETCH_CHUNKING_TYPE: &str = "parallel-prefetch";
+
+/// The chunking type requested by the `chunking-type` import annotation
+/// `annotation`.
+///
+/// An unknown value is an error in `strict` mode. Otherwise the default
+/// chunking type is used, and the unknown value is returned to be reported.
+fn annotated_chunking_type(
+    annotation: Option<&str>,
+    strict: bool,
+) -> Result<(Option<ChunkingType>, Option<&str>)> {
+    let chunking_type = match annotation {
+        None | Some("parallel") => Some(ChunkingType::ParallelInheritAsync),
+        // Prefetching imports are parallel ones that additionally prefetch the
+        // module's chunks when idle, see `EsmAssetReference::prefetches_when_idle`.
+        Some(PARALLEL_PREFETCH_CHUNKING_TYPE) => Some(ChunkingType::ParallelInheritAsync),
+        // Isolated imports are placed in their own chunk group, which is loaded
+        // through an async loader, so they can be loaded with a separate runtime.
+        Some("isolated") => Some(ChunkingType::Async),
+        // Weak imports don't include the module, they only bind it when another
+        // reference already did.
+        Some("weak") => Some(ChunkingType::Weak),
+        // Eager imports ask for the module to be inlined into the importing
+        // one. Only the default import of a JSON module can be, which then
+        // isn't chunked, see `EsmAssetReference::inlined_referenced_asset`.
+        // Other eager imports are imported like parallel ones.
+        Some(EAGER_CHUNKING_TYPE) => Some(ChunkingType::ParallelInheritAsync),
+        Some("none") => None,
+        Some(unknown) if strict => bail!("unknown chunking_type: {}", unknown),
+        Some(unknown) => return Ok((Some(ChunkingType::ParallelInheritAsync), Some(unknown))),
+    };
+    Ok((chunking_type, None))
+}
+
+/// The chunking type of a reference with the `chunking-type` annotation
+/// `annotation`, which is `chunking_type` on its own, in a chunking context
+/// that does or doesn't `chunk_unannotated` references.
+fn chunking_type_in_context(
+    annotation: Option<&str>,
+    chunking_type: Option<ChunkingType>,
+    chunk_unannotated: bool,
+) -> Option<ChunkingType> {
+    if annotation.is_none() && !chunk_unannotated {
+        None
+    } else {
+        chunking_type
+    }
+}
+
+/// A statement throwing a "Cannot find module" error for `request`, imported
+/// by `origin`, which replaces an import that can't be loaded.
+fn module_not_found_stmt(request: &str, origin: &str, span: Option<Span>) -> Stmt {
+    Stmt::Expr(ExprStmt {
+        expr: Box::new(throw_module_not_found_expr_with_origin(request, origin)),
+        span: span.unwrap_or(DUMMY_SP),
+    })
+}
+
+/// Creates the statement binding an empty namespace object to `ident`, for an
+/// ignored import whose bindings are used. It avoids a ReferenceError when
+/// they are accessed.
+fn ignored_import_stmt(ident: &str, span: Option<Span>) -> Stmt {
+    let mut stmt = quote!(
+        "var $name = {};" as Stmt,
+        name = Ident::new(ident.into(), DUMMY_SP),
+    );
+    set_var_decl_span(&mut stmt, span);
+    stmt
+}
+
+/// Whether the import binding `ident` is emitted. Imports that are
+/// `drop_when_unused`, see [EsmAssetReference::is_unused_and_side_effect_free],
+/// are only emitted when `ident` is used in `program`.
+fn import_is_emitted(program: &Program, ident: &str, drop_when_unused: bool) -> bool {
+    !drop_when_unused || ident_is_used(program, ident)
+}
+
+/// Whether the import binding `ident` is referenced anywhere in `program`.
+/// The rewritten bindings have no syntax context, so identifiers of the source
+/// with the same name, e.g. a shadowing local, don't count. Neither do property
+/// names and keys of object literals.
+fn ident_is_used(program: &Program, ident: &str) -> bool {
+    struct Visitor<'a> {
+        ident: &'a str,
+        found: bool,
+    }
+
+    impl Visit for Visitor<'_> {
+        visit_obj_and_computed!();
+
+        fn visit_ident(&mut self, i: &Ident) {
+            if &*i.sym == self.ident && i.span.ctxt == SyntaxContext::empty() {
+                self.found = true;
+            }
+        }
+    }
+
+    let mut visitor = Visitor {
+        ident,
+        found: false,
+    };
+    program.visit_with(&mut visitor);
+    visitor.found
+}
+
+/// Returns true when all exports of `placeable` are statically known ESM
+/// exports.
+async fn has_static_esm_exports(placeable: Vc<Box<dyn EcmascriptChunkPlaceable>>) -> Result<bool> {
+    Ok(match &*placeable.get_exports().await? {
+        EcmascriptExports::EsmExports(exports) => {
+            exports.expand_exports().await?.dynamic_exports.is_empty()
+        }
+        _ => false,
+    })
+}
+
+/// Creates the statement binding the module with the id `id` to `ident`.
+///
+/// When `namespace` is set, the import is a namespace import
+/// (`import * as ns from "..."`) and the module is imported with
+/// `__turbopack_import_namespace__(id)`. It binds the same namespace object as
+/// `__turbopack_import__`, but makes sure it has a `Symbol.toStringTag` of
+/// "Module", which namespace objects set with `__turbopack_export_namespace__`
+/// lack. With [EsmNamespaceShape::Frozen], it's called as
+/// `__turbopack_import_namespace__(id, true)` and freezes the namespace
+/// object. Exports are exposed as getters, so freezing keeps live bindings
+/// intact. The namespace object of an async module is a promise, which is
+/// shared by all importers and settled by the runtime, so it's never frozen.
+///
+/// When `uncached` is set, the module is imported with
+/// `__turbopack_import_uncached__(id)` instead. That runtime helper has the
+/// same signature as `__turbopack_import__`, but runs the module factory on
+/// every call and returns the fresh namespace object without reading or
+/// writing the module cache. The binding is still created once per
+/// evaluation of the importing module. For async modules, the fresh
+/// namespace object is awaited by the async dependency handler just like a
+/// cached one.
+///
+/// When `weak` is set, the module isn't included by this import. It's only
+/// imported when its factory is registered in `__turbopack_modules__`
+/// because another import included it. Otherwise `undefined` is bound, and
+/// the generated code says so with a
+/// `("TURBOPACK weak import, the module isn't included by another import",
+/// undefined)` fallback.
+pub(super) fn esm_import_stmt(
+    ident: &str,
+    id: Expr,
+    namespace: Option<EsmNamespaceShape>,
+    uncached: bool,
+    weak: bool,
+    span: Option<Span>,
+    names: &RuntimeFunctionNames,
+) -> Stmt {
+    let import = runtime_function(if uncached {
+        &names.import_uncached
+    } else if namespace.is_some() {
+        &names.import_namespace
+    } else {
+        &names.import
+    });
+    let import = if matches!(namespace, Some(EsmNamespaceShape::Frozen)) {
+        quote_expr!("$import($id, true)", import = import, id: Expr = id.clone())
+    } else {
+        quote_expr!("$import($id)", import = import, id: Expr = id.clone())
+    };
+    let import = if weak {
+        quote_expr!(
+            "$modules[$id] ? $import : (\"TURBOPACK weak import, the module isn't included by \
+             another import\", undefined)",
+            modules = runtime_function(&names.modules),
+            id: Expr = id,
+            import: Expr = *import
+        )
+    } else {
+        import
+    };
+    let mut stmt = quote!(
+        "var $name = $import;" as Stmt,
+        name = Ident::new(ident.into(), DUMMY_SP),
+        import: Expr = *import
+    );
+    set_var_decl_span(&mut stmt, span);
+    stmt
+}
+
+/// Creates the statement registering a resource hint for the chunk of the
+/// module `id`. The runtime emits a `<link rel="prefetch">` or
+/// `<link rel="preload">` for it where the environment supports it.
+fn resource_hint_stmt(
+    resource_hint: ImportResourceHint,
+    id: Expr,
+    names: &RuntimeFunctionNames,
+) -> Stmt {
+    let register = runtime_function(match resource_hint {
+        ImportResourceHint::Prefetch => &names.register_prefetch,
+        ImportResourceHint::Preload => &names.register_preload,
+    });
+    quote!("$register($id);" as Stmt, register = register, id: Expr = id)
+}
+
+/// Creates the statement prefetching the chunks of the module `id` when the
+/// browser is idle. It's placed after the import binding, as it doesn't block
+/// evaluation.
+fn prefetch_stmt(id: Expr, names: &RuntimeFunctionNames) -> Stmt {
+    quote!(
+        "$prefetch($id);" as Stmt,
+        prefetch = runtime_function(&names.prefetch),
+        id: Expr = id
+    )
+}
+
+/// Creates the ident of the runtime function `name`.
+pub(crate) fn runtime_function(name: &str) -> Ident {
+    Ident::new(name.into(), DUMMY_SP)
+}
+
+/// Maps a generated `var` declaration back to the import it was created for,
+/// so it's attributed to the `import` line in source maps. Without a `span`,
+/// the declaration is kept at [DUMMY_SP].
+pub(crate) fn set_var_decl_span(stmt: &mut Stmt, span: Option<Span>) {
+    let (Some(span), Stmt::Decl(Decl::Var(var))) = (span, stmt) else {
+        return;
+    };
+    var.span = span;
+    for decl in &mut var.decls {
+        decl.span = span;
+    }
+}
+
+/// Creates a statement that increments the counter for `key` (a module id or
+/// an external request) in the global import coverage registry.
+fn import_coverage_stmt(key: &str) -> Stmt {
+    quote!(
+        "(globalThis.__turbopack_import_coverage__ = globalThis.__turbopack_import_coverage__ || \
+         {})[$key] = (globalThis.__turbopack_import_coverage__[$key] || 0) + 1;" as Stmt,
+        key: Expr = key.into()
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use swc_core::{
+        common::{FileName, Globals, Mark, SourceMap, Spanned, GLOBALS},
+        ecma::{
+            ast::{EsVersion, Program},
+            parser::parse_file_as_program,
+            transforms::base::resolver,
+            visit::VisitMutWith,
+        },
+        testing::run_test,
+    };
+    use turbo_tasks_fs::{DiskFileSystem, FileSystem};
+
+    use super::*;
+
+    pub(crate) fn parse(cm: &Arc<SourceMap>, src: &str) -> Program {
+        let fm = cm.new_source_file(FileName::Anon, src.into());
+        parse_file_as_program(
+            &fm,
+            Default::default(),
+            EsVersion::latest(),
+            None,
+            &mut vec![],
+        )
+        .unwrap()
+    }
+
+    pub(crate) fn to_js(program: &Program, cm: &Arc<SourceMap>) -> String {
+        let mut bytes = Vec::new();
+        let mut emitter = Emitter {
+            cfg: swc_core::ecma::codegen::Config::default().with_minify(true),
+            cm: cm.clone(),
+            comments: None,
+            wr: JsWriter::new(cm.clone(), "\n", &mut bytes, None),
+        };
+
+        emitter.emit_program(program).unwrap();
+
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn namespace_import_shape() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(&cm, "");
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt(
+                    "frozen",
+                    "./a.js".into(),
+                    Some(EsmNamespaceShape::Frozen),
+                    false,
+                    false,
+                    None,
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt(
+                    "extensible",
+                    "./b.js".into(),
+                    Some(EsmNamespaceShape::Extensible),
+                    false,
+                    false,
+                    None,
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+
+            let js = to_js(&program, &cm);
+            assert!(js.contains(r#"var frozen=__turbopack_import_namespace__("./a.js",true);"#));
+            assert!(js.contains(r#"var extensible=__turbopack_import_namespace__("./b.js");"#));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn namespace_import() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(&cm, "");
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt(
+                    "ns",
+                    "./a.js".into(),
+                    Some(EsmNamespaceShape::Extensible),
+                    false,
+                    false,
+                    None,
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt(
+                    "plain",
+                    "./b.js".into(),
+                    None,
+                    false,
+                    false,
+                    None,
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+            let names = RuntimeFunctionNames {
+                import_namespace: "rtImportNamespace".to_string(),
+                ..Default::default()
+            };
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt(
+                    "renamed",
+                    "./c.js".into(),
+                    Some(EsmNamespaceShape::Extensible),
+                    false,
+                    false,
+                    None,
+                    &names,
+                ),
+                &Default::default(),
+            );
+
+            let js = to_js(&program, &cm);
+            assert!(js.contains(r#"var ns=__turbopack_import_namespace__("./a.js");"#));
+            assert!(js.contains(r#"var plain=__turbopack_import__("./b.js");"#));
+            assert!(js.contains(r#"var renamed=rtImportNamespace("./c.js");"#));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn hoisted_stmts_are_deterministic() {
+        run_test(false, |cm, _handler| {
+            let build = || {
+                let mut program = parse(&cm, "import './b'; import './a'; console.log(1);");
+                for (ident, request) in [("b", "./b.js"), ("a", "./a.js")] {
+                    insert_hoisted_stmt(
+                        &mut program,
+                        esm_import_stmt(
+                            ident,
+                            request.into(),
+                            None,
+                            false,
+                            false,
+                            None,
+                            &Default::default(),
+                        ),
+                        &Default::default(),
+                    );
+                    insert_hoisted_stmt(
+                        &mut program,
+                        import_coverage_stmt(request),
+                        &Default::default(),
+                    );
+                }
+                to_js(&program, &cm)
+            };
+
+            let js = build();
+            assert_eq!(js, build());
+            assert!(js.find("./b.js").unwrap() < js.find("./a.js").unwrap());
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn uncached_import() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(&cm, "");
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt(
+                    "a",
+                    "./a.js".into(),
+                    None,
+                    true,
+                    false,
+                    None,
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt(
+                    "b",
+                    "./b.js".into(),
+                    Some(EsmNamespaceShape::Frozen),
+                    true,
+                    false,
+                    None,
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+
+            let js = to_js(&program, &cm);
+            assert!(js.contains(r#"var a=__turbopack_import_uncached__("./a.js");"#));
+            assert!(js.contains(r#"var b=__turbopack_import_uncached__("./b.js",true);"#));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn import_attribute_types() {
+        assert_eq!(expected_extension("json"), Some("json"));
+        assert_eq!(expected_extension("css"), Some("css"));
+        assert_eq!(expected_extension("javascript"), None);
+        assert_eq!(
+            import_attribute_type_mismatch_message("./x.js", "json", "[project]/x.js"),
+            "The request ./x.js is imported with type \"json\", but resolves to [project]/x.js. \
+             It's loaded as the type of [project]/x.js instead."
+        );
+    }
+
+    #[test]
+    fn referenced_asset_kinds() {
+        let external = ReferencedAsset::OriginalReferenceTypeExternal("react".to_string());
+        assert!(external.is_external());
+
+        let builtin = ReferencedAsset::NodeBuiltin("node:fs".to_string());
+        assert!(!builtin.is_external());
+
+        let data_url = ReferencedAsset::DataUrl("data:text/javascript,".to_string());
+        assert!(!data_url.is_external());
+
+        assert!(!ReferencedAsset::None.is_external());
+    }
+
+    #[test]
+    fn explicit_transition_wins() {
+        assert_eq!(
+            effective_transition(Some("client"), Some("server")),
+            Some("client")
+        );
+        assert_eq!(effective_transition(None, Some("server")), Some("server"));
+        assert_eq!(effective_transition(Some("client"), None), Some("client"));
+        assert_eq!(effective_transition(None, None), None);
+    }
+
+    #[test]
+    fn duplicate_imports() {
+        // `import { a } from "./m"; import { b } from "./m"; import "./n";`
+        let owners = first_binding_owners([("./m", 1), ("./m", 2), ("./n", 3)]);
+        assert_eq!(owners, IndexMap::from([("./m", 1), ("./n", 3)]));
+    }
+
+    #[test]
+    fn chunk_load_counts() {
+        let mut counts = ReferenceChunkLoadCounts::default();
+        for load in [
+            ReferenceChunkLoad::SameChunk,
+            ReferenceChunkLoad::CrossChunk,
+            ReferenceChunkLoad::External,
+            ReferenceChunkLoad::SameChunk,
+        ] {
+            counts.add(load);
+        }
+
+        assert_eq!(counts.same_chunk, 2);
+        assert_eq!(counts.cross_chunk, 1);
+        assert_eq!(counts.external, 1);
+        assert_eq!(counts.unresolvable, 0);
+    }
+
+    #[test]
+    fn import_coverage_counts_per_key() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(&cm, "import './a';");
+            insert_hoisted_stmt(
+                &mut program,
+                import_coverage_stmt("./a.js"),
+                &Default::default(),
+            );
+
+            let js = to_js(&program, &cm);
+            assert!(js.contains(concat!(
+                r#"(globalThis.__turbopack_import_coverage__="#,
+                r#"globalThis.__turbopack_import_coverage__||{})["./a.js"]="#,
+                r#"(globalThis.__turbopack_import_coverage__["./a.js"]||0)+1"#,
+            )));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn import_stmts_keep_import_span() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(&cm, "import \"./a\";\nlocal();");
+            let Program::Module(module) = &program else {
+                panic!("expected a module");
+            };
+            let span = module.body[0].span();
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt(
+                    "a",
+                    "./a.js".into(),
+                    None,
+                    false,
+                    false,
+                    Some(span),
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+            // A second reference to the same module is deduplicated, no matter
+            // its span.
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt(
+                    "a",
+                    "./a.js".into(),
+                    None,
+                    false,
+                    false,
+                    None,
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+
+            let Program::Module(module) = &program else {
+                unreachable!();
+            };
+            let vars = module
+                .body
+                .iter()
+                .filter_map(|item| match item {
+                    ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => Some(var),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(vars.len(), 1);
+            assert_eq!(vars[0].span, span);
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn resource_hints() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(&cm, "");
+            insert_hoisted_stmt(
+                &mut program,
+                resource_hint_stmt(
+                    ImportResourceHint::Prefetch,
+                    "./a.js".into(),
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+            insert_hoisted_stmt(
+                &mut program,
+                resource_hint_stmt(
+                    ImportResourceHint::Preload,
+                    "./b.js".into(),
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+
+            let js = to_js(&program, &cm);
+            assert!(js.contains(r#"__turbopack_register_prefetch__("./a.js");"#));
+            assert!(js.contains(r#"__turbopack_register_preload__("./b.js");"#));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn parallel_prefetch_imports() {
+        assert!(matches!(
+            annotated_chunking_type(Some(PARALLEL_PREFETCH_CHUNKING_TYPE), true),
+            Ok((Some(ChunkingType::ParallelInheritAsync), None))
+        ));
+
+        run_test(false, |cm, _handler| {
+            let names = RuntimeFunctionNames::default();
+            let mut program = parse(&cm, "console.log(a);");
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt("a", "./a.js".into(), None, false, false, None, &names),
+                &Default::default(),
+            );
+            insert_hoisted_stmt(
+                &mut program,
+                prefetch_stmt("./a.js".into(), &names),
+                &Default::default(),
+            );
+
+            let js = to_js(&program, &cm);
+            let import = js.find(r#"var a=__turbopack_import__("./a.js")"#).unwrap();
+            let prefetch = js.find(r#"__turbopack_prefetch__("./a.js")"#).unwrap();
+            let usage = js.find("console.log(a)").unwrap();
+            assert!(import < prefetch);
+            assert!(prefetch < usage);
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn runtime_function_names() {
+        run_test(false, |cm, _handler| {
+            let names = RuntimeFunctionNames {
+                import: "rtImport".to_string(),
+                import_uncached: "rtImportUncached".to_string(),
+                register_prefetch: "rtPrefetch".to_string(),
+                ..Default::default()
+            };
+            let mut program = parse(&cm, "");
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt("a", "./a.js".into(), None, false, false, None, &names),
+                &Default::default(),
+            );
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt("b", "./b.js".into(), None, true, false, None, &names),
+                &Default::default(),
+            );
+            insert_hoisted_stmt(
+                &mut program,
+                resource_hint_stmt(ImportResourceHint::Prefetch, "./a.js".into(), &names),
+                &Default::default(),
+            );
+
+            let js = to_js(&program, &cm);
+            assert!(js.contains(r#"var a=rtImport("./a.js")"#));
+            assert!(js.contains(r#"var b=rtImportUncached("./b.js")"#));
+            assert!(js.contains(r#"rtPrefetch("./a.js")"#));
+            assert!(!js.contains("__turbopack_"));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn imported_module_idents_with_the_same_path() {
+        let idents = disambiguate_imported_module_idents([
+            ("[project]/a.js".to_string(), 1),
+            ("[project]/virtual.js".to_string(), 2),
+            ("[project]/virtual.js".to_string(), 3),
+            ("[project]/virtual.js".to_string(), 2),
+        ]);
+
+        assert_eq!(idents.len(), 3);
+        assert_eq!(
+            idents[&2],
+            magic_identifier::mangle("imported module [project]/virtual.js")
+        );
+        assert_eq!(
+            idents[&3],
+            magic_identifier::mangle("imported module [project]/virtual.js #2")
+        );
+        assert_ne!(idents[&2], idents[&3]);
+        assert_ne!(idents[&1], idents[&2]);
+    }
+
+    #[test]
+    fn imported_module_idents_with_queries() {
+        // Sources resolved with a query carry it in their ident.
+        let idents = disambiguate_imported_module_idents([
+            ("[project]/icon.svg?raw".to_string(), 1),
+            ("[project]/icon.svg?url".to_string(), 2),
+            ("[project]/icon.svg".to_string(), 3),
+        ]);
+
+        assert_eq!(idents.len(), 3);
+        assert_eq!(
+            idents[&1],
+            magic_identifier::mangle("imported module [project]/icon.svg?raw")
+        );
+        assert_eq!(
+            idents[&2],
+            magic_identifier::mangle("imported module [project]/icon.svg?url")
+        );
+        assert_ne!(idents[&1], idents[&2]);
+        assert_ne!(idents[&1], idents[&3]);
+    }
+
+    #[tokio::test]
+    async fn stable_import_idents() {
+        crate::register();
+        let tt = turbo_tasks::TurboTasks::new(turbo_tasks_memory::MemoryBackend::default());
+        tt.run_once(async {
+            // Module paths are rendered relative to the root of their file
+            // system, so the same module has the same readable ident on every
+            // machine.
+            let module_path = |root: &str| {
+                DiskFileSystem::new("project".to_string(), root.to_string(), vec![])
+                    .root()
+                    .join("src/a.js".to_string())
+                    .to_string()
+            };
+            let on_one_machine = module_path("/home/one/project").await?;
+            let on_another_machine = module_path("/srv/ci/checkout").await?;
+            assert_eq!(*on_one_machine, "[project]/src/a.js");
+            assert_eq!(on_one_machine, on_another_machine);
+
+            let readable = imported_module_ident(&on_one_machine, 0);
+            let stable = stable_ident(&readable);
+            assert_eq!(
+                stable,
+                stable_ident(&imported_module_ident(&on_another_machine, 0))
+            );
+            assert!(!stable.contains("src"));
+            assert!(stable.len() < readable.len());
+
+            assert_ne!(
+                stable,
+                stable_ident(&imported_module_ident(&on_one_machine, 1))
+            );
+            assert_ne!(
+                stable,
+                stable_ident(&imported_module_ident("[project]/src/b.js", 0))
+            );
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn unsupported_external_throws() {
+        assert_eq!(
+            unsupported_external_message("react"),
+            "The module imports the external react, but the chunking context doesn't support \
+             external modules. Loading it throws at runtime."
+        );
+
+        let cm = Arc::new(SourceMap::default());
+        let mut program = parse(&cm, "import React from \"react\";\nconsole.log(React);\n");
+        if let Program::Module(module) = &mut program {
+            module.body.remove(0);
+        }
+        insert_hoisted_stmt(
+            &mut program,
+            module_not_found_stmt("react", "src/index.js", None),
+            &Default::default(),
+        );
+        let js = to_js(&program, &cm);
+        let throw = js
+            .find("Cannot find module 'react' imported from 'src/index.js'")
+            .unwrap();
+        assert!(js.contains("throw e"));
+        assert!(throw < js.find("console.log(React)").unwrap());
+    }
+
+    #[test]
+    fn chunking_types_per_environment() {
+        let chunking_type = |annotation| {
+            let (chunking_type, _) = annotated_chunking_type(annotation, true).unwrap();
+            // A browser build chunks unannotated references, a server build
+            // doesn't.
+            (
+                chunking_type_in_context(annotation, chunking_type, true),
+                chunking_type_in_context(annotation, chunking_type, false),
+            )
+        };
+        assert!(matches!(
+            chunking_type(None),
+            (Some(ChunkingType::ParallelInheritAsync), None)
+        ));
+        assert!(matches!(
+            chunking_type(Some("parallel")),
+            (
+                Some(ChunkingType::ParallelInheritAsync),
+                Some(ChunkingType::ParallelInheritAsync)
+            )
+        ));
+        assert!(matches!(
+            chunking_type(Some("isolated")),
+            (Some(ChunkingType::Async), Some(ChunkingType::Async))
+        ));
+    }
+
+    #[test]
+    fn unknown_chunking_type_annotations() {
+        assert!(matches!(
+            annotated_chunking_type(Some("isolated"), true),
+            Ok((Some(ChunkingType::Async), None))
+        ));
+        assert!(matches!(
+            annotated_chunking_type(Some("eager"), true),
+            Ok((Some(ChunkingType::ParallelInheritAsync), None))
+        ));
+        assert!(matches!(
+            annotated_chunking_type(Some("none"), false),
+            Ok((None, None))
+        ));
+        assert!(matches!(
+            annotated_chunking_type(Some("paralel"), false),
+            Ok((Some(ChunkingType::ParallelInheritAsync), Some("paralel")))
+        ));
+        assert!(annotated_chunking_type(Some("paralel"), true).is_err());
+    }
+
+    #[test]
+    fn split_unresolvable_references() {
+        let (results, unresolvable) = split_unresolvable([
+            ("./a", "a.js", false),
+            ("./missing", "", true),
+            ("./b", "b.js", false),
+            ("./also-missing", "", true),
+        ]);
+        assert_eq!(results, vec!["a.js", "", "b.js", ""]);
+        assert_eq!(unresolvable, vec!["./missing", "./also-missing"]);
+    }
+
+    #[test]
+    fn group_references_by_module() {
+        let groups = group_by_key([
+            (Some("[project]/shared.js"), "import ./shared"),
+            (Some("react"), "import react"),
+            (Some("[project]/shared.js"), "import ../lib/shared"),
+            (None, "import ./ignored"),
+        ]);
+        assert_eq!(
+            groups.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    "[project]/shared.js",
+                    vec!["import ./shared", "import ../lib/shared"]
+                ),
+                ("react", vec!["import react"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn referenced_module_ids_of_internal_imports() {
+        let ids = unique_module_ids([
+            Some(ModuleId::String("[project]/a.js".to_string())),
+            None,
+            Some(ModuleId::Number(3)),
+            Some(ModuleId::String("[project]/a.js".to_string())),
+            Some(ModuleId::String("[project]/b.js".to_string())),
+        ]);
+        assert_eq!(
+            ids,
+            vec![
+                ModuleId::String("[project]/a.js".to_string()),
+                ModuleId::Number(3),
+                ModuleId::String("[project]/b.js".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn filter_references_by_chunked() {
+        let references = [
+            ("parallel", Some("parallel")),
+            ("none", Some("none")),
+            ("default", None),
+            ("other none", Some("none")),
+        ]
+        .map(|(name, annotation)| {
+            let (chunking_type, _) = annotated_chunking_type(annotation, true).unwrap();
+            (name, chunking_type)
+        });
+        assert_eq!(
+            filter_by_chunked(references, true),
+            vec!["parallel", "default"]
+        );
+        assert_eq!(
+            filter_by_chunked(references, false),
+            vec!["none", "other none"]
+        );
+    }
+
+    #[test]
+    fn module_not_found_names_origin() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(&cm, "");
+            insert_hoisted_stmt(
+                &mut program,
+                Stmt::Expr(ExprStmt {
+                    expr: Box::new(throw_module_not_found_expr_with_origin(
+                        "./x",
+                        "app/page.js",
+                    )),
+                    span: DUMMY_SP,
+                }),
+                &Default::default(),
+            );
+
+            let js = to_js(&program, &cm);
+            assert!(js.contains("Cannot find module './x' imported from 'app/page.js'"));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn weak_import() {
+        assert!(matches!(
+            annotated_chunking_type(Some("weak"), true),
+            Ok((Some(ChunkingType::Weak), None))
+        ));
+        run_test(false, |cm, _handler| {
+            // `./excluded.js` isn't included by another import, so its factory
+            // is missing from `__turbopack_modules__` at runtime and the
+            // binding falls back to `undefined`.
+            let mut program = parse(&cm, "");
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt(
+                    "a",
+                    "./excluded.js".into(),
+                    None,
+                    false,
+                    true,
+                    None,
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+
+            let js = to_js(&program, &cm);
+            assert!(js.contains(concat!(
+                r#"var a=__turbopack_modules__["./excluded.js"]?"#,
+                r#"__turbopack_import__("./excluded.js"):"#,
+                r#"("TURBOPACK weak import, "#,
+                r#"the module isn't included by another import",undefined)"#,
+            )));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn imported_module_idents_of_parts() {
+        let part_a = module_name_with_part("[project]/a.js", "export a");
+        let part_b = module_name_with_part("[project]/a.js", "export b");
+        let idents = disambiguate_imported_module_idents([
+            (part_a.clone(), (1, Some("a"))),
+            (part_b, (1, Some("b"))),
+            (part_a, (1, Some("a"))),
+        ]);
+
+        assert_eq!(idents.len(), 2);
+        assert_eq!(
+            idents[&(1, Some("a"))],
+            magic_identifier::mangle("imported module [project]/a.js (part export a)")
+        );
+        assert_eq!(
+            idents[&(1, Some("b"))],
+            magic_identifier::mangle("imported module [project]/a.js (part export b)")
+        );
+    }
+
+    #[test]
+    fn referenced_asset_equality() {
+        // Referenced placeables are compared in the integration tests, which
+        // have real modules.
+        let external =
+            |request: &str| ReferencedAsset::OriginalReferenceTypeExternal(request.to_string());
+        assert_eq!(external("lodash"), external("lodash"));
+        assert_ne!(external("lodash"), external("react"));
+        assert_ne!(
+            external("node:fs"),
+            ReferencedAsset::NodeBuiltin("node:fs".to_string())
+        );
+
+        assert_eq!(ReferencedAsset::None, ReferencedAsset::None);
+        assert_ne!(ReferencedAsset::None, external("lodash"));
+
+        let keys = HashSet::from([
+            external("lodash"),
+            external("lodash"),
+            external("react"),
+            ReferencedAsset::None,
+            ReferencedAsset::None,
+        ]);
+        assert_eq!(keys.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn resolve_statuses() {
+        crate::register();
+        turbo_tasks_testing::VcStorage::with(async {
+            let status = |item: ModuleResolveResultItem| {
+                let result = ModuleResolveResult {
+                    primary: IndexMap::from([(RequestKey::new("./a".to_string()), item)]),
+                    affecting_sources: Vec::new(),
+                }
+                .cell();
+                ResolveStatus::of(result, ReferencedAsset::from_resolve_result(result))
+            };
+            let external = |request: &str| {
+                ModuleResolveResultItem::OriginalReferenceTypeExternal(request.to_string())
+            };
+
+            assert_eq!(
+                status(external("lodash")).await.unwrap(),
+                ResolveStatus::External
+            );
+            assert_eq!(
+                status(external("node:fs")).await.unwrap(),
+                ResolveStatus::External
+            );
+            // Only `data:` URL requests are evaluated inline, see
+            // [ReferencedAsset::from_request_and_resolve_result].
+            assert_eq!(
+                status(external("data:text/javascript,export {}"))
+                    .await
+                    .unwrap(),
+                ResolveStatus::External
+            );
+            assert_eq!(
+                status(ModuleResolveResultItem::Ignore).await.unwrap(),
+                ResolveStatus::Ignored
+            );
+            let unresolvable = ModuleResolveResult::unresolveable().cell();
+            assert_eq!(
+                ResolveStatus::of(
+                    unresolvable,
+                    ReferencedAsset::from_resolve_result(unresolvable)
+                )
+                .await
+                .unwrap(),
+                ResolveStatus::Unresolvable
+            );
+        })
+        .await
+    }
+
+    #[test]
+    fn merges_results_of_parts() {
+        let result = ModuleResolveResult {
+            primary: IndexMap::from([(
+                RequestKey::new("./lib.js".to_string()),
+                ModuleResolveResultItem::OriginalReferenceTypeExternal("lib".to_string()),
+            )]),
+            affecting_sources: Vec::new(),
+        };
+        let merged = merge_part_results([
+            ("export a", &result),
+            ("export b", &result),
+            ("export c", &result),
+        ]);
+
+        let keys = merged
+            .primary
+            .keys()
+            .map(|key| key.request.clone().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            vec![
+                "./lib.js (part export a)",
+                "./lib.js (part export b)",
+                "./lib.js (part export c)",
+            ]
+        );
+    }
+
+    #[test]
+    fn previews_statements() {
+        let names = RuntimeFunctionNames::default();
+        let preview = |stmt: Stmt| {
+            preview_code_generation(
+                &CodeGeneration {
+                    visitors: vec![create_visitor!(visit_mut_program(program: &mut Program) {
+                        insert_hoisted_stmt(program, stmt.clone(), &Default::default());
+                    })],
+                },
+                &Default::default(),
+            )
+        };
+
+        assert_eq!(
+            preview(esm_import_stmt(
+                "a",
+                "./a.js".into(),
+                None,
+                false,
+                false,
+                None,
+                &names
+            )),
+            vec![r#"var a = __turbopack_import__("./a.js");"#]
+        );
+        assert_eq!(
+            preview(external_stmt("b", "b", false, false, false, false, &names)),
+            vec![r#"var b = __turbopack_external_require__("b", false);"#]
+        );
+        assert_eq!(
+            preview(remote_stmt("c", "app1", "default", "./Button", &names)),
+            vec![r#"var c = await __turbopack_federation_import__("app1", "./Button");"#]
+        );
+        assert_eq!(
+            preview(remote_stmt("d", "app1", "legacy", "./Button", &names)),
+            vec![r#"var d = await __turbopack_federation_import__("app1", "./Button", "legacy");"#]
+        );
+
+        let throw = preview(Stmt::Expr(ExprStmt {
+            expr: Box::new(throw_module_not_found_expr_with_origin(
+                "./missing.js",
+                "src/index.js",
+            )),
+            span: DUMMY_SP,
+        }));
+        assert_eq!(throw.len(), 1);
+        assert!(throw[0].contains("Cannot find module './missing.js'"));
+    }
+
+    #[tokio::test]
+    async fn detects_two_module_cycle() {
+        let edges = HashMap::from([("a", vec!["b"]), ("b", vec!["a"]), ("c", vec![])]);
+        let edges = |module| {
+            let targets = edges[module].clone();
+            async move { Ok(targets) }
+        };
+
+        // a imports b, which imports a back.
+        assert!(reaches(vec!["b"], "a", edges).await.unwrap());
+        assert!(reaches(vec!["a"], "b", edges).await.unwrap());
+        // c doesn't import anything.
+        assert!(!reaches(vec!["c"], "a", edges).await.unwrap());
+    }
+
+    #[test]
+    fn defers_cyclic_imports() {
+        run_test(false, |cm, _handler| {
+            let mut stmt = esm_import_stmt(
+                "a",
+                "./a.js".into(),
+                None,
+                false,
+                false,
+                None,
+                &Default::default(),
+            );
+            defer_cyclic_import(&mut stmt, "./a.js".into());
+            let mut program = parse(&cm, "");
+            insert_hoisted_stmt(&mut program, stmt, &Default::default());
+
+            let js = to_js(&program, &cm);
+            assert!(js.contains(r#"__turbopack_cache__["./a.js"]?.loaded===false"#));
+            assert!(js.contains(r#"}))(__turbopack_import__("./a.js"));"#));
+            assert!(js.contains("Circular import"));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn describes_imports_with_origin() {
+        let annotations = ImportAnnotations::parse("{ chunking-type: parallel }").unwrap();
+        assert_eq!(
+            describe_import("./x", Some("app/page.js"), &annotations, None),
+            "import ./x (from app/page.js) { chunking-type: parallel }"
+        );
+        // The form without an origin is part of cache keys and stays as is.
+        assert_eq!(
+            describe_import("./x", None, &annotations, None),
+            "import ./x { chunking-type: parallel }"
+        );
+    }
+
+    #[test]
+    fn drops_unused_side_effect_free_imports() {
+        let cm = Arc::new(SourceMap::default());
+        let ident = "__TURBOPACK__imported__module__a__";
+
+        // The binding isn't used, only a property and a key of the same name.
+        let program = parse(
+            &cm,
+            "local.__TURBOPACK__imported__module__a__();\nvar o = { \
+             __TURBOPACK__imported__module__a__: 1 };",
+        );
+        // Dropped when side-effect free, retained for its side effects otherwise.
+        assert!(!import_is_emitted(&program, ident, true));
+        assert!(import_is_emitted(&program, ident, false));
+
+        let program = parse(&cm, "__TURBOPACK__imported__module__a__.foo();");
+        assert!(import_is_emitted(&program, ident, true));
+
+        // A local of the same name shadows the binding.
+        GLOBALS.set(&Globals::new(), || {
+            let mut program = parse(
+                &cm,
+                "function f(__TURBOPACK__imported__module__a__) {\n  \
+                 __TURBOPACK__imported__module__a__.foo();\n}",
+            );
+            program.visit_mut_with(&mut resolver(Mark::new(), Mark::new(), false));
+            assert!(!import_is_emitted(&program, ident, true));
+        });
     }
 }
@@ -8,17 +8,73 @@ use swc_core::{
         },
         visit::fields::PropField,
     },
+    quote_expr,
 };
 use turbo_tasks::Vc;
+use turbopack_core::chunk::{ChunkableModuleReference, ChunkingType, ModuleId};
 
-use super::EsmAssetReference;
+use super::{base::runtime_function, EsmAssetReference};
 use crate::{
-    chunk::EcmascriptChunkingContext,
+    chunk::{EcmascriptChunkingContext, RuntimeFunctionNames},
     code_gen::{CodeGenerateable, CodeGeneration},
     create_visitor,
     references::AstPath,
+    utils::module_id_to_lit,
 };
 
+/// Creates an access to the export `export` of the imported module bound to
+/// `imported_module`.
+///
+/// This always uses bracket access with a string literal (`m["default"]`), so
+/// reserved words like `default` or `delete` are valid in older targets too.
+pub(crate) fn export_member_expr(imported_module: &str, export: &str) -> Expr {
+    Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(Expr::Ident(Ident::new(imported_module.into(), DUMMY_SP))),
+        prop: MemberProp::Computed(ComputedPropName {
+            span: DUMMY_SP,
+            expr: Box::new(Expr::Lit(Lit::Str(Str {
+                span: DUMMY_SP,
+                value: export.into(),
+                raw: None,
+            }))),
+        }),
+    })
+}
+
+/// Creates a read of the export `export` of the module `id` for a live
+/// binding, e.g. `__turbopack_esm_import__("./a.js", "value")`. The export is
+/// read on every access, so reassignments in the exporting module are
+/// observable.
+pub(crate) fn live_binding_expr(id: &ModuleId, export: &str, names: &RuntimeFunctionNames) -> Expr {
+    *quote_expr!(
+        "$esm_import($id, $export)",
+        esm_import = runtime_function(&names.esm_import),
+        id: Expr = module_id_to_lit(id),
+        export: Expr = export.into()
+    )
+}
+
+/// The id of the module `reference` binds live, if named imports through it
+/// should be live bindings. Only imports of internal modules that are
+/// evaluated with the importing module are, weak and async imports don't
+/// evaluate the module.
+async fn live_binding_module_id(
+    reference: Vc<EsmAssetReference>,
+    context: Vc<Box<dyn EcmascriptChunkingContext>>,
+) -> Result<Option<Vc<ModuleId>>> {
+    if !*context.esm_live_bindings().await? {
+        return Ok(None);
+    }
+    if !matches!(
+        *reference.chunking_type_for(Vc::upcast(context)).await?,
+        Some(ChunkingType::Parallel | ChunkingType::ParallelInheritAsync)
+    ) {
+        return Ok(None);
+    }
+    Ok(*reference.referenced_module_id(context).await?)
+}
+
 #[turbo_tasks::value(shared)]
 #[derive(Hash, Debug)]
 pub struct EsmBinding {
@@ -49,33 +105,29 @@ impl CodeGenerateable for EsmBinding {
     #[turbo_tasks::function]
     async fn code_generation(
         self: Vc<Self>,
-        _context: Vc<Box<dyn EcmascriptChunkingContext>>,
+        context: Vc<Box<dyn EcmascriptChunkingContext>>,
     ) -> Result<Vc<CodeGeneration>> {
         let this = self.await?;
         let mut visitors = Vec::new();
-        let imported_module = this.reference.get_referenced_asset();
-
-        fn make_expr(imported_module: &str, export: Option<&str>) -> Expr {
-            if let Some(export) = export {
-                Expr::Member(MemberExpr {
-                    span: DUMMY_SP,
-                    obj: Box::new(Expr::Ident(Ident::new(imported_module.into(), DUMMY_SP))),
-                    prop: MemberProp::Computed(ComputedPropName {
-                        span: DUMMY_SP,
-                        expr: Box::new(Expr::Lit(Lit::Str(Str {
-                            span: DUMMY_SP,
-                            value: export.into(),
-                            raw: None,
-                        }))),
-                    }),
-                })
-            } else {
-                Expr::Ident(Ident::new(imported_module.into(), DUMMY_SP))
-            }
-        }
+        let imported_module = this.reference.get_referenced_ident(context);
+
+        let live_binding = match this.export {
+            Some(_) => match live_binding_module_id(this.reference, context).await? {
+                Some(id) => Some((id.await?, context.runtime_function_names().await?)),
+                None => None,
+            },
+            None => None,
+        };
+
+        let make_expr =
+            move |imported_module: &str, export: Option<&str>| match (export, &live_binding) {
+                (Some(export), Some((id, names))) => live_binding_expr(id, export, names),
+                (Some(export), None) => export_member_expr(imported_module, export),
+                (None, _) => Expr::Ident(Ident::new(imported_module.into(), DUMMY_SP)),
+            };
 
         let mut ast_path = this.ast_path.await?.clone_value();
-        let imported_module = imported_module.await?.get_ident().await?;
+        let imported_module = imported_module.await?.clone_value();
 
         loop {
             match ast_path.last() {
@@ -120,3 +172,72 @@ impl CodeGenerateable for EsmBinding {
         Ok(CodeGeneration { visitors }.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use swc_core::{
+        common::SourceMap,
+        ecma::codegen::{text_writer::JsWriter, Emitter, Node},
+    };
+
+    use super::*;
+
+    fn to_js(expr: &Expr) -> String {
+        let cm = Arc::new(SourceMap::default());
+        let mut bytes = Vec::new();
+        let mut emitter = Emitter {
+            cfg: Default::default(),
+            cm: cm.clone(),
+            comments: None,
+            wr: JsWriter::new(cm, "\n", &mut bytes, None),
+        };
+        expr.emit_with(&mut emitter).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn reserved_export_names() {
+        assert_eq!(
+            to_js(&export_member_expr(
+                "__TURBOPACK__imported__module__",
+                "default"
+            )),
+            "__TURBOPACK__imported__module__[\"default\"]"
+        );
+        assert_eq!(
+            to_js(&export_member_expr(
+                "__TURBOPACK__imported__module__",
+                "delete"
+            )),
+            "__TURBOPACK__imported__module__[\"delete\"]"
+        );
+    }
+
+    #[test]
+    fn live_bindings() {
+        let names = RuntimeFunctionNames::default();
+        assert_eq!(
+            to_js(&live_binding_expr(
+                &ModuleId::String("./counter.js".to_string()),
+                "count",
+                &names
+            )),
+            "__turbopack_esm_import__(\"./counter.js\", \"count\")"
+        );
+        assert_eq!(
+            to_js(&live_binding_expr(&ModuleId::Number(7), "default", &names)),
+            "__turbopack_esm_import__(7, \"default\")"
+        );
+
+        let names = RuntimeFunctionNames {
+            esm_import: "rtEsmImport".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            to_js(&live_binding_expr(&ModuleId::Number(7), "count", &names)),
+            "rtEsmImport(7, \"count\")"
+        );
+    }
+}
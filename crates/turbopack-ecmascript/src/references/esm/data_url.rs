@@ -0,0 +1,199 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use swc_core::{
+    common::{FileName, SourceMap, Span, DUMMY_SP},
+    ecma::{
+        ast::{
+            ArrowExpr, BlockStmt, BlockStmtOrExpr, CallExpr, Callee, EsVersion, Expr, Ident,
+            ParenExpr, Stmt,
+        },
+        parser::parse_file_as_script,
+        visit::{VisitMut, VisitMutWith},
+    },
+    quote,
+};
+
+use super::base::set_var_decl_span;
+
+/// The decoded content of a `data:` URL.
+#[derive(Debug, PartialEq, Eq)]
+struct DataUrl {
+    /// The lowercased MIME type without parameters, e.g. `text/javascript`.
+    mime_type: String,
+    content: String,
+}
+
+impl DataUrl {
+    /// Decodes a `data:[<mediatype>][;base64],<data>` URL.
+    fn parse(url: &str) -> Result<Self> {
+        let (meta, data) = url
+            .strip_prefix("data:")
+            .and_then(|url| url.split_once(','))
+            .context("expected a `data:[<mediatype>][;base64],<data>` URL")?;
+        let (meta, base64) = match meta.strip_suffix(";base64") {
+            Some(meta) => (meta, true),
+            None => (meta, false),
+        };
+        let mime_type = meta.split(';').next().unwrap_or_default().trim();
+        let mime_type = if mime_type.is_empty() {
+            "text/plain".to_string()
+        } else {
+            mime_type.to_ascii_lowercase()
+        };
+        let content = if base64 {
+            String::from_utf8(STANDARD.decode(data).context("invalid base64 data")?)
+                .context("the data is not valid UTF-8")?
+        } else {
+            urlencoding::decode(data)
+                .context("the data is not valid UTF-8")?
+                .into_owned()
+        };
+        Ok(DataUrl { mime_type, content })
+    }
+}
+
+/// Creates the statement that evaluates the module of the data URL `url`
+/// inline and binds its namespace object to `ident`.
+///
+/// JavaScript is evaluated in its own function scope and has no exports, so
+/// only scripts are supported. JSON is bound as the default export.
+pub(crate) fn data_url_module_stmt(ident: &str, url: &str, span: Option<Span>) -> Result<Stmt> {
+    let DataUrl { mime_type, content } = DataUrl::parse(url)?;
+    let name = Ident::new(ident.into(), DUMMY_SP);
+    let mut stmt = match &*mime_type {
+        "text/javascript" | "application/javascript" => {
+            let cm = Arc::new(SourceMap::default());
+            let fm = cm.new_source_file(FileName::Custom(url.to_string()), content);
+            let mut errors = vec![];
+            let script = parse_file_as_script(
+                &fm,
+                Default::default(),
+                EsVersion::latest(),
+                None,
+                &mut errors,
+            )
+            .ok()
+            .filter(|_| errors.is_empty())
+            .ok_or_else(|| {
+                anyhow!("the data can't be parsed as a script, ESM syntax is not supported")
+            })?;
+            let mut stmts = script.body;
+            // The spans point into the source map created above, which is not
+            // the one of the importing module.
+            stmts.visit_mut_with(&mut ResetSpans);
+            stmts.push(quote!("return {};" as Stmt));
+            quote!(
+                "var $name = $init;" as Stmt,
+                name = name,
+                init: Expr = iife(stmts),
+            )
+        }
+        "application/json" => {
+            serde_json::from_str::<serde_json::Value>(&content).context("invalid JSON")?;
+            quote!(
+                "var $name = { default: JSON.parse($json) };" as Stmt,
+                name = name,
+                json: Expr = content.into(),
+            )
+        }
+        _ => bail!("the MIME type {mime_type} is not supported"),
+    };
+    set_var_decl_span(&mut stmt, span);
+    Ok(stmt)
+}
+
+/// Creates `(() => { ...stmts })()`.
+fn iife(stmts: Vec<Stmt>) -> Expr {
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: Callee::Expr(Box::new(Expr::Paren(ParenExpr {
+            span: DUMMY_SP,
+            expr: Box::new(Expr::Arrow(ArrowExpr {
+                span: DUMMY_SP,
+                params: vec![],
+                body: Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts,
+                })),
+                is_async: false,
+                is_generator: false,
+                type_params: None,
+                return_type: None,
+            })),
+        }))),
+        args: vec![],
+        type_args: None,
+    })
+}
+
+struct ResetSpans;
+
+impl VisitMut for ResetSpans {
+    fn visit_mut_span(&mut self, span: &mut Span) {
+        *span = DUMMY_SP;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::references::esm::{
+        base::tests::{parse, to_js},
+        hoisting::insert_hoisted_stmt,
+    };
+
+    fn inline_module_js(url: &str) -> String {
+        let cm = Arc::new(SourceMap::default());
+        let mut program = parse(&cm, "");
+        insert_hoisted_stmt(
+            &mut program,
+            data_url_module_stmt("m", url, None).unwrap(),
+            &Default::default(),
+        );
+        to_js(&program, &cm)
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            DataUrl::parse("data:text/javascript;charset=utf-8,console.log(%22a%22)").unwrap(),
+            DataUrl {
+                mime_type: "text/javascript".to_string(),
+                content: "console.log(\"a\")".to_string(),
+            }
+        );
+        assert_eq!(
+            DataUrl::parse("data:;base64,YQ==").unwrap(),
+            DataUrl {
+                mime_type: "text/plain".to_string(),
+                content: "a".to_string(),
+            }
+        );
+        assert!(DataUrl::parse("data:text/javascript").is_err());
+    }
+
+    #[test]
+    fn javascript_data_url() {
+        let js = inline_module_js("data:text/javascript,var a = 1; console.log(a);");
+        assert!(js.contains("var m=(()=>{var a=1;console.log(a);return{};})()"));
+
+        let js = inline_module_js("data:text/javascript;base64,Y29uc29sZS5sb2coMSk=");
+        assert!(js.contains("var m=(()=>{console.log(1);return{};})()"));
+
+        assert!(data_url_module_stmt("m", "data:text/javascript,export default 1", None).is_err());
+    }
+
+    #[test]
+    fn json_data_url() {
+        let js = inline_module_js("data:application/json,%7B%22a%22%3A1%7D");
+        assert!(js.contains(r#"var m={default:JSON.parse('{"a":1}')}"#));
+
+        let js = inline_module_js("data:application/json;base64,WzFd");
+        assert!(js.contains(r#"var m={default:JSON.parse("[1]")}"#));
+
+        assert!(data_url_module_stmt("m", "data:application/json,{", None).is_err());
+        assert!(data_url_module_stmt("m", "data:text/css,a{}", None).is_err());
+    }
+}
@@ -16,7 +16,7 @@ use turbopack_core::{
 
 use super::super::pattern_mapping::{PatternMapping, ResolveType};
 use crate::{
-    chunk::EcmascriptChunkingContext,
+    chunk::{EcmascriptChunkPlaceable, EcmascriptChunkingContext},
     code_gen::{CodeGenerateable, CodeGeneration},
     create_visitor,
     references::AstPath,
@@ -54,6 +54,32 @@ impl EsmAsyncAssetReference {
             import_externals,
         })
     }
+
+    /// Whether the dynamically imported module is an async module, i.e. its
+    /// exports are only available once its top-level await or async externals
+    /// settled. Async-ness inherited from async dependencies is only known per
+    /// chunk item, see [AsyncModule::is_async], so it isn't included.
+    ///
+    /// The import of an async module is awaited with
+    /// `__turbopack_async_import__`. Unlike static imports, it doesn't make the
+    /// importing module async.
+    ///
+    /// [AsyncModule::is_async]: crate::references::async_module::AsyncModule::is_async
+    #[turbo_tasks::function]
+    pub async fn is_target_async(self: Vc<Self>) -> Result<Vc<bool>> {
+        let Some(module) = *self.resolve_reference().first_module().await? else {
+            return Ok(Vc::cell(false));
+        };
+        let Some(placeable) =
+            Vc::try_resolve_downcast::<Box<dyn EcmascriptChunkPlaceable>>(module).await?
+        else {
+            return Ok(Vc::cell(false));
+        };
+        Ok(match *placeable.get_async_module().await? {
+            Some(async_module) => async_module.is_async(None),
+            None => Vc::cell(false),
+        })
+    }
 }
 
 #[turbo_tasks::value_impl]
@@ -63,7 +89,9 @@ impl ModuleReference for EsmAsyncAssetReference {
         esm_resolve(
             self.origin,
             self.request,
-            Default::default(),
+            Value::new(EcmaScriptModulesReferenceSubType::DynamicImport),
+            None,
+            None,
             try_to_severity(self.in_try),
             Some(self.issue_source),
         )
@@ -93,19 +121,23 @@ impl ChunkableModuleReference for EsmAsyncAssetReference {
 impl CodeGenerateable for EsmAsyncAssetReference {
     #[turbo_tasks::function]
     async fn code_generation(
-        &self,
+        self: Vc<Self>,
         chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
     ) -> Result<Vc<CodeGeneration>> {
+        let async_import = *self.is_target_async().await?;
+        let this = self.await?;
         let pm = PatternMapping::resolve_request(
-            self.request,
-            self.origin,
+            this.request,
+            this.origin,
             Vc::upcast(chunking_context),
             esm_resolve(
-                self.origin,
-                self.request,
+                this.origin,
+                this.request,
                 Value::new(EcmaScriptModulesReferenceSubType::DynamicImport),
-                try_to_severity(self.in_try),
-                Some(self.issue_source),
+                None,
+                None,
+                try_to_severity(this.in_try),
+                Some(this.issue_source),
             ),
             if matches!(
                 *chunking_context.environment().chunk_loading().await?,
@@ -118,15 +150,15 @@ impl CodeGenerateable for EsmAsyncAssetReference {
         )
         .await?;
 
-        let path = &self.path.await?;
-        let import_externals = self.import_externals;
+        let path = &this.path.await?;
+        let import_externals = this.import_externals;
 
         let visitor = create_visitor!(path, visit_mut_expr(expr: &mut Expr) {
             let old_expr = expr.take();
             let message = if let Expr::Call(CallExpr { args, ..}) = old_expr {
                 match args.into_iter().next() {
                     Some(ExprOrSpread { spread: None, expr: key_expr }) => {
-                        *expr = pm.create_import(*key_expr, import_externals);
+                        *expr = pm.create_import(*key_expr, import_externals, async_import);
                         return;
                     }
                     // These are SWC bugs: https://github.com/swc-project/swc/issues/5394
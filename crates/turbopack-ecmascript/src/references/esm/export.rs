@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     ops::ControlFlow,
 };
 
@@ -8,8 +8,8 @@ use serde::{Deserialize, Serialize};
 use swc_core::{
     common::DUMMY_SP,
     ecma::ast::{
-        self, ComputedPropName, Expr, ExprStmt, Ident, KeyValueProp, Lit, MemberExpr, MemberProp,
-        ModuleItem, ObjectLit, Program, Prop, PropName, PropOrSpread, Script, Stmt, Str,
+        self, ArrayLit, Expr, ExprOrSpread, ExprStmt, Ident, KeyValueProp, Lit, ModuleItem,
+        ObjectLit, Program, Prop, PropName, PropOrSpread, Script, Stmt, Str,
     },
     quote, quote_expr,
 };
@@ -19,14 +19,15 @@ use turbopack_core::{
     issue::{analyze::AnalyzeIssue, IssueExt, IssueSeverity, StyledString},
     module::Module,
     reference::ModuleReference,
+    resolve::origin::ResolveOrigin,
 };
 
-use super::base::ReferencedAsset;
+use super::{base::ReferencedAsset, binding::export_member_expr, EsmAssetReference};
 use crate::{
     chunk::{EcmascriptChunkPlaceable, EcmascriptChunkingContext, EcmascriptExports},
     code_gen::{CodeGenerateable, CodeGeneration},
     create_visitor,
-    references::esm::base::insert_hoisted_stmt,
+    references::{esm::hoisting::insert_hoisted_stmt, util::request_to_string},
 };
 
 #[derive(Clone, Hash, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
@@ -351,8 +352,14 @@ impl EsmExports {
     pub async fn expand_exports(&self) -> Result<Vc<ExpandedExports>> {
         let mut exports: BTreeMap<String, EsmExport> = self.exports.clone();
         let mut dynamic_exports = vec![];
+        // The star export and its module that each export expanded from star
+        // exports comes from.
+        let mut star_exported = HashMap::new();
 
         for esm_ref in self.star_exports.iter() {
+            if emit_remote_star_export_issue(*esm_ref).await? {
+                continue;
+            }
             // TODO(PACK-2176): we probably need to handle re-exporting from external
             // modules.
             let ReferencedAsset::Some(asset) =
@@ -364,11 +371,18 @@ impl EsmExports {
             let export_info = expand_star_exports(*asset).await?;
 
             for export in &export_info.star_exports {
-                if !exports.contains_key(export) {
+                if let Some(&(first_ref, first_asset)) = star_exported.get(export) {
+                    if first_asset != *asset
+                        && !is_same_binding(first_asset, *asset, export).await?
+                    {
+                        emit_star_export_conflict_issue(export, first_ref, *esm_ref).await?;
+                    }
+                } else if !exports.contains_key(export) {
                     exports.insert(
                         export.clone(),
                         EsmExport::ImportedBinding(Vc::upcast(*esm_ref), export.to_string()),
                     );
+                    star_exported.insert(export.clone(), (*esm_ref, *asset));
                 }
             }
 
@@ -385,25 +399,171 @@ impl EsmExports {
     }
 }
 
+/// Whether the export `export` of the modules `a` and `b` is the same binding,
+/// e.g. because both re-export it from a third module.
+async fn is_same_binding(
+    a: Vc<Box<dyn EcmascriptChunkPlaceable>>,
+    b: Vc<Box<dyn EcmascriptChunkPlaceable>>,
+    export: &str,
+) -> Result<bool> {
+    let a = follow_reexports_internal(a, export.to_string(), false).await?;
+    let b = follow_reexports_internal(b, export.to_string(), false).await?;
+    Ok(a.ty == FoundExportType::Found
+        && b.ty == FoundExportType::Found
+        && a.module.resolve().await? == b.module.resolve().await?
+        && a.export_name == b.export_name)
+}
+
+/// Warns that `export` is exported by the star exports `first` and `second`
+/// with different bindings. It's exported from `first`, which comes first.
+async fn emit_star_export_conflict_issue(
+    export: &str,
+    first: Vc<Box<dyn ModuleReference>>,
+    second: Vc<Box<dyn ModuleReference>>,
+) -> Result<()> {
+    let (Some(first), Some(second)) = (
+        Vc::try_resolve_downcast_type::<EsmAssetReference>(first).await?,
+        Vc::try_resolve_downcast_type::<EsmAssetReference>(second).await?,
+    ) else {
+        return Ok(());
+    };
+    let second = second.await?;
+    AnalyzeIssue {
+        code: None,
+        category: Vc::cell("analyze".to_string()),
+        message: StyledString::Text(format!(
+            "The export {export} is exported by both `export * from \"{}\"` and `export * from \
+             \"{}\"`, which is ambiguous. It's exported from the first one.",
+            request_to_string(first.await?.request).await?,
+            request_to_string(second.request).await?,
+        ))
+        .cell(),
+        source_ident: AssetIdent::from_path(second.origin.origin_path()),
+        severity: IssueSeverity::Warning.into(),
+        source: second.issue_source,
+        title: Vc::cell("conflicting star exports".to_string()),
+    }
+    .cell()
+    .emit();
+    Ok(())
+}
+
+/// Reports `export * from` a module of a module federation remote container,
+/// which isn't supported: the remote module is only bound after awaiting it,
+/// so its exports can't be re-exported like the ones of a chunked module.
+/// Returns whether `esm_ref` imports from a remote.
+async fn emit_remote_star_export_issue(esm_ref: Vc<Box<dyn ModuleReference>>) -> Result<bool> {
+    let Some(esm_ref) = Vc::try_resolve_downcast_type::<EsmAssetReference>(esm_ref).await? else {
+        return Ok(false);
+    };
+    let esm_ref = esm_ref.await?;
+    let Some(remote) = esm_ref.annotations.remote() else {
+        return Ok(false);
+    };
+    AnalyzeIssue {
+        code: None,
+        category: Vc::cell("analyze".to_string()),
+        message: StyledString::Text(format!(
+            "`export * from \"{}\"` re-exports the module {} of the module federation remote {}, \
+             which isn't supported. Re-export its exports by name instead.",
+            request_to_string(esm_ref.request).await?,
+            remote.module,
+            remote.container,
+        ))
+        .cell(),
+        source_ident: AssetIdent::from_path(esm_ref.origin.origin_path()),
+        severity: IssueSeverity::Error.into(),
+        source: esm_ref.issue_source,
+        title: Vc::cell("star export from a module federation remote".to_string()),
+    }
+    .cell()
+    .emit();
+    Ok(true)
+}
+
+/// The ident the module referenced by `esm_ref` is bound to in the importing
+/// module.
+async fn referenced_ident(
+    esm_ref: Vc<Box<dyn ModuleReference>>,
+    chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+) -> Result<Option<String>> {
+    if let Some(esm_ref) = Vc::try_resolve_downcast_type::<EsmAssetReference>(esm_ref).await? {
+        return Ok(esm_ref
+            .get_referenced_ident(chunking_context)
+            .await?
+            .clone_value());
+    }
+    let referenced_asset =
+        ReferencedAsset::from_resolve_result(esm_ref.resolve_reference()).await?;
+    if *chunking_context.stable_import_idents().await? {
+        referenced_asset.get_stable_ident().await
+    } else {
+        referenced_asset.get_ident().await
+    }
+}
+
+/// The names of the exports the module declares itself, which take precedence
+/// over the exports of its star re-exports.
+fn declared_export_names(exports: &BTreeMap<String, EsmExport>) -> Vec<&str> {
+    exports.keys().map(String::as_str).collect()
+}
+
+/// Copies the own enumerable exports of the namespace bound to `ident` to the
+/// exports of the module, except for `default` and the `exclude`d names the
+/// module declares itself. One call handles one `export * from "..."`, whose
+/// exports couldn't be expanded statically.
+fn reexport_namespace_expr(ident: &str, exclude: &[&str]) -> Box<Expr> {
+    let exclude = ArrayLit {
+        span: DUMMY_SP,
+        elems: exclude
+            .iter()
+            .map(|&name| {
+                Some(ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Lit(Lit::Str(Str {
+                        span: DUMMY_SP,
+                        value: name.into(),
+                        raw: None,
+                    }))),
+                })
+            })
+            .collect(),
+    };
+    quote_expr!(
+        "__turbopack_reexport_namespace__($arg, $exclude)",
+        arg: Expr = Ident::new(ident.into(), DUMMY_SP).into(),
+        exclude: Expr = exclude.into()
+    )
+}
+
 #[turbo_tasks::value_impl]
 impl CodeGenerateable for EsmExports {
     #[turbo_tasks::function]
     async fn code_generation(
         self: Vc<Self>,
-        _context: Vc<Box<dyn EcmascriptChunkingContext>>,
+        context: Vc<Box<dyn EcmascriptChunkingContext>>,
     ) -> Result<Vc<CodeGeneration>> {
         let mut visitors = Vec::new();
 
+        let this = self.await?;
         let expanded = self.expand_exports().await?;
 
+        let exclude = declared_export_names(&this.exports);
         let mut dynamic_exports = Vec::<Box<Expr>>::new();
-        for dynamic_export_asset in &expanded.dynamic_exports {
-            let ident = ReferencedAsset::get_ident_from_placeable(dynamic_export_asset).await?;
+        for esm_ref in &this.star_exports {
+            let ReferencedAsset::Some(asset) =
+                &*ReferencedAsset::from_resolve_result(esm_ref.resolve_reference()).await?
+            else {
+                continue;
+            };
+            if !expanded.dynamic_exports.contains(asset) {
+                continue;
+            }
+            let Some(ident) = referenced_ident(*esm_ref, context).await? else {
+                continue;
+            };
 
-            dynamic_exports.push(quote_expr!(
-                "__turbopack_dynamic__($arg)",
-                arg: Expr = Ident::new(ident.into(), DUMMY_SP).into()
-            ));
+            dynamic_exports.push(reexport_namespace_expr(&ident, &exclude));
         }
 
         let mut props = Vec::new();
@@ -417,30 +577,15 @@ impl CodeGenerateable for EsmExports {
                     local = Ident::new((name as &str).into(), DUMMY_SP)
                 )),
                 EsmExport::ImportedBinding(esm_ref, name) => {
-                    let referenced_asset =
-                        ReferencedAsset::from_resolve_result(esm_ref.resolve_reference()).await?;
-                    referenced_asset.get_ident().await?.map(|ident| {
+                    referenced_ident(*esm_ref, context).await?.map(|ident| {
                         quote!(
                             "(() => $expr)" as Expr,
-                            expr: Expr = Expr::Member(MemberExpr {
-                                span: DUMMY_SP,
-                                obj: Box::new(Expr::Ident(Ident::new(ident.into(), DUMMY_SP))),
-                                prop: MemberProp::Computed(ComputedPropName {
-                                    span: DUMMY_SP,
-                                    expr: Box::new(Expr::Lit(Lit::Str(Str {
-                                        span: DUMMY_SP,
-                                        value: (name as &str).into(),
-                                        raw: None,
-                                    })))
-                                })
-                            })
+                            expr: Expr = export_member_expr(&ident, name)
                         )
                     })
                 }
                 EsmExport::ImportedNamespace(esm_ref) => {
-                    let referenced_asset =
-                        ReferencedAsset::from_resolve_result(esm_ref.resolve_reference()).await?;
-                    referenced_asset.get_ident().await?.map(|ident| {
+                    referenced_ident(*esm_ref, context).await?.map(|ident| {
                         quote!(
                             "(() => $imported)" as Expr,
                             imported = Ident::new(ident.into(), DUMMY_SP)
@@ -463,6 +608,7 @@ impl CodeGenerateable for EsmExports {
             span: DUMMY_SP,
             props,
         });
+        let markers = context.hoisting_markers().await?;
         let dynamic_stmt = if !dynamic_exports.is_empty() {
             Some(Stmt::Expr(ExprStmt {
                 span: DUMMY_SP,
@@ -485,10 +631,66 @@ impl CodeGenerateable for EsmExports {
                 }
             }
             if let Some(dynamic_stmt) = dynamic_stmt.clone() {
-                insert_hoisted_stmt(program, dynamic_stmt);
+                insert_hoisted_stmt(program, dynamic_stmt, &markers);
             }
         }));
 
         Ok(CodeGeneration { visitors }.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The namespace ident and the excluded names of a
+    /// `__turbopack_reexport_namespace__` call.
+    fn reexport_namespace_args(expr: &Expr) -> (String, Vec<String>) {
+        let Expr::Call(call) = expr else {
+            panic!("expected a call");
+        };
+        let Expr::Ident(ident) = &*call.args[0].expr else {
+            panic!("expected an ident");
+        };
+        let Expr::Array(exclude) = &*call.args[1].expr else {
+            panic!("expected an array");
+        };
+        let exclude = exclude
+            .elems
+            .iter()
+            .map(|elem| match elem.as_ref().map(|elem| &*elem.expr) {
+                Some(Expr::Lit(Lit::Str(name))) => name.value.to_string(),
+                _ => panic!("expected a string"),
+            })
+            .collect();
+        (ident.sym.to_string(), exclude)
+    }
+
+    #[test]
+    fn star_reexports_exclude_local_exports() {
+        // export * from "./a";
+        // export * from "./b";
+        // export const local = 1;
+        let exports = BTreeMap::from([(
+            "local".to_string(),
+            EsmExport::LocalBinding("local".to_string()),
+        )]);
+        let exclude = declared_export_names(&exports);
+
+        let calls = ["__TURBOPACK__imported__a", "__TURBOPACK__imported__b"]
+            .map(|ident| reexport_namespace_args(&reexport_namespace_expr(ident, &exclude)));
+        assert_eq!(
+            calls,
+            [
+                (
+                    "__TURBOPACK__imported__a".to_string(),
+                    vec!["local".to_string()]
+                ),
+                (
+                    "__TURBOPACK__imported__b".to_string(),
+                    vec!["local".to_string()]
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use swc_core::{
+    common::{Span, DUMMY_SP},
+    ecma::ast::{Expr, Ident, ImportDecl, ImportSpecifier, ImportStarAsSpecifier, Stmt},
+    quote, quote_expr,
+};
+
+use super::base::runtime_function;
+use crate::{analyzer::imports::DEFAULT_REMOTE_SCOPE, chunk::RuntimeFunctionNames};
+
+pub(super) fn unsupported_external_message(request: &str) -> String {
+    format!(
+        "The module imports the external {request}, but the chunking context doesn't support \
+         external modules. Loading it throws at runtime."
+    )
+}
+
+pub(super) const NODE_PROTOCOL: &str = "node:";
+
+/// Creates the statement binding the Node.js builtin `request`, e.g.
+/// `node:fs`, to `ident`. The builtin is required without the `node:`
+/// protocol.
+pub(super) fn node_builtin_stmt(ident: &str, request: &str, names: &RuntimeFunctionNames) -> Stmt {
+    let name = request.strip_prefix(NODE_PROTOCOL).unwrap_or(request);
+    quote!(
+        "var $name = $require($builtin);" as Stmt,
+        name = Ident::new(ident.into(), DUMMY_SP),
+        require = runtime_function(&names.require_builtin),
+        builtin: Expr = Expr::Lit(name.into()),
+    )
+}
+
+/// Creates the statement binding the module `module` of the module federation
+/// remote container `container` to `ident`. The module is awaited in the
+/// module body, which makes the importing module async, see
+/// [AsyncModule](crate::references::async_module::AsyncModule). The share
+/// scope is only passed when it isn't the default one.
+pub(super) fn remote_stmt(
+    ident: &str,
+    container: &str,
+    scope: &str,
+    module: &str,
+    names: &RuntimeFunctionNames,
+) -> Stmt {
+    let name = Ident::new(ident.into(), DUMMY_SP);
+    let import = runtime_function(&names.federation_import);
+    let container = Expr::Lit(container.into());
+    let module = Expr::Lit(module.into());
+    if scope == DEFAULT_REMOTE_SCOPE {
+        quote!(
+            "var $name = await $import($container, $module);" as Stmt,
+            name = name,
+            import = import,
+            container: Expr = container,
+            module: Expr = module,
+        )
+    } else {
+        quote!(
+            "var $name = await $import($container, $module, $scope);" as Stmt,
+            name = name,
+            import = import,
+            container: Expr = container,
+            module: Expr = module,
+            scope: Expr = Expr::Lit(scope.into()),
+        )
+    }
+}
+
+/// Creates the statement binding the external `request` to `ident`. It's
+/// loaded with `__turbopack_external_import__` when `import_externals` is set
+/// and with `__turbopack_external_require__` otherwise.
+///
+/// When `optional` is set, `undefined` is bound if the external fails to
+/// load, instead of failing the evaluation of the importing module. A failed
+/// require throws, so it's wrapped in a try/catch, while a failed import
+/// rejects the promise the async module awaits. Externals imported with
+/// import declarations can't be optional.
+///
+/// When `lazy` is set, a required external is bound with
+/// `__turbopack_external_lazy__`, which defers the require until one of its
+/// exports is first accessed. Imported externals are loaded before the
+/// importing module evaluates anyway, and optional externals are required
+/// eagerly so a failure can bind `undefined`, so neither is made lazy.
+pub(super) fn external_stmt(
+    ident: &str,
+    request: &str,
+    import_externals: bool,
+    interop: bool,
+    optional: bool,
+    lazy: bool,
+    names: &RuntimeFunctionNames,
+) -> Stmt {
+    let name = Ident::new(ident.into(), DUMMY_SP);
+    let id = Expr::Lit(request.into());
+    let load = if import_externals {
+        let import = quote_expr!(
+            "$import($id)",
+            import = runtime_function(&names.external_import),
+            id: Expr = id
+        );
+        if optional {
+            quote_expr!("$import.catch(() => undefined)", import: Expr = *import)
+        } else {
+            import
+        }
+    } else if lazy && !optional {
+        quote_expr!(
+            "$lazy($id, $interop)",
+            lazy = runtime_function(&names.external_lazy),
+            id: Expr = id,
+            interop: Expr = Expr::Lit(interop.into())
+        )
+    } else {
+        let require = quote_expr!(
+            "$require($id, $interop)",
+            require = runtime_function(&names.external_require),
+            id: Expr = id,
+            interop: Expr = Expr::Lit(interop.into())
+        );
+        if optional {
+            quote_expr!(
+                "(() => { try { return $require; } catch { return undefined; } })()",
+                require: Expr = *require
+            )
+        } else {
+            require
+        }
+    };
+    quote!("var $name = $load;" as Stmt, name = name, load: Expr = *load)
+}
+
+/// The name of the package the external `request` is loaded from, e.g.
+/// `@scope/pkg` for `@scope/pkg/sub/path`. Relative, absolute and URL-like
+/// requests, e.g. `node:fs`, aren't loaded from a package.
+pub(super) fn external_package_name(request: &str) -> Option<&str> {
+    if request.starts_with('.') || request.starts_with('/') || request.contains(':') {
+        return None;
+    }
+    let mut separators = request.match_indices('/').map(|(index, _)| index);
+    let end = if request.starts_with('@') {
+        separators.nth(1)
+    } else {
+        separators.next()
+    };
+    Some(&request[..end.unwrap_or(request.len())])
+}
+
+/// Whether the external `request` needs the ESM interop when it's required.
+///
+/// Genuine ESM, i.e. `.mjs` files and packages with `"type": "module"` or an
+/// `.mjs` main entry, has its own exports and doesn't need it. CommonJS
+/// externals only provide `module.exports`, which becomes the default export.
+///
+/// When the package has an `exports` field, it takes precedence over `main`,
+/// and the file it exports for the `require` condition decides. Dual packages
+/// export CommonJS there, even if their `import` entry is ESM.
+pub(super) fn external_interop_needed(
+    request: &str,
+    package_json: Option<&serde_json::Value>,
+) -> bool {
+    if request.ends_with(".mjs") {
+        return false;
+    }
+    if request.ends_with(".cjs") {
+        return true;
+    }
+    let Some(package_json) = package_json else {
+        return true;
+    };
+    let type_module = package_json.get("type").and_then(|ty| ty.as_str()) == Some("module");
+    let exports = package_json.get("exports");
+    if let (Some(exports), Some(package_name)) = (exports, external_package_name(request)) {
+        let subpath = format!(".{}", &request[package_name.len()..]);
+        return match required_export(exports, &subpath) {
+            Some(target) if target.ends_with(".mjs") => false,
+            Some(target) if target.ends_with(".cjs") => true,
+            Some(_) => !type_module,
+            // The subpath is only exported for other conditions, e.g.
+            // `import`, so it's ESM.
+            None => false,
+        };
+    }
+    let is_esm = type_module
+        || package_json
+            .get("main")
+            .and_then(|main| main.as_str())
+            .is_some_and(|main| main.ends_with(".mjs"));
+    !is_esm
+}
+
+/// The conditions Node.js matches in the `exports` field of a package when
+/// it's required.
+const REQUIRE_CONDITIONS: [&str; 3] = ["node", "require", "default"];
+
+/// The file the package.json `exports` field maps `subpath` of the package,
+/// e.g. `.` or `./sub`, to when it's required. Subpath patterns aren't
+/// expanded.
+fn required_export<'a>(exports: &'a serde_json::Value, subpath: &str) -> Option<&'a str> {
+    let target = match exports {
+        serde_json::Value::Object(map) if map.keys().any(|key| key.starts_with('.')) => {
+            map.get(subpath)?
+        }
+        exports if subpath == "." => exports,
+        _ => return None,
+    };
+    required_export_target(target)
+}
+
+/// The file of an `exports` field `target` for the [REQUIRE_CONDITIONS]. The
+/// first matching condition and alternative wins.
+fn required_export_target(target: &serde_json::Value) -> Option<&str> {
+    match target {
+        serde_json::Value::String(target) => Some(target),
+        serde_json::Value::Array(targets) => targets.iter().find_map(required_export_target),
+        serde_json::Value::Object(conditions) => conditions
+            .iter()
+            .filter(|(condition, _)| REQUIRE_CONDITIONS.contains(&condition.as_str()))
+            .find_map(|(_, target)| required_export_target(target)),
+        _ => None,
+    }
+}
+
+/// Creates the `import * as ident from "request"` declaration for an ESM
+/// external.
+pub(super) fn esm_external_import_decl(
+    ident: &str,
+    request: &str,
+    span: Option<Span>,
+) -> ImportDecl {
+    ImportDecl {
+        span: span.unwrap_or(DUMMY_SP),
+        specifiers: vec![ImportSpecifier::Namespace(ImportStarAsSpecifier {
+            span: DUMMY_SP,
+            local: Ident::new(ident.into(), DUMMY_SP),
+        })],
+        src: Box::new(request.into()),
+        type_only: false,
+        with: None,
+        phase: Default::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::{ecma::ast::Program, testing::run_test};
+
+    use super::*;
+    use crate::{
+        analyzer::imports::ImportAnnotations,
+        references::esm::{
+            base::tests::{parse, to_js},
+            hoisting::{insert_hoisted_import, insert_hoisted_stmt},
+        },
+    };
+
+    #[test]
+    fn esm_external_imports() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(&cm, "import \"./local\";\nconsole.log(1);");
+            let Program::Module(module) = &mut program else {
+                panic!("expected a module");
+            };
+            insert_hoisted_import(
+                module,
+                esm_external_import_decl("a", "ext-a", None),
+                &Default::default(),
+            );
+            insert_hoisted_import(
+                module,
+                esm_external_import_decl("b", "ext-b", None),
+                &Default::default(),
+            );
+
+            assert_eq!(
+                to_js(&program, &cm),
+                r#"import"./local";import*as a from"ext-a";import*as b from"ext-b";console.log(1);"#
+            );
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn node_builtin_import() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(&cm, "");
+            insert_hoisted_stmt(
+                &mut program,
+                node_builtin_stmt("fs", "node:fs", &Default::default()),
+                &Default::default(),
+            );
+            insert_hoisted_stmt(
+                &mut program,
+                node_builtin_stmt("fsp", "node:fs/promises", &Default::default()),
+                &Default::default(),
+            );
+
+            let js = to_js(&program, &cm);
+            assert!(js.contains(r#"var fs=__turbopack_require_builtin__("fs");"#));
+            assert!(js.contains(r#"var fsp=__turbopack_require_builtin__("fs/promises");"#));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn optional_externals() {
+        run_test(false, |cm, _handler| {
+            let external_js = |annotations: &str, import_externals: bool| {
+                let optional = ImportAnnotations::parse(annotations).unwrap().optional();
+                let mut program = parse(&cm, "");
+                insert_hoisted_stmt(
+                    &mut program,
+                    external_stmt(
+                        "a",
+                        "ext",
+                        import_externals,
+                        true,
+                        optional,
+                        false,
+                        &Default::default(),
+                    ),
+                    &Default::default(),
+                );
+                to_js(&program, &cm)
+            };
+
+            assert!(external_js("{}", false)
+                .contains(r#"var a=__turbopack_external_require__("ext",true);"#));
+            assert!(external_js("{ optional }", false).contains(concat!(
+                r#"var a=(()=>{try{return __turbopack_external_require__("ext",true);}"#,
+                r#"catch{return undefined;}})();"#,
+            )));
+            assert!(external_js("{ optional: false }", false)
+                .contains(r#"var a=__turbopack_external_require__("ext",true);"#));
+
+            assert!(
+                external_js("{}", true).contains(r#"var a=__turbopack_external_import__("ext");"#)
+            );
+            assert!(external_js("{ optional }", true)
+                .contains(r#"var a=__turbopack_external_import__("ext").catch(()=>undefined);"#));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn lazy_externals() {
+        run_test(false, |cm, _handler| {
+            let external_js = |annotations: &str, import_externals: bool| {
+                let annotations = ImportAnnotations::parse(annotations).unwrap();
+                let mut program = parse(&cm, "");
+                insert_hoisted_stmt(
+                    &mut program,
+                    external_stmt(
+                        "a",
+                        "ext",
+                        import_externals,
+                        true,
+                        annotations.optional(),
+                        annotations.lazy(),
+                        &Default::default(),
+                    ),
+                    &Default::default(),
+                );
+                to_js(&program, &cm)
+            };
+
+            assert!(external_js("{ lazy }", false)
+                .contains(r#"var a=__turbopack_external_lazy__("ext",true);"#));
+            assert!(external_js("{ lazy: true }", false)
+                .contains(r#"var a=__turbopack_external_lazy__("ext",true);"#));
+
+            for (annotations, import_externals) in [
+                ("{}", false),
+                ("{ lazy: false }", false),
+                ("{ lazy, optional }", false),
+                ("{ lazy }", true),
+            ] {
+                assert!(!external_js(annotations, import_externals)
+                    .contains("__turbopack_external_lazy__"));
+            }
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn external_interop() {
+        assert_eq!(external_package_name("pkg/sub/path"), Some("pkg"));
+        assert_eq!(external_package_name("@scope/pkg/sub"), Some("@scope/pkg"));
+        assert_eq!(external_package_name("node:fs"), None);
+        assert_eq!(external_package_name("./local"), None);
+
+        // A CommonJS package only provides `module.exports`, which the interop
+        // exposes as the default export.
+        let cjs = serde_json::json!({ "name": "cjs", "main": "index.js" });
+        assert!(external_interop_needed("cjs", Some(&cjs)));
+        assert!(external_interop_needed("fs", None));
+
+        // ESM packages have their own exports, even without a default one.
+        let esm = serde_json::json!({ "name": "esm", "type": "module" });
+        assert!(!external_interop_needed("esm", Some(&esm)));
+        let mjs_main = serde_json::json!({ "name": "esm", "main": "index.mjs" });
+        assert!(!external_interop_needed("esm", Some(&mjs_main)));
+        assert!(!external_interop_needed("cjs/esm.mjs", Some(&cjs)));
+        assert!(external_interop_needed("esm/cjs.cjs", Some(&esm)));
+
+        // Dual packages are required through the `require` condition of their
+        // `exports`, which takes precedence over `type` and `main`.
+        let dual = serde_json::json!({
+            "name": "dual",
+            "type": "module",
+            "main": "index.mjs",
+            "exports": {
+                ".": { "import": "./index.mjs", "require": "./index.cjs" },
+                "./sub": { "import": "./sub.js", "default": "./sub.cjs" },
+                "./esm-only": { "import": "./esm-only.js" },
+            },
+        });
+        assert!(external_interop_needed("dual", Some(&dual)));
+        assert!(external_interop_needed("dual/sub", Some(&dual)));
+        assert!(!external_interop_needed("dual/esm-only", Some(&dual)));
+        let sugar = serde_json::json!({
+            "name": "sugar",
+            "exports": { "import": "./index.mjs", "node": "./index.js" },
+        });
+        assert!(external_interop_needed("sugar", Some(&sugar)));
+        let esm_exports = serde_json::json!({
+            "name": "esm",
+            "type": "module",
+            "exports": "./index.js",
+        });
+        assert!(!external_interop_needed("esm", Some(&esm_exports)));
+    }
+}
@@ -0,0 +1,122 @@
+use anyhow::Result;
+use turbo_tasks::Vc;
+use turbopack_core::{
+    environment::{Environment, NODE_BUILTIN_MODULES},
+    ident::AssetIdent,
+    issue::{analyze::AnalyzeIssue, IssueExt, IssueSeverity, StyledString},
+    resolve::{origin::ResolveOrigin, ModuleResolveResultItem},
+};
+
+use super::external::NODE_PROTOCOL;
+use crate::EcmascriptModuleAsset;
+
+/// The Node.js builtin `request` names, if any. Requests with the `node:`
+/// protocol always name a builtin.
+fn node_builtin_name(request: &str) -> Option<&str> {
+    match request.strip_prefix(NODE_PROTOCOL) {
+        Some(name) => Some(name),
+        None => NODE_BUILTIN_MODULES.contains(&request).then_some(request),
+    }
+}
+
+/// The external `requests` that name a Node.js builtin, which isn't among the
+/// `builtin_modules` of the environment. Loading them likely fails at
+/// runtime. Every request is listed once.
+fn likely_broken_externals<'a>(
+    requests: impl IntoIterator<Item = &'a str>,
+    builtin_modules: &[String],
+) -> Vec<&'a str> {
+    let mut broken = Vec::new();
+    for request in requests {
+        let Some(name) = node_builtin_name(request) else {
+            continue;
+        };
+        if !builtin_modules.iter().any(|module| module == name) && !broken.contains(&request) {
+            broken.push(request);
+        }
+    }
+    broken
+}
+
+fn likely_broken_externals_message(externals: &[&str]) -> String {
+    format!(
+        "The module imports {}, which the target environment likely doesn't provide. They are \
+         loaded as externals, so the build succeeds, but loading them will probably fail at \
+         runtime.",
+        externals.join(", ")
+    )
+}
+
+/// Checks the externals referenced by `origin` against the builtin modules of
+/// `environment`, see [Environment::builtin_modules], and emits a warning
+/// listing the ones that are likely broken. This doesn't fail the build.
+/// Returns the listed externals.
+#[turbo_tasks::function]
+pub(crate) async fn check_externals_for_environment(
+    origin: Vc<Box<dyn ResolveOrigin>>,
+    environment: Vc<Environment>,
+) -> Result<Vc<Vec<String>>> {
+    let Some(module) = Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(origin).await? else {
+        return Ok(Vc::cell(Vec::new()));
+    };
+    let mut requests = Vec::new();
+    for &reference in module.analyze().await?.references.await?.iter() {
+        for item in reference.resolve_reference().await?.primary.values() {
+            if let ModuleResolveResultItem::OriginalReferenceTypeExternal(request) = item {
+                requests.push(request.clone());
+            }
+        }
+    }
+    let builtin_modules = environment.builtin_modules().await?;
+    let broken = likely_broken_externals(requests.iter().map(String::as_str), &builtin_modules);
+    if !broken.is_empty() {
+        AnalyzeIssue {
+            code: None,
+            category: Vc::cell("code generation".to_string()),
+            message: StyledString::Text(likely_broken_externals_message(&broken)).cell(),
+            source_ident: AssetIdent::from_path(origin.origin_path()),
+            severity: IssueSeverity::Warning.into(),
+            source: None,
+            title: Vc::cell("externals not provided by the environment".to_string()),
+        }
+        .cell()
+        .emit();
+    }
+    Ok(Vc::cell(broken.into_iter().map(str::to_string).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_builtin_in_browser() {
+        // Browsers don't provide any Node.js builtins.
+        assert_eq!(
+            likely_broken_externals(["node:fs", "react", "node:fs"], &[]),
+            vec!["node:fs"]
+        );
+        assert_eq!(likely_broken_externals(["fs"], &[]), vec!["fs"]);
+        assert_eq!(
+            likely_broken_externals_message(&["node:fs", "path"]),
+            "The module imports node:fs, path, which the target environment likely doesn't \
+             provide. They are loaded as externals, so the build succeeds, but loading them will \
+             probably fail at runtime."
+        );
+    }
+
+    #[test]
+    fn node_builtin_in_node() {
+        let builtin_modules = NODE_BUILTIN_MODULES.map(str::to_string);
+        assert!(likely_broken_externals(["node:fs", "fs", "react"], &builtin_modules).is_empty());
+    }
+
+    #[test]
+    fn node_builtin_in_edge_worker() {
+        let builtin_modules = ["buffer".to_string()];
+        assert_eq!(
+            likely_broken_externals(["node:buffer", "node:fs"], &builtin_modules),
+            vec!["node:fs"]
+        );
+    }
+}
@@ -0,0 +1,698 @@
+use swc_core::{
+    common::{
+        comments::{Comment, CommentKind},
+        BytePos, EqIgnoreSpan, FileName, SourceMap, Span, DUMMY_SP,
+    },
+    ecma::ast::{
+        self, Decl, Expr, ExprStmt, ImportDecl, Lit, ModuleDecl, ModuleItem, Program, Script, Stmt,
+    },
+};
+use turbopack_core::source_map::GENERATED_CODE_SOURCE_NAME;
+
+use crate::chunk::HoistingMarkers;
+
+/// Inserts `decl` after the leading import declarations of `module`. The
+/// engine evaluates imported modules before the module body, so the import is
+/// always hoisted, but the order in which externals are evaluated is kept.
+pub(super) fn insert_hoisted_import(
+    module: &mut ast::Module,
+    decl: ImportDecl,
+    markers: &HoistingMarkers,
+) {
+    let prologue = directive_prologue_len(
+        module.body.iter().map_while(|item| match item {
+            ModuleItem::Stmt(stmt) => Some(stmt),
+            ModuleItem::ModuleDecl(_) => None,
+        }),
+        markers,
+    );
+    let pos = module.body[prologue..]
+        .iter()
+        .position(|item| !matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_))))
+        .map_or(module.body.len(), |pos| prologue + pos);
+    module
+        .body
+        .insert(pos, ModuleItem::ModuleDecl(ModuleDecl::Import(decl)));
+}
+
+/// A region of hoisted statements at the start of a module. Every region ends
+/// with a marker of its own, see [HoistingMarkers], and the regions are in the
+/// order of the variants, independent of the order statements are inserted
+/// in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum HoistingAnchor {
+    /// Setup that has to run before the imports, e.g. interop helpers or
+    /// polyfills.
+    Prologue,
+    /// The import bindings of the module.
+    #[default]
+    Imports,
+}
+
+impl HoistingAnchor {
+    fn marker(self, markers: &HoistingMarkers) -> &str {
+        match self {
+            HoistingAnchor::Prologue => &markers.prologue,
+            HoistingAnchor::Imports => &markers.imports,
+        }
+    }
+
+    fn marker_stmt(self, markers: &HoistingMarkers) -> Stmt {
+        Stmt::Expr(ExprStmt {
+            expr: Box::new(Expr::Lit(Lit::Str(self.marker(markers).into()))),
+            span: DUMMY_SP,
+        })
+    }
+}
+
+/// The anchor `stmt` is the marker of, if any. Markers of other chunking
+/// contexts are ordinary statements.
+pub(super) fn hoisting_anchor(stmt: &Stmt, markers: &HoistingMarkers) -> Option<HoistingAnchor> {
+    let Stmt::Expr(ExprStmt {
+        expr: box Expr::Lit(Lit::Str(s)),
+        ..
+    }) = stmt
+    else {
+        return None;
+    };
+    [HoistingAnchor::Prologue, HoistingAnchor::Imports]
+        .into_iter()
+        .find(|anchor| &*s.value == anchor.marker(markers))
+}
+
+/// Returns the number of directives, like `"use strict"`, at the start of
+/// `stmts`. Hoisted statements have to be inserted after them, as directives
+/// are only recognized at the very beginning of a program.
+fn directive_prologue_len<'a>(
+    stmts: impl Iterator<Item = &'a Stmt>,
+    markers: &HoistingMarkers,
+) -> usize {
+    stmts
+        .take_while(|stmt| {
+            matches!(
+                stmt,
+                Stmt::Expr(ExprStmt {
+                    expr: box Expr::Lit(Lit::Str(_)),
+                    ..
+                })
+            ) && hoisting_anchor(stmt, markers).is_none()
+                && import_comment(stmt).is_none()
+        })
+        .count()
+}
+
+/// The position of the marker of `anchor` in `stmts`, or, when its region
+/// doesn't exist yet, the position to insert the region at: after the regions
+/// of earlier anchors, or after the directive prologue. Module declarations
+/// are [None].
+fn anchor_position(
+    stmts: &[Option<&Stmt>],
+    anchor: HoistingAnchor,
+    markers: &HoistingMarkers,
+) -> Result<usize, usize> {
+    let anchor_of = |stmt: &Option<&Stmt>| stmt.and_then(|stmt| hoisting_anchor(stmt, markers));
+    if let Some(pos) = stmts
+        .iter()
+        .position(|stmt| anchor_of(stmt) == Some(anchor))
+    {
+        return Ok(pos);
+    }
+    match stmts
+        .iter()
+        .rposition(|stmt| anchor_of(stmt).is_some_and(|other| other < anchor))
+    {
+        Some(pos) => Err(pos + 1),
+        None => Err(directive_prologue_len(
+            stmts.iter().map_while(|stmt| *stmt),
+            markers,
+        )),
+    }
+}
+
+/// Whether `stmt` is already hoisted in the region ending at the marker at
+/// `pos`.
+fn is_hoisted_in_region(
+    stmts: &[Option<&Stmt>],
+    pos: usize,
+    stmt: &Stmt,
+    markers: &HoistingMarkers,
+) -> bool {
+    let start = stmts[..pos]
+        .iter()
+        .rposition(|item| item.is_some_and(|item| hoisting_anchor(item, markers).is_some()))
+        .map_or(0, |start| start + 1);
+    stmts[start..pos]
+        .iter()
+        .flatten()
+        .any(|item| stmt.eq_ignore_span(item))
+}
+
+/// Maps the hoisted statements without a source location to the
+/// [GENERATED_CODE_SOURCE_NAME] source, which puts them on the ignore list of
+/// the generated source map. This keeps runtime imports and the async
+/// dependency handler out of user-facing stack traces.
+pub(crate) fn ignore_list_hoisted_stmts(
+    program: &mut Program,
+    files_map: &SourceMap,
+    markers: &HoistingMarkers,
+) {
+    let mut stmts = match program {
+        Program::Module(ast::Module { body, .. }) => body
+            .iter_mut()
+            .map_while(|item| match item {
+                ModuleItem::Stmt(stmt) => Some(stmt),
+                ModuleItem::ModuleDecl(_) => None,
+            })
+            .collect::<Vec<_>>(),
+        Program::Script(Script { body, .. }) => body.iter_mut().collect(),
+    };
+    let Some(pos) = stmts
+        .iter()
+        .rposition(|stmt| hoisting_anchor(stmt, markers).is_some())
+    else {
+        return;
+    };
+
+    let file = files_map.new_source_file(
+        FileName::Custom(GENERATED_CODE_SOURCE_NAME.to_string()),
+        "/* generated by turbopack */".to_string(),
+    );
+    let span = Span::new(file.start_pos, file.start_pos, Default::default());
+    for stmt in stmts.drain(..=pos) {
+        match stmt {
+            Stmt::Decl(Decl::Var(var)) if var.span.is_dummy() => var.span = span,
+            Stmt::Expr(expr) if expr.span.is_dummy() => {
+                expr.span = span;
+                // The emitter maps expression statements by their expression.
+                match &mut *expr.expr {
+                    Expr::Lit(Lit::Str(str)) if str.span.is_dummy() => str.span = span,
+                    Expr::Call(call) if call.span.is_dummy() => call.span = span,
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Prefix of the string literal statements that carry the text of a debug
+/// comment for the next statement, see [take_import_comments].
+const IMPORT_COMMENT_MARKER: &str = "__TURBOPACK__import__comment__";
+
+/// The text of the debug comment describing an import of `request` by the
+/// module at `origin`. A `*/` in either would end the comment early, so it's
+/// escaped.
+pub(super) fn import_comment_text(request: &str, origin: &str) -> String {
+    format!(" import {request} from {origin} ").replace("*/", "*\\/")
+}
+
+/// Creates a statement that [take_import_comments] replaces with a comment
+/// containing `text` in front of the next statement.
+pub(super) fn import_comment_stmt(text: &str) -> Stmt {
+    Stmt::Expr(ExprStmt {
+        expr: Box::new(Expr::Lit(Lit::Str(
+            format!("{IMPORT_COMMENT_MARKER}{text}").into(),
+        ))),
+        span: DUMMY_SP,
+    })
+}
+
+/// The comment text `stmt` carries, if it's created by [import_comment_stmt].
+pub(super) fn import_comment(stmt: &Stmt) -> Option<&str> {
+    let Stmt::Expr(ExprStmt {
+        expr: box Expr::Lit(Lit::Str(s)),
+        ..
+    }) = stmt
+    else {
+        return None;
+    };
+    s.value.strip_prefix(IMPORT_COMMENT_MARKER)
+}
+
+/// Removes the statements created by [import_comment_stmt] and returns the
+/// comments they carry, as leading comments of the statements following
+/// them. Comments are attached to positions, so import bindings without a
+/// source location get a location of their own in the
+/// [GENERATED_CODE_SOURCE_NAME] source.
+pub(crate) fn take_import_comments(
+    program: &mut Program,
+    files_map: &SourceMap,
+) -> Vec<(BytePos, Comment)> {
+    let mut comments = Vec::new();
+    let mut pending = None;
+    let mut take = |stmt: &mut Stmt| {
+        if let Some(text) = import_comment(stmt) {
+            pending = Some(text.to_string());
+            return false;
+        }
+        if let Some(text) = pending.take() {
+            if let Stmt::Decl(Decl::Var(var)) = stmt {
+                if var.span.is_dummy() {
+                    let file = files_map.new_source_file(
+                        FileName::Custom(GENERATED_CODE_SOURCE_NAME.to_string()),
+                        "/* generated by turbopack */".to_string(),
+                    );
+                    var.span = Span::new(file.start_pos, file.start_pos, Default::default());
+                }
+                comments.push((
+                    var.span.lo,
+                    Comment {
+                        kind: CommentKind::Block,
+                        span: DUMMY_SP,
+                        text: text.into(),
+                    },
+                ));
+            }
+        }
+        true
+    };
+    match program {
+        Program::Module(ast::Module { body, .. }) => body.retain_mut(|item| match item {
+            ModuleItem::Stmt(stmt) => take(stmt),
+            ModuleItem::ModuleDecl(_) => true,
+        }),
+        Program::Script(Script { body, .. }) => body.retain_mut(|stmt| take(stmt)),
+    }
+    comments
+}
+
+/// Inserts `stmt` before the hoisting location, after all previously hoisted
+/// statements. See [insert_hoisted_stmt_at].
+pub(crate) fn insert_hoisted_stmt(program: &mut Program, stmt: Stmt, markers: &HoistingMarkers) {
+    insert_hoisted_stmt_at(program, stmt, HoistingAnchor::Imports, markers)
+}
+
+/// Inserts `stmt` at the end of the region of `anchor`, after all statements
+/// previously hoisted into it. A statement that's already in the region isn't
+/// inserted again.
+///
+/// Hoisted statements keep their insertion order, which is the order of the
+/// module's references, as code generation is collected in that order. This
+/// must not be sorted: imports have to be evaluated in source order.
+pub(crate) fn insert_hoisted_stmt_at(
+    program: &mut Program,
+    stmt: Stmt,
+    anchor: HoistingAnchor,
+    markers: &HoistingMarkers,
+) {
+    match program {
+        Program::Module(ast::Module { body, .. }) => {
+            let position = {
+                let stmts = module_stmts(body);
+                anchor_position(&stmts, anchor, markers)
+                    .map(|pos| (pos, is_hoisted_in_region(&stmts, pos, &stmt, markers)))
+            };
+            match position {
+                Ok((_, true)) => {}
+                Ok((pos, false)) => body.insert(pos, ModuleItem::Stmt(stmt)),
+                Err(pos) => {
+                    body.splice(
+                        pos..pos,
+                        [
+                            ModuleItem::Stmt(stmt),
+                            ModuleItem::Stmt(anchor.marker_stmt(markers)),
+                        ],
+                    );
+                }
+            }
+        }
+        Program::Script(Script { body, .. }) => {
+            let position = {
+                let stmts = body.iter().map(Some).collect::<Vec<_>>();
+                anchor_position(&stmts, anchor, markers)
+                    .map(|pos| (pos, is_hoisted_in_region(&stmts, pos, &stmt, markers)))
+            };
+            match position {
+                Ok((_, true)) => {}
+                Ok((pos, false)) => body.insert(pos, stmt),
+                Err(pos) => {
+                    body.splice(pos..pos, [stmt, anchor.marker_stmt(markers)]);
+                }
+            }
+        }
+    }
+}
+
+/// The statements of a module body, with [None] for module declarations.
+fn module_stmts(body: &[ModuleItem]) -> Vec<Option<&Stmt>> {
+    body.iter()
+        .map(|item| match item {
+            ModuleItem::Stmt(stmt) => Some(stmt),
+            ModuleItem::ModuleDecl(_) => None,
+        })
+        .collect()
+}
+
+/// Inserts `stmts` directly after the hoisting location, so they run after
+/// all hoisted statements, including ones that are only hoisted later on, but
+/// before any of the module's own top-level code.
+pub(crate) fn insert_stmts_after_hoisting_location(
+    program: &mut Program,
+    stmts: Vec<Stmt>,
+    markers: &HoistingMarkers,
+) {
+    let anchor = HoistingAnchor::Imports;
+    match program {
+        Program::Module(ast::Module { body, .. }) => {
+            let position = anchor_position(&module_stmts(body), anchor, markers);
+            let pos = match position {
+                Ok(pos) => pos,
+                Err(pos) => {
+                    body.insert(pos, ModuleItem::Stmt(anchor.marker_stmt(markers)));
+                    pos
+                }
+            };
+            body.splice(pos + 1..pos + 1, stmts.into_iter().map(ModuleItem::Stmt));
+        }
+        Program::Script(Script { body, .. }) => {
+            let position =
+                anchor_position(&body.iter().map(Some).collect::<Vec<_>>(), anchor, markers);
+            let pos = match position {
+                Ok(pos) => pos,
+                Err(pos) => {
+                    body.insert(pos, anchor.marker_stmt(markers));
+                    pos
+                }
+            };
+            body.splice(pos + 1..pos + 1, stmts);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::{
+        base::SwcComments,
+        common::comments::Comments,
+        ecma::codegen::{text_writer::JsWriter, Emitter},
+        quote,
+        testing::run_test,
+    };
+
+    use super::*;
+    use crate::{
+        references::esm::{
+            base::{
+                esm_import_stmt,
+                tests::{parse, to_js},
+            },
+            external::esm_external_import_decl,
+        },
+        swc_comments::ImmutableComments,
+    };
+
+    #[test]
+    fn ignore_lists_hoisted_stmts() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(&cm, "console.log(1);");
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt(
+                    "a",
+                    "./a.js".into(),
+                    None,
+                    false,
+                    false,
+                    None,
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+            ignore_list_hoisted_stmts(&mut program, &cm, &Default::default());
+
+            let mut bytes = Vec::new();
+            let mut mappings = Vec::new();
+            let mut emitter = Emitter {
+                cfg: Default::default(),
+                cm: cm.clone(),
+                comments: None,
+                wr: JsWriter::new(cm.clone(), "\n", &mut bytes, Some(&mut mappings)),
+            };
+            emitter.emit_program(&program).unwrap();
+            let map = cm.build_source_map(&mappings);
+
+            let source_of_line = |line| {
+                map.lookup_token(line, 0)
+                    .filter(|token| token.get_dst_line() == line)
+                    .and_then(|token| token.get_source())
+            };
+            let generated = FileName::Custom(GENERATED_CODE_SOURCE_NAME.to_string()).to_string();
+            // line 0 is the hoisted import, line 1 the hoisting location
+            assert_eq!(source_of_line(0), Some(&*generated));
+            assert_eq!(source_of_line(1), Some(&*generated));
+            assert_ne!(source_of_line(2), Some(&*generated));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn hoisted_stmts_after_script_directives() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(&cm, "\"use strict\";\nlocal();");
+            assert!(matches!(program, Program::Script(_)));
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt(
+                    "a",
+                    "./a.js".into(),
+                    None,
+                    false,
+                    false,
+                    None,
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+
+            let js = to_js(&program, &cm);
+            assert!(js.starts_with(r#""use strict";var a=__turbopack_import__("./a.js");"#));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn hoisted_stmts_after_module_directives() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(
+                &cm,
+                "\"use client\";\n'use strict';\nimport \"./local\";\nlocal();",
+            );
+            assert!(matches!(program, Program::Module(_)));
+            insert_hoisted_stmt(
+                &mut program,
+                esm_import_stmt(
+                    "a",
+                    "./a.js".into(),
+                    None,
+                    false,
+                    false,
+                    None,
+                    &Default::default(),
+                ),
+                &Default::default(),
+            );
+            let Program::Module(module) = &mut program else {
+                unreachable!();
+            };
+            insert_hoisted_import(
+                module,
+                esm_external_import_decl("b", "ext-b", None),
+                &Default::default(),
+            );
+
+            let js = to_js(&program, &cm);
+            assert!(js.starts_with(concat!(
+                r#""use client";"use strict";"#,
+                r#"import*as b from"ext-b";var a=__turbopack_import__("./a.js");"#,
+            )));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn hoisting_regions_keep_their_order() {
+        for prologue_first in [true, false] {
+            run_test(false, |cm, _handler| {
+                let mut program = parse(&cm, "\"use strict\";\nimport \"./local\";\nlocal();");
+                let import = || {
+                    esm_import_stmt(
+                        "a",
+                        "./a.js".into(),
+                        None,
+                        false,
+                        false,
+                        None,
+                        &Default::default(),
+                    )
+                };
+                let setup = || quote!("var interop = {};" as Stmt);
+                let markers = HoistingMarkers::default();
+                if prologue_first {
+                    insert_hoisted_stmt_at(
+                        &mut program,
+                        setup(),
+                        HoistingAnchor::Prologue,
+                        &markers,
+                    );
+                    insert_hoisted_stmt(&mut program, import(), &markers);
+                } else {
+                    insert_hoisted_stmt(&mut program, import(), &markers);
+                    insert_hoisted_stmt_at(
+                        &mut program,
+                        setup(),
+                        HoistingAnchor::Prologue,
+                        &markers,
+                    );
+                }
+                insert_hoisted_stmt_at(&mut program, setup(), HoistingAnchor::Prologue, &markers);
+
+                let js = to_js(&program, &cm);
+                let setup = js.find("var interop={}").unwrap();
+                let prologue = js.find(&markers.prologue).unwrap();
+                let import = js.find(r#"var a=__turbopack_import__("./a.js")"#).unwrap();
+                let imports = js.find(&markers.imports).unwrap();
+                assert!(js.starts_with(r#""use strict";"#));
+                assert!(setup < prologue);
+                assert!(prologue < import);
+                assert!(import < imports);
+                assert_eq!(js.matches("var interop={}").count(), 1);
+
+                Ok(())
+            })
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn import_debug_comments() {
+        run_test(false, |cm, _handler| {
+            let js = |debug: bool| {
+                let mut program = parse(&cm, "\"use strict\";\nlocal();");
+                if debug {
+                    insert_hoisted_stmt(
+                        &mut program,
+                        import_comment_stmt(&import_comment_text("./foo", "app/page.js")),
+                        &Default::default(),
+                    );
+                }
+                insert_hoisted_stmt(
+                    &mut program,
+                    esm_import_stmt(
+                        "foo",
+                        "./foo.js".into(),
+                        None,
+                        false,
+                        false,
+                        None,
+                        &Default::default(),
+                    ),
+                    &Default::default(),
+                );
+                // Setup hoisted later on stays in front of the comment.
+                insert_hoisted_stmt_at(
+                    &mut program,
+                    quote!("var interop = {};" as Stmt),
+                    HoistingAnchor::Prologue,
+                    &Default::default(),
+                );
+
+                let import_comments = take_import_comments(&mut program, &cm);
+                let comments = ImmutableComments::new(SwcComments::default());
+                let comments = comments.consumable();
+                for (pos, comment) in import_comments {
+                    comments.add_leading(pos, comment);
+                }
+                let mut bytes = Vec::new();
+                let mut emitter = Emitter {
+                    cfg: Default::default(),
+                    cm: cm.clone(),
+                    comments: Some(&comments),
+                    wr: JsWriter::new(cm.clone(), "\n", &mut bytes, None),
+                };
+                emitter.emit_program(&program).unwrap();
+                String::from_utf8(bytes).unwrap()
+            };
+
+            let debug = js(true);
+            let comment = debug.find("/* import ./foo from app/page.js */").unwrap();
+            assert!(debug.find("var interop").unwrap() < comment);
+            assert!(comment < debug.find("var foo = __turbopack_import__").unwrap());
+            assert!(!debug.contains(IMPORT_COMMENT_MARKER));
+
+            let release = js(false);
+            assert!(!release.contains("/*"));
+            assert!(release.contains("var foo = __turbopack_import__"));
+
+            // The comment can't be ended by the request.
+            assert_eq!(
+                import_comment_text("./a*/b.js", "app/*/page.js"),
+                " import ./a*\\/b.js from app/*\\/page.js "
+            );
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn hoisting_markers_of_other_contexts_are_ignored() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(&cm, "local();");
+            let first = HoistingMarkers::new("__FIRST__");
+            let second = HoistingMarkers::new("__SECOND__");
+            assert_ne!(first.imports, second.imports);
+
+            insert_hoisted_stmt(&mut program, quote!("first(1);" as Stmt), &first);
+            insert_hoisted_stmt(&mut program, quote!("second(1);" as Stmt), &second);
+            insert_hoisted_stmt(&mut program, quote!("first(2);" as Stmt), &first);
+            insert_hoisted_stmt(&mut program, quote!("second(2);" as Stmt), &second);
+
+            // Each context appends to its own region and treats the marker of
+            // the other one like any other statement.
+            let js = to_js(&program, &cm);
+            for markers in [&first, &second] {
+                assert_eq!(js.matches(&markers.imports).count(), 1);
+            }
+            let find = |s: &str| js.find(s).unwrap();
+            assert!(find("first(1)") < find("first(2)"));
+            assert!(find("first(2)") < find(&first.imports));
+            assert!(find("second(1)") < find("second(2)"));
+            assert!(find("second(2)") < find(&second.imports));
+            assert!(find(&first.imports) < find("local()"));
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn dedupes_hoisted_stmts_in_scripts() {
+        run_test(false, |cm, _handler| {
+            let mut program = parse(&cm, "local();");
+            assert!(matches!(program, Program::Script(_)));
+            let stmt = esm_import_stmt(
+                "a",
+                "./a.js".into(),
+                None,
+                false,
+                false,
+                None,
+                &Default::default(),
+            );
+            insert_hoisted_stmt(&mut program, stmt.clone(), &Default::default());
+            insert_hoisted_stmt(&mut program, stmt, &Default::default());
+
+            let js = to_js(&program, &cm);
+            assert_eq!(js.matches("__turbopack_import__").count(), 1);
+
+            Ok(())
+        })
+        .unwrap();
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use turbo_tasks::{CollectiblesSource, TryJoinIterExt, Vc};
+use turbopack_core::diagnostics::{
+    Diagnostic, DiagnosticContextExt, DiagnosticExt, DiagnosticPayload, PlainDiagnostic,
+};
+
+const GENERATED_IMPORT_CATEGORY: &str = "metrics";
+const GENERATED_IMPORT_NAME: &str = "generated import";
+const KIND_KEY: &str = "kind";
+
+/// The kind of code generated for an import of an
+/// [EsmAssetReference](super::EsmAssetReference), by the referenced asset and
+/// how it's chunked.
+#[turbo_tasks::value(serialization = "auto_for_input")]
+#[derive(Debug, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum GeneratedImportKind {
+    /// A module in the same chunk group, imported with
+    /// `__turbopack_import__`.
+    Internal,
+    /// A module in a separate chunk group, bound to its async loader.
+    CrossChunk,
+    /// An external loaded with `__turbopack_external_require__`.
+    ExternalRequire,
+    /// An external loaded with an ESM import, either an import declaration or
+    /// `__turbopack_external_import__`.
+    ExternalImport,
+    /// A Node.js builtin, required by its name.
+    NodeBuiltin,
+    /// A `data:` URL module evaluated inline.
+    DataUrl,
+    /// A JSON module inlined as an object literal.
+    InlineJson,
+    /// A module of a module federation remote container, awaited with
+    /// `__turbopack_federation_import__`.
+    Remote,
+    /// An ignored module, bound to an empty object.
+    Ignored,
+    /// An unresolvable request, or an external the chunking context can't
+    /// load, replaced with a throw.
+    Unresolvable,
+}
+
+impl GeneratedImportKind {
+    const ALL: [Self; 10] = [
+        Self::Internal,
+        Self::CrossChunk,
+        Self::ExternalRequire,
+        Self::ExternalImport,
+        Self::NodeBuiltin,
+        Self::DataUrl,
+        Self::InlineJson,
+        Self::Remote,
+        Self::Ignored,
+        Self::Unresolvable,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Internal => "internal",
+            Self::CrossChunk => "cross chunk",
+            Self::ExternalRequire => "external require",
+            Self::ExternalImport => "external import",
+            Self::NodeBuiltin => "node builtin",
+            Self::DataUrl => "data url",
+            Self::InlineJson => "inline json",
+            Self::Remote => "remote",
+            Self::Ignored => "ignored",
+            Self::Unresolvable => "unresolvable",
+        }
+    }
+
+    fn from_str(kind: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.as_str() == kind)
+    }
+}
+
+/// Emitted as a collectible for every import code generation generates, see
+/// [GeneratedImportCounts::collect].
+#[turbo_tasks::value(shared)]
+struct GeneratedImport {
+    kind: GeneratedImportKind,
+}
+
+#[turbo_tasks::value_impl]
+impl Diagnostic for GeneratedImport {
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell(GENERATED_IMPORT_CATEGORY.to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn name(&self) -> Vc<String> {
+        Vc::cell(GENERATED_IMPORT_NAME.to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn payload(&self) -> Vc<DiagnosticPayload> {
+        Vc::cell(HashMap::from([(
+            KIND_KEY.to_string(),
+            self.kind.as_str().to_string(),
+        )]))
+    }
+}
+
+/// Records that an import of `kind` was generated. This doesn't change the
+/// generated code.
+pub(crate) fn emit_generated_import(kind: GeneratedImportKind) {
+    GeneratedImport { kind }.cell().emit();
+}
+
+/// The number of generated imports per [GeneratedImportKind].
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GeneratedImportCounts {
+    pub internal: usize,
+    pub cross_chunk: usize,
+    pub external_require: usize,
+    pub external_import: usize,
+    pub node_builtin: usize,
+    pub data_url: usize,
+    pub inline_json: usize,
+    pub remote: usize,
+    pub ignored: usize,
+    pub unresolvable: usize,
+}
+
+impl GeneratedImportCounts {
+    /// Sums the imports generated by `source` and everything it depends on,
+    /// e.g. the output assets of a build.
+    pub async fn collect(source: impl CollectiblesSource + Copy + Send) -> Result<Self> {
+        let diagnostics = source
+            .peek_diagnostics()
+            .await?
+            .diagnostics
+            .iter()
+            .map(|diagnostic| async move { diagnostic.into_plain().await })
+            .try_join()
+            .await?;
+        Ok(Self::from_diagnostics(
+            diagnostics.iter().map(|diagnostic| &**diagnostic),
+        ))
+    }
+
+    /// Counts the generated imports among `diagnostics`, ignoring other
+    /// diagnostics.
+    fn from_diagnostics<'a>(diagnostics: impl IntoIterator<Item = &'a PlainDiagnostic>) -> Self {
+        let mut counts = Self::default();
+        for diagnostic in diagnostics {
+            if diagnostic.category != GENERATED_IMPORT_CATEGORY
+                || diagnostic.name != GENERATED_IMPORT_NAME
+            {
+                continue;
+            }
+            if let Some(kind) = diagnostic
+                .payload
+                .get(KIND_KEY)
+                .and_then(|kind| GeneratedImportKind::from_str(kind))
+            {
+                counts.add(kind);
+            }
+        }
+        counts
+    }
+
+    fn add(&mut self, kind: GeneratedImportKind) {
+        match kind {
+            GeneratedImportKind::Internal => self.internal += 1,
+            GeneratedImportKind::CrossChunk => self.cross_chunk += 1,
+            GeneratedImportKind::ExternalRequire => self.external_require += 1,
+            GeneratedImportKind::ExternalImport => self.external_import += 1,
+            GeneratedImportKind::NodeBuiltin => self.node_builtin += 1,
+            GeneratedImportKind::DataUrl => self.data_url += 1,
+            GeneratedImportKind::InlineJson => self.inline_json += 1,
+            GeneratedImportKind::Remote => self.remote += 1,
+            GeneratedImportKind::Ignored => self.ignored += 1,
+            GeneratedImportKind::Unresolvable => self.unresolvable += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generated_import(kind: GeneratedImportKind) -> PlainDiagnostic {
+        PlainDiagnostic {
+            category: GENERATED_IMPORT_CATEGORY.to_string(),
+            name: GENERATED_IMPORT_NAME.to_string(),
+            payload: HashMap::from([(KIND_KEY.to_string(), kind.as_str().to_string())]),
+        }
+    }
+
+    #[test]
+    fn counts_generated_imports_per_kind() {
+        let diagnostics = [
+            generated_import(GeneratedImportKind::Internal),
+            generated_import(GeneratedImportKind::Internal),
+            generated_import(GeneratedImportKind::CrossChunk),
+            generated_import(GeneratedImportKind::ExternalRequire),
+            generated_import(GeneratedImportKind::ExternalImport),
+            generated_import(GeneratedImportKind::ExternalImport),
+            generated_import(GeneratedImportKind::Unresolvable),
+            PlainDiagnostic {
+                category: "telemetry".to_string(),
+                name: "other".to_string(),
+                payload: HashMap::new(),
+            },
+        ];
+        let counts = GeneratedImportCounts::from_diagnostics(&diagnostics);
+
+        assert_eq!(counts.internal, 2);
+        assert_eq!(counts.cross_chunk, 1);
+        assert_eq!(counts.external_require, 1);
+        assert_eq!(counts.external_import, 2);
+        assert_eq!(counts.node_builtin, 0);
+        assert_eq!(counts.data_url, 0);
+        assert_eq!(counts.inline_json, 0);
+        assert_eq!(counts.remote, 0);
+        assert_eq!(counts.ignored, 0);
+        assert_eq!(counts.unresolvable, 1);
+    }
+
+    #[test]
+    fn generated_import_kinds_round_trip() {
+        for kind in GeneratedImportKind::ALL {
+            assert_eq!(GeneratedImportKind::from_str(kind.as_str()), Some(kind));
+        }
+        assert_eq!(GeneratedImportKind::from_str("unknown"), None);
+    }
+}
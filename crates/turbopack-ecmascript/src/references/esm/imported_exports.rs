@@ -0,0 +1,280 @@
+use anyhow::Result;
+use indexmap::IndexMap;
+use turbo_tasks::{ValueToString, Vc};
+use turbopack_core::{
+    ident::AssetIdent,
+    issue::{analyze::AnalyzeIssue, IssueExt, IssueSeverity, StyledString},
+    resolve::origin::ResolveOrigin,
+};
+
+use super::{base::ReferencedAsset, EsmAssetReference};
+use crate::{
+    chunk::{EcmascriptChunkPlaceable, EcmascriptChunkingContext, EcmascriptExports},
+    code_gen::{CodeGenerateable, CodeGeneration},
+};
+
+/// Validates that all names imported through the [EsmAssetReference]s of a
+/// module are exported by the referenced modules. The imports are grouped by
+/// the module they resolve to, so all missing names of one module are
+/// reported in a single issue, even when they're imported by several import
+/// statements.
+#[turbo_tasks::value(shared)]
+#[derive(Hash, Debug)]
+pub struct EsmImportedExportsCheck {
+    /// The references with the names imported through them, in the order of
+    /// the imports.
+    pub imports: Vec<(Vc<EsmAssetReference>, Vec<String>)>,
+}
+
+impl EsmImportedExportsCheck {
+    pub fn new(imports: Vec<(Vc<EsmAssetReference>, Vec<String>)>) -> Vc<Self> {
+        Self::cell(EsmImportedExportsCheck { imports })
+    }
+}
+
+/// The statically known exports of a module, see [static_exports].
+enum StaticExports {
+    /// The export names of an ES module.
+    Esm(Vec<String>),
+    /// The class names of a CSS module. The `default` export is the map of
+    /// all of them.
+    CssModuleClasses(Vec<String>),
+}
+
+impl StaticExports {
+    fn names(&self) -> &[String] {
+        match self {
+            StaticExports::Esm(names) | StaticExports::CssModuleClasses(names) => names,
+        }
+    }
+
+    /// What the names are called in issues.
+    fn kind(&self) -> &'static str {
+        match self {
+            StaticExports::Esm(_) => "exports",
+            StaticExports::CssModuleClasses(_) => "class names",
+        }
+    }
+}
+
+/// Returns the statically known exports of `placeable`, or `None` when they
+/// can't be determined.
+async fn static_exports(
+    placeable: Vc<Box<dyn EcmascriptChunkPlaceable>>,
+) -> Result<Option<StaticExports>> {
+    if let Some(class_names) = &*placeable.css_module_class_names().await? {
+        return Ok(Some(StaticExports::CssModuleClasses(css_module_exports(
+            class_names,
+        ))));
+    }
+    let EcmascriptExports::EsmExports(exports) = &*placeable.get_exports().await? else {
+        return Ok(None);
+    };
+    let expanded = exports.expand_exports().await?;
+    if !expanded.dynamic_exports.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(StaticExports::Esm(
+        expanded.exports.keys().cloned().collect(),
+    )))
+}
+
+/// The names a CSS module with the class names `class_names` can be imported
+/// by: every class name, and `default` for the map of all of them.
+fn css_module_exports(class_names: &[String]) -> Vec<String> {
+    let mut exports = class_names.to_vec();
+    if !exports.iter().any(|name| name == "default") {
+        exports.push("default".to_string());
+    }
+    exports
+}
+
+#[turbo_tasks::value_impl]
+impl CodeGenerateable for EsmImportedExportsCheck {
+    #[turbo_tasks::function]
+    async fn code_generation(
+        &self,
+        _context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    ) -> Result<Vc<CodeGeneration>> {
+        // The names imported from each module, with the first reference to it,
+        // which the issue is reported for.
+        let mut imports_by_module = IndexMap::<_, (Vc<EsmAssetReference>, Vec<String>)>::new();
+        for &(reference, ref names) in &self.imports {
+            // Partial imports resolve to module parts, which only contain the
+            // imported exports, so their names are checked against the whole
+            // module.
+            let referenced_asset = if reference.await?.export_names.is_empty() {
+                reference.get_referenced_asset()
+            } else {
+                reference.get_whole_module_asset()
+            };
+            let ReferencedAsset::Some(placeable) = &*referenced_asset.await? else {
+                continue;
+            };
+            let (_, imported) = imports_by_module
+                .entry(placeable.resolve().await?)
+                .or_insert_with(|| (reference, Vec::new()));
+            for name in names {
+                if !imported.contains(name) {
+                    imported.push(name.clone());
+                }
+            }
+        }
+
+        for (placeable, (reference, names)) in imports_by_module {
+            let Some(available) = static_exports(placeable).await? else {
+                continue;
+            };
+            let missing = missing_exports(&names, available.names());
+            if missing.is_empty() {
+                continue;
+            }
+            let reference = reference.await?;
+            AnalyzeIssue {
+                code: None,
+                category: Vc::cell("analyze".to_string()),
+                message: StyledString::Text(missing_exports_message(
+                    available.kind(),
+                    &reference.request.to_string().await?,
+                    &missing,
+                ))
+                .cell(),
+                source_ident: AssetIdent::from_path(reference.origin.origin_path()),
+                severity: IssueSeverity::Warning.into(),
+                source: reference.issue_source,
+                title: Vc::cell(format!("missing {}", available.kind())),
+            }
+            .cell()
+            .emit();
+        }
+
+        Ok(CodeGeneration { visitors: vec![] }.cell())
+    }
+}
+
+/// An imported name that isn't exported by the referenced module, with the
+/// closest available export as suggestion.
+#[derive(Debug, PartialEq, Eq)]
+struct MissingExport<'a> {
+    name: &'a str,
+    suggestion: Option<&'a str>,
+}
+
+fn missing_exports<'a>(names: &'a [String], available: &'a [String]) -> Vec<MissingExport<'a>> {
+    names
+        .iter()
+        .filter(|name| !available.contains(name))
+        .map(|name| MissingExport {
+            name,
+            suggestion: suggest_export(name, available),
+        })
+        .collect()
+}
+
+/// Lists the `missing` names of `kind`, e.g. `exports`, in one message.
+fn missing_exports_message(kind: &str, request: &str, missing: &[MissingExport<'_>]) -> String {
+    let mut message = format!("The following {kind} were not found in {request}:");
+    for MissingExport { name, suggestion } in missing {
+        message.push_str(&format!("\n  - {name}"));
+        if let Some(suggestion) = suggestion {
+            message.push_str(&format!(" (did you mean {suggestion}?)"));
+        }
+    }
+    message
+}
+
+/// Returns the available export that is closest to `name`, if it's close
+/// enough to likely be a typo.
+fn suggest_export<'a>(name: &str, available: &'a [String]) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    available
+        .iter()
+        .map(|export| (edit_distance(name, export), export))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, export)| export.as_str())
+}
+
+/// The Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn groups_missing_exports() {
+        let names = strings(&["a", "bar", "qux"]);
+        let available = strings(&["a", "baz"]);
+
+        let missing = missing_exports(&names, &available);
+        assert_eq!(
+            missing,
+            vec![
+                MissingExport {
+                    name: "bar",
+                    suggestion: Some("baz"),
+                },
+                MissingExport {
+                    name: "qux",
+                    suggestion: None,
+                },
+            ]
+        );
+        assert_eq!(
+            missing_exports_message("exports", "./x", &missing),
+            "The following exports were not found in ./x:\n  - bar (did you mean baz?)\n  - qux"
+        );
+    }
+
+    #[test]
+    fn unknown_css_module_class_names() {
+        let available = css_module_exports(&strings(&["button", "title"]));
+        // Both a class name and the map of all of them can be imported.
+        let names = strings(&["default", "button", "buton", "header"]);
+
+        let missing = missing_exports(&names, &available);
+        assert_eq!(
+            missing,
+            vec![
+                MissingExport {
+                    name: "buton",
+                    suggestion: Some("button"),
+                },
+                MissingExport {
+                    name: "header",
+                    suggestion: None,
+                },
+            ]
+        );
+        assert_eq!(
+            missing_exports_message("class names", "./a.module.css", &missing),
+            "The following class names were not found in ./a.module.css:\n  - buton (did you mean \
+             button?)\n  - header"
+        );
+    }
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("default", "default"), 0);
+    }
+}
@@ -0,0 +1,118 @@
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::ast::{
+        ArrayLit, ComputedPropName, Expr, Ident, KeyValueProp, Lit, Null, ObjectLit, Prop,
+        PropName, PropOrSpread, Stmt,
+    },
+    quote,
+};
+
+/// Whether a JSON module of `size` bytes with the value `json` is inlined
+/// when modules of up to `max_size` bytes are. Only objects are, as their
+/// properties and the `default` export are all a namespace object needs.
+/// Objects with an `__esModule` property aren't, as the runtime interop
+/// takes their `default` export from the object then.
+pub(super) fn inlinable_json(size: usize, max_size: u32, json: &serde_json::Value) -> bool {
+    size <= max_size as usize
+        && json
+            .as_object()
+            .is_some_and(|object| !object.contains_key("__esModule"))
+}
+
+/// Creates the statement binding the namespace object of a JSON module with
+/// the value `json` to `ident`. Like the namespace object of an imported
+/// module, it's frozen, and `default` is the value itself, which is frozen
+/// too, as it's shared by all evaluations of the importing module.
+pub(super) fn inline_json_stmt(ident: &str, json: &serde_json::Value) -> Stmt {
+    quote!(
+        "var $name = Object.freeze({ default: Object.freeze($json) });" as Stmt,
+        name = Ident::new(ident.into(), DUMMY_SP),
+        json: Expr = json_to_expr(json),
+    )
+}
+
+/// The object literal, or other literal, evaluating to the JSON value `json`.
+fn json_to_expr(json: &serde_json::Value) -> Expr {
+    match json {
+        serde_json::Value::Null => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+        serde_json::Value::Bool(value) => Expr::Lit((*value).into()),
+        serde_json::Value::Number(value) => Expr::Lit(value.as_f64().unwrap_or(f64::NAN).into()),
+        serde_json::Value::String(value) => Expr::Lit(value.as_str().into()),
+        serde_json::Value::Array(values) => Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: values
+                .iter()
+                .map(|value| Some(json_to_expr(value).into()))
+                .collect(),
+        }),
+        serde_json::Value::Object(object) => Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: object
+                .iter()
+                .map(|(key, value)| {
+                    let key = if key == "__proto__" {
+                        // A `__proto__` key would set the prototype instead of
+                        // defining a property like `JSON.parse` does.
+                        PropName::Computed(ComputedPropName {
+                            span: DUMMY_SP,
+                            expr: Box::new(Expr::Lit(key.as_str().into())),
+                        })
+                    } else {
+                        PropName::Str(key.as_str().into())
+                    };
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key,
+                        value: Box::new(json_to_expr(value)),
+                    })))
+                })
+                .collect(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use swc_core::common::SourceMap;
+
+    use super::*;
+    use crate::references::esm::{
+        base::tests::{parse, to_js},
+        hoisting::insert_hoisted_stmt,
+    };
+
+    #[test]
+    fn inlines_small_json() {
+        let json = serde_json::json!({ "name": "a", "__proto__": { "x": [1, null, true] } });
+        assert!(inlinable_json(64, 1024, &json));
+
+        let cm = Arc::new(SourceMap::default());
+        let mut program = parse(&cm, "");
+        insert_hoisted_stmt(
+            &mut program,
+            inline_json_stmt("__TURBOPACK__imported__module__a__", &json),
+            &Default::default(),
+        );
+        let js = to_js(&program, &cm);
+        assert!(js.contains(concat!(
+            r#"var __TURBOPACK__imported__module__a__="#,
+            r#"Object.freeze({default:Object.freeze("#,
+            r#"{"name":"a",["__proto__"]:{"x":[1,null,true]}}"#,
+            r#")});"#,
+        )));
+    }
+
+    #[test]
+    fn references_large_json() {
+        let json = serde_json::json!({ "name": "a" });
+        assert!(!inlinable_json(2048, 1024, &json));
+        // Only objects are inlined, and not those posing as ES modules.
+        assert!(!inlinable_json(8, 1024, &serde_json::json!([1, 2])));
+        assert!(!inlinable_json(
+            32,
+            1024,
+            &serde_json::json!({ "__esModule": true, "default": 1 })
+        ));
+    }
+}
@@ -13,7 +13,7 @@ use crate::{
     chunk::EcmascriptChunkingContext,
     code_gen::{CodeGenerateable, CodeGeneration},
     create_visitor, magic_identifier,
-    references::{as_abs_path, esm::base::insert_hoisted_stmt, AstPath},
+    references::{as_abs_path, esm::hoisting::insert_hoisted_stmt, AstPath},
 };
 
 /// Responsible for initializing the `import.meta` object binding, so that it
@@ -40,7 +40,7 @@ impl CodeGenerateable for ImportMetaBinding {
     #[turbo_tasks::function]
     async fn code_generation(
         &self,
-        _context: Vc<Box<dyn EcmascriptChunkingContext>>,
+        context: Vc<Box<dyn EcmascriptChunkingContext>>,
     ) -> Result<Vc<CodeGeneration>> {
         let path = as_abs_path(self.path).await?.as_str().map_or_else(
             || {
@@ -58,6 +58,7 @@ impl CodeGenerateable for ImportMetaBinding {
             },
         );
 
+        let markers = context.hoisting_markers().await?;
         let visitor = create_visitor!(visit_mut_program(program: &mut Program) {
             // [NOTE] url property is lazy-evaluated, as it should be computed once turbopack_runtime injects a function
             // to calculate an absolute path.
@@ -66,7 +67,7 @@ impl CodeGenerateable for ImportMetaBinding {
                 name = meta_ident(),
                 path: Expr = path.clone(),
             );
-            insert_hoisted_stmt(program, meta);
+            insert_hoisted_stmt(program, meta, &markers);
         });
 
         Ok(CodeGeneration {
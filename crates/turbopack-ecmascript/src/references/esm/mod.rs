@@ -1,18 +1,33 @@
 pub(crate) mod base;
 pub(crate) mod binding;
+pub(crate) mod data_url;
 pub(crate) mod dynamic;
 pub(crate) mod export;
+pub(crate) mod external;
+pub(crate) mod external_check;
+pub(crate) mod hoisting;
+pub(crate) mod import_metrics;
+pub(crate) mod imported_exports;
+pub(crate) mod inline_json;
 pub(crate) mod meta;
 pub(crate) mod module_id;
 pub(crate) mod module_item;
+pub(crate) mod request_transform;
 pub(crate) mod url;
 
 pub use self::{
-    base::EsmAssetReference,
+    base::{
+        EsmAssetReference, EsmAssetReferences, EsmNamespaceShape, ModuleResolveResults,
+        OutOfRootPolicy, ReferenceChunkLoad, ReferenceChunkLoadCounts, ReferencedAsset,
+        ReferencesByModule, ResolveStatus,
+    },
     binding::EsmBinding,
     dynamic::EsmAsyncAssetReference,
     export::{EsmExport, EsmExports},
+    import_metrics::{GeneratedImportCounts, GeneratedImportKind},
+    imported_exports::EsmImportedExportsCheck,
     meta::{ImportMetaBinding, ImportMetaRef},
     module_item::EsmModuleItem,
+    request_transform::{PrefixRequestTransform, RequestTransform},
     url::{UrlAssetReference, UrlRewriteBehavior},
 };
@@ -3,7 +3,8 @@ use swc_core::{ecma::ast::Expr, quote};
 use turbo_tasks::{ValueToString, Vc};
 use turbopack_core::{
     chunk::{
-        ChunkItemExt, ChunkableModule, ChunkableModuleReference, ChunkingTypeOption, ModuleId,
+        ChunkItemExt, ChunkableModule, ChunkableModuleReference, ChunkingContext,
+        ChunkingTypeOption, ModuleId,
     },
     reference::ModuleReference,
     resolve::ModuleResolveResult,
@@ -57,6 +58,14 @@ impl ChunkableModuleReference for EsmModuleIdAssetReference {
     fn chunking_type(&self) -> Vc<ChunkingTypeOption> {
         self.inner.chunking_type()
     }
+
+    #[turbo_tasks::function]
+    fn chunking_type_for(
+        &self,
+        chunking_context: Vc<Box<dyn ChunkingContext>>,
+    ) -> Vc<ChunkingTypeOption> {
+        self.inner.chunking_type_for(chunking_context)
+    }
 }
 
 #[turbo_tasks::value_impl]
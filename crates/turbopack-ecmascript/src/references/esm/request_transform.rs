@@ -0,0 +1,69 @@
+use anyhow::Result;
+use turbo_tasks::Vc;
+use turbopack_core::resolve::parse::Request;
+
+/// Rewrites the request of an [EsmAssetReference](super::EsmAssetReference)
+/// before it's resolved. This allows normalizing aliases per reference
+/// instead of in the global resolve options.
+#[turbo_tasks::value_trait]
+pub trait RequestTransform {
+    fn transform(self: Vc<Self>, request: Vc<Request>) -> Vc<Request>;
+}
+
+/// A [RequestTransform] replacing the prefix `prefix` of a request with
+/// `replacement`, e.g. `@/` with `./src/`. Requests without the prefix and
+/// dynamic requests are unchanged.
+#[turbo_tasks::value]
+pub struct PrefixRequestTransform {
+    prefix: String,
+    replacement: String,
+}
+
+#[turbo_tasks::value_impl]
+impl PrefixRequestTransform {
+    #[turbo_tasks::function]
+    pub fn new(prefix: String, replacement: String) -> Vc<Self> {
+        PrefixRequestTransform {
+            prefix,
+            replacement,
+        }
+        .cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl RequestTransform for PrefixRequestTransform {
+    #[turbo_tasks::function]
+    async fn transform(&self, request: Vc<Request>) -> Result<Vc<Request>> {
+        let Some(path) = request
+            .await?
+            .request()
+            .and_then(|path| replace_prefix(&path, &self.prefix, &self.replacement))
+        else {
+            return Ok(request);
+        };
+        Ok(Request::parse_string(path).with_query(request.query()))
+    }
+}
+
+/// Replaces the prefix `prefix` of `request` with `replacement`, or returns
+/// `None` if `request` doesn't start with `prefix`.
+fn replace_prefix(request: &str, prefix: &str, replacement: &str) -> Option<String> {
+    let rest = request.strip_prefix(prefix)?;
+    Some(format!("{replacement}{rest}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_prefix() {
+        assert_eq!(
+            replace_prefix("@/utils", "@/", "./src/").as_deref(),
+            Some("./src/utils")
+        );
+        assert_eq!(replace_prefix("react", "@/", "./src/"), None);
+        assert_eq!(replace_prefix("@scope/pkg", "@/", "./src/"), None);
+    }
+}
@@ -3,13 +3,15 @@ use swc_core::{
     ecma::ast::{Expr, ExprOrSpread, NewExpr},
     quote,
 };
-use turbo_tasks::{Value, ValueToString, Vc};
+use turbo_tasks::{TryFlatJoinIterExt, Value, ValueToString, Vc};
 use turbopack_core::{
     chunk::{
-        ChunkItemExt, ChunkableModule, ChunkableModuleReference, ChunkingType, ChunkingTypeOption,
+        ChunkItem, ChunkItemExt, ChunkableModule, ChunkableModuleReference, ChunkingContext,
+        ChunkingType, ChunkingTypeOption,
     },
     environment::Rendering,
     issue::IssueSource,
+    output::OutputAsset,
     reference::ModuleReference,
     reference_type::{ReferenceType, UrlReferenceSubType},
     resolve::{origin::ResolveOrigin, parse::Request, url_resolve, ModuleResolveResult},
@@ -35,6 +37,9 @@ pub enum UrlRewriteBehavior {
     Relative,
     /// Uses the full URL, including the base.
     Full,
+    /// Inlines the URL of the emitted asset as a string literal, so it's
+    /// known without evaluating the asset's chunk item.
+    Static,
     /// Do not attempt to rewrite the URL.
     None,
 }
@@ -134,6 +139,7 @@ impl CodeGenerateable for UrlAssetReference {
     │ Relative                      │ __turbopack_relative_url__(__turbopack_require__(urlId))                │ __turbopack_relative_url__(url)                │ new URL(url, base)    │
     │ Full(RenderingClient::Client) │ new URL(__turbopack_require__(urlId), location.origin)                  │ new URL(url, location.origin)                  │ new URL(url, base)    │
     │ Full(RenderingClient::..)     │ new URL(__turbopack_resolve_module_id_path__(urlId))                    │ new URL(url, base)                             │ new URL(url, base)    │
+    │ Static                        │ new URL("<asset url>", base)                                            │ new URL(url, base)                             │ new URL(url, base)    │
     │ None                          │ new URL(url, base)                                                      │ new URL(url, base)                             │ new URL(url, base)    │
     └───────────────────────────────┴─────────────────────────────────────────────────────────────────────────┴────────────────────────────────────────────────┴───────────────────────┘
     */
@@ -181,7 +187,9 @@ impl CodeGenerateable for UrlAssetReference {
                             }
                         }));
                     }
-                    ReferencedAsset::OriginalReferenceTypeExternal(request) => {
+                    ReferencedAsset::OriginalReferenceTypeExternal(request)
+                    | ReferencedAsset::NodeBuiltin(request)
+                    | ReferencedAsset::DataUrl(request) => {
                         let request = request.to_string();
                         visitors.push(create_visitor!(ast_path, visit_mut_expr(new_expr: &mut Expr) {
                             let should_rewrite_to_relative = if let Expr::New(NewExpr { args: Some(args), .. }) = new_expr {
@@ -198,7 +206,10 @@ impl CodeGenerateable for UrlAssetReference {
                             }
                         }));
                     }
-                    ReferencedAsset::None => {}
+                    // URL references are never annotated as remote.
+                    ReferencedAsset::InlineJson(_)
+                    | ReferencedAsset::Remote { .. }
+                    | ReferencedAsset::None => {}
                 }
             }
             UrlRewriteBehavior::Full => {
@@ -263,7 +274,9 @@ impl CodeGenerateable for UrlAssetReference {
                             }
                         }));
                     }
-                    ReferencedAsset::OriginalReferenceTypeExternal(request) => {
+                    ReferencedAsset::OriginalReferenceTypeExternal(request)
+                    | ReferencedAsset::NodeBuiltin(request)
+                    | ReferencedAsset::DataUrl(request) => {
                         let request = request.to_string();
                         visitors.push(create_visitor!(ast_path, visit_mut_expr(new_expr: &mut Expr) {
                             if let Expr::New(NewExpr { args: Some(args), .. }) = new_expr {
@@ -279,7 +292,61 @@ impl CodeGenerateable for UrlAssetReference {
                             }
                         }));
                     }
-                    ReferencedAsset::None => {}
+                    // URL references are never annotated as remote.
+                    ReferencedAsset::InlineJson(_)
+                    | ReferencedAsset::Remote { .. }
+                    | ReferencedAsset::None => {}
+                }
+            }
+            UrlRewriteBehavior::Static => {
+                let referenced_asset = self.get_referenced_asset().await?;
+                let ast_path = this.ast_path.await?;
+
+                let url = match &*referenced_asset {
+                    ReferencedAsset::Some(asset) => {
+                        // The chunk item of a static asset exports the URL of the single output
+                        // asset it references. Other chunk items are required at runtime to get
+                        // their URL.
+                        let chunk_item = asset.as_chunk_item(Vc::upcast(chunking_context));
+                        let output_assets = chunk_item
+                            .references()
+                            .await?
+                            .iter()
+                            .map(|reference| async move {
+                                Ok(reference
+                                    .resolve_reference()
+                                    .primary_output_assets()
+                                    .await?
+                                    .clone_value())
+                            })
+                            .try_flat_join()
+                            .await?;
+                        if let [output_asset] = &output_assets[..] {
+                            let url = chunking_context.asset_url(output_asset.ident()).await?;
+                            Some(url.as_str().into())
+                        } else {
+                            Some(quote!(
+                                "__turbopack_require__($id)" as Expr,
+                                id: Expr = module_id_to_lit(&*chunk_item.id().await?),
+                            ))
+                        }
+                    }
+                    ReferencedAsset::OriginalReferenceTypeExternal(request)
+                    | ReferencedAsset::NodeBuiltin(request)
+                    | ReferencedAsset::DataUrl(request) => Some(request.as_str().into()),
+                    ReferencedAsset::InlineJson(_)
+                    | ReferencedAsset::Remote { .. }
+                    | ReferencedAsset::None => None,
+                };
+
+                if let Some(url) = url {
+                    visitors.push(create_visitor!(ast_path, visit_mut_expr(new_expr: &mut Expr) {
+                        if let Expr::New(NewExpr { args: Some(args), .. }) = new_expr {
+                            if let Some(ExprOrSpread { box expr, spread: None }) = args.get_mut(0) {
+                                *expr = url.clone();
+                            }
+                        }
+                    }));
                 }
             }
             UrlRewriteBehavior::None => {
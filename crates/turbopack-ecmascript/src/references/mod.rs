@@ -15,7 +15,7 @@ pub mod util;
 
 use std::{
     borrow::Cow,
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     future::Future,
     mem::take,
     pin::Pin,
@@ -77,8 +77,9 @@ use self::{
     },
     cjs::CjsAssetReference,
     esm::{
-        export::EsmExport, EsmAssetReference, EsmAsyncAssetReference, EsmExports, EsmModuleItem,
-        ImportMetaBinding, ImportMetaRef, UrlAssetReference,
+        export::EsmExport, EsmAssetReference, EsmAsyncAssetReference, EsmExports,
+        EsmImportedExportsCheck, EsmModuleItem, ImportMetaBinding, ImportMetaRef,
+        UrlAssetReference,
     },
     node::DirAssetReference,
     raw::FileSourceReference,
@@ -111,7 +112,7 @@ use crate::{
         graph::{ConditionalKind, EffectArg, EvalContext, VarGraph},
         imports::{ImportedSymbol, Reexport},
         parse_require_context,
-        top_level_await::has_top_level_await,
+        top_level_await::{detect_top_level_await, has_top_level_await},
         ConstantNumber, ConstantString, ModuleValue, RequireContextValue,
     },
     chunk::EcmascriptExports,
@@ -524,13 +525,74 @@ pub(crate) async fn analyse_ecmascript_module_internal(
         set_handler_and_globals(&handler, globals, || create_graph(program, eval_context));
 
     let mut evaluation_references = Vec::new();
+    let mut used_imports = HashSet::new();
+    collect_used_imports(&var_graph.effects, &mut used_imports);
+
+    // Requests of which the module imports the default export and nothing
+    // else, besides evaluating them.
+    let mut default_import_only = HashMap::new();
+    for r in eval_context.imports.references() {
+        let only = default_import_only
+            .entry(r.module_path.clone())
+            .or_insert(Some(false));
+        *only = match (&r.imported_symbol, *only) {
+            (_, None) => None,
+            (ImportedSymbol::ModuleEvaluation, only) => only,
+            (ImportedSymbol::Symbol(name), _) if &**name == "default" => Some(true),
+            _ => None,
+        };
+    }
+
+    // Isolated imports bind the async loader of the module instead of its
+    // exports, which named and default imports would read as properties of the
+    // loader. Modules imported that way are imported with the default chunking
+    // type instead.
+    let mut isolated_with_bindings = HashSet::new();
+    for r in eval_context.imports.references() {
+        if matches!(r.imported_symbol, ImportedSymbol::Symbol(_))
+            && r.annotations.chunking_type() == Some("isolated")
+            && isolated_with_bindings.insert(r.module_path.clone())
+        {
+            AnalyzeIssue {
+                code: None,
+                category: Vc::cell("analyze".to_string()),
+                message: StyledString::Text(format!(
+                    "The import of {} is annotated with chunking-type \"isolated\", which binds \
+                     the async loader of the module instead of its exports, so it can't have \
+                     named or default imports. It's imported with the default chunking type \
+                     instead. Import the module with a namespace import to load it on its own.",
+                    r.module_path
+                ))
+                .cell(),
+                source_ident: source.ident(),
+                severity: IssueSeverity::Error.into(),
+                source: r.issue_source,
+                title: Vc::cell("named import of an isolated module".to_string()),
+            }
+            .cell()
+            .emit();
+        }
+    }
 
     for (i, r) in eval_context.imports.references().enumerate() {
+        let namespace_import = matches!(r.imported_symbol, ImportedSymbol::Namespace);
+        let span = r.span;
+        let import_attributes = r.attributes.clone();
+        let type_attribute_span = r.type_attribute_span;
+        let default_import = default_import_only.get(&r.module_path) == Some(&Some(true));
+        let conditions = r.annotations.conditions();
+        let annotations = if isolated_with_bindings.contains(&r.module_path)
+            && r.annotations.chunking_type() == Some("isolated")
+        {
+            r.annotations.without_chunking_type()
+        } else {
+            r.annotations.clone()
+        };
         let r = EsmAssetReference::new(
             origin,
             Request::parse(Value::new(r.module_path.to_string().into())),
             r.issue_source,
-            Value::new(r.annotations.clone()),
+            Value::new(annotations),
             match options.tree_shaking_mode {
                 Some(TreeShakingMode::ModuleFragments) => match &r.imported_symbol {
                     ImportedSymbol::ModuleEvaluation => {
@@ -552,6 +614,44 @@ pub(crate) async fn analyse_ecmascript_module_internal(
             },
             import_externals,
         );
+        let r = if namespace_import {
+            r.with_namespace_import()
+        } else {
+            r
+        };
+        let r = if used_imports.contains(&i) {
+            r.with_bindings_used()
+        } else {
+            r
+        };
+        let r = if default_import {
+            r.with_default_import_only()
+        } else {
+            r
+        };
+        let r = if options.strict_import_annotations {
+            r.with_strict_annotations()
+        } else {
+            r
+        };
+        let r = match conditions {
+            Some(conditions) => r.with_conditions(conditions),
+            None => r,
+        };
+        let r = match options.import_map {
+            Some(import_map) => r.with_import_map(import_map),
+            None => r,
+        };
+        let r = r.with_span(span.lo.0, span.hi.0);
+        let r = if import_attributes.is_empty() {
+            r
+        } else {
+            r.with_import_attributes(import_attributes.cell())
+        };
+        let r = match type_attribute_span {
+            Some(span) => r.with_type_attribute_issue_source(issue_source(source, span)),
+            None => r,
+        };
         import_references.push(r);
     }
 
@@ -560,9 +660,18 @@ pub(crate) async fn analyse_ecmascript_module_internal(
         // passing that to other turbo tasks functions later.
         *r = r.resolve().await?;
     }
-    for r in import_references.iter() {
+    let mut imported_names = Vec::new();
+    for (i, r) in import_references.iter().enumerate() {
         // `add_reference` will avoid adding duplicate references
         analysis.add_import_reference(*r);
+
+        let names = eval_context.imports.imported_names(i);
+        if !names.is_empty() {
+            imported_names.push((*r, names.into_iter().map(|name| name.to_string()).collect()));
+        }
+    }
+    if !imported_names.is_empty() {
+        analysis.add_code_gen(EsmImportedExportsCheck::new(imported_names));
     }
     for i in evaluation_references {
         analysis.add_evaluation_reference(import_references[i]);
@@ -729,21 +838,25 @@ pub(crate) async fn analyse_ecmascript_module_internal(
         }
     };
 
-    let top_level_await_span =
-        set_handler_and_globals(&handler, globals, || has_top_level_await(program));
-    let has_top_level_await = top_level_await_span.is_some();
-
     if eval_context.is_esm() || specified_type == SpecifiedModuleType::EcmaScript {
+        let has_top_level_await =
+            set_handler_and_globals(&handler, globals, || detect_top_level_await(program));
         let async_module = AsyncModule {
             placeable: Vc::upcast(module),
             references: import_references.iter().copied().collect(),
             has_top_level_await,
             import_externals,
+            esm_externals: *compile_time_info
+                .environment()
+                .supports_esm_externals()
+                .await?,
         }
         .cell();
         analysis.set_async_module(async_module);
         analysis.add_code_gen_with_availability_info(async_module);
-    } else if let Some(span) = top_level_await_span {
+    } else if let Some(span) =
+        set_handler_and_globals(&handler, globals, || has_top_level_await(program))
+    {
         AnalyzeIssue {
             code: None,
             category: Vc::cell("analyze".to_string()),
@@ -1911,6 +2024,42 @@ fn issue_source(source: Vc<Box<dyn Source>>, span: Span) -> Vc<IssueSource> {
     IssueSource::from_swc_offsets(source, span.lo.to_usize(), span.hi.to_usize())
 }
 
+/// Collects the indices of the imports whose bindings are referenced by
+/// `effects`, including the effects of conditional blocks and closures.
+fn collect_used_imports(effects: &[Effect], used: &mut HashSet<usize>) {
+    for effect in effects {
+        match effect {
+            Effect::ImportedBinding {
+                esm_reference_index,
+                ..
+            } => {
+                used.insert(*esm_reference_index);
+            }
+            Effect::Conditional { kind, .. } => match &**kind {
+                ConditionalKind::If { then } => collect_used_imports(&then.effects, used),
+                ConditionalKind::IfElse { then, r#else }
+                | ConditionalKind::Ternary { then, r#else } => {
+                    collect_used_imports(&then.effects, used);
+                    collect_used_imports(&r#else.effects, used);
+                }
+                ConditionalKind::And { expr }
+                | ConditionalKind::Or { expr }
+                | ConditionalKind::NullishCoalescing { expr } => {
+                    collect_used_imports(&expr.effects, used)
+                }
+            },
+            Effect::Call { args, .. } | Effect::MemberCall { args, .. } => {
+                for arg in args {
+                    if let EffectArg::Closure(_, block) = arg {
+                        collect_used_imports(&block.effects, used);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 fn analyze_amd_define(
     source: Vc<Box<dyn Source>>,
     analysis: &mut AnalyzeEcmascriptModuleResultBuilder,
@@ -136,7 +136,14 @@ impl SinglePatternMapping {
         }
     }
 
-    pub fn create_import(&self, key_expr: Cow<'_, Expr>, import_externals: bool) -> Expr {
+    /// With `async_import`, the imported module is an async module, which is
+    /// awaited with `__turbopack_async_import__`.
+    pub fn create_import(
+        &self,
+        key_expr: Cow<'_, Expr>,
+        import_externals: bool,
+        async_import: bool,
+    ) -> Expr {
         match self {
             Self::Invalid => {
                 let error = quote_expr!(
@@ -186,7 +193,11 @@ impl SinglePatternMapping {
                 )),
                 args: vec![ExprOrSpread {
                     spread: None,
-                    expr: quote_expr!("__turbopack_import__"),
+                    expr: if async_import {
+                        quote_expr!("(id) => __turbopack_async_import__(__turbopack_import__(id))")
+                    } else {
+                        quote_expr!("__turbopack_import__")
+                    },
                 }],
                 span: DUMMY_SP,
                 type_args: None,
@@ -195,10 +206,17 @@ impl SinglePatternMapping {
                 callee: Callee::Expr(quote_expr!("Promise.resolve().then")),
                 args: vec![ExprOrSpread {
                     spread: None,
-                    expr: quote_expr!(
-                        "() => __turbopack_import__($arg)",
-                        arg: Expr = self.create_id(key_expr)
-                    ),
+                    expr: if async_import {
+                        quote_expr!(
+                            "() => __turbopack_async_import__(__turbopack_import__($arg))",
+                            arg: Expr = self.create_id(key_expr)
+                        )
+                    } else {
+                        quote_expr!(
+                            "() => __turbopack_import__($arg)",
+                            arg: Expr = self.create_id(key_expr)
+                        )
+                    },
                 }],
                 span: DUMMY_SP,
                 type_args: None,
@@ -232,9 +250,16 @@ impl PatternMapping {
         }
     }
 
-    pub fn create_import(&self, key_expr: Expr, import_externals: bool) -> Expr {
+    pub fn create_import(
+        &self,
+        key_expr: Expr,
+        import_externals: bool,
+        async_import: bool,
+    ) -> Expr {
         match self {
-            PatternMapping::Single(pm) => pm.create_import(Cow::Owned(key_expr), import_externals),
+            PatternMapping::Single(pm) => {
+                pm.create_import(Cow::Owned(key_expr), import_externals, async_import)
+            }
             PatternMapping::Map(map) => {
                 let map = Expr::Object(ObjectLit {
                     span: DUMMY_SP,
@@ -243,7 +268,7 @@ impl PatternMapping {
                         .map(|(k, v)| {
                             PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
                                 key: PropName::Str(k.as_str().into()),
-                                value: quote_expr!("() => $expr", expr: Expr = v.create_import(Cow::Borrowed(&key_expr), import_externals)),
+                                value: quote_expr!("() => $expr", expr: Expr = v.create_import(Cow::Borrowed(&key_expr), import_externals, async_import)),
                             })))
                         })
                         .collect(),
@@ -377,3 +402,55 @@ impl PatternMapping {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use swc_core::{
+        common::SourceMap,
+        ecma::codegen::{text_writer::JsWriter, Emitter, Node},
+    };
+
+    use super::*;
+
+    fn to_js(expr: &Expr) -> String {
+        let cm = Arc::new(SourceMap::default());
+        let mut bytes = Vec::new();
+        let mut emitter = Emitter {
+            cfg: swc_core::ecma::codegen::Config::default().with_minify(true),
+            cm: cm.clone(),
+            comments: None,
+            wr: JsWriter::new(cm, "\n", &mut bytes, None),
+        };
+        expr.emit_with(&mut emitter).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn dynamic_import_of_template_literal() {
+        // import(`./pages/${name}.js`) with three matching files.
+        let map = PatternMapping::Map(
+            ["a", "b", "c"]
+                .into_iter()
+                .map(|name| {
+                    (
+                        format!("./pages/{name}.js"),
+                        SinglePatternMapping::ModuleLoader(ModuleId::String(format!(
+                            "loader-{name}"
+                        ))),
+                    )
+                })
+                .collect(),
+        );
+        let key = quote_expr!("`./pages/${name}.js`");
+
+        assert_eq!(
+            to_js(&map.create_import(*key, false, false)),
+            "__turbopack_lookup__({\"./pages/a.js\":()=>__turbopack_require__(\"loader-a\"\
+             )(__turbopack_import__),\"./pages/b.js\":()=>__turbopack_require__(\"loader-b\"\
+             )(__turbopack_import__),\"./pages/c.js\":()=>__turbopack_require__(\"loader-c\"\
+             )(__turbopack_import__)},`./pages/${name}.js`,true)"
+        );
+    }
+}
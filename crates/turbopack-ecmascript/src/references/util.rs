@@ -14,13 +14,50 @@ pub fn throw_module_not_found_expr(request: &str) -> Expr {
     )
 }
 
+/// Creates a IIFE expression that throws a "Cannot find module" error for the
+/// given request string, naming the module `origin` that imported it.
+pub fn throw_module_not_found_expr_with_origin(request: &str, origin: &str) -> Expr {
+    let message = format!("Cannot find module '{request}' imported from '{origin}'");
+    quote!(
+        "(() => { const e = new Error($message); e.code = 'MODULE_NOT_FOUND'; throw e; })()"
+            as Expr,
+        message: Expr = message.into()
+    )
+}
+
+/// The request as written in the source, including its query, so requests
+/// like `./icon.svg?raw` and `./icon.svg?url` stay distinct.
 #[turbo_tasks::function]
 pub async fn request_to_string(request: Vc<Request>) -> Result<Vc<String>> {
-    Ok(Vc::cell(
-        request
-            .await?
-            .request()
-            // TODO: Handle Request::Dynamic, Request::Alternatives
-            .unwrap_or_else(|| "unknown".into()),
-    ))
+    let request_value = request.await?;
+    // TODO: Handle Request::Dynamic, Request::Alternatives
+    let Some(path) = request_value.request() else {
+        return Ok(Vc::cell("unknown".into()));
+    };
+    Ok(Vc::cell(format!("{path}{}", request_value.query().await?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn request_to_string_keeps_query() {
+        crate::register();
+        turbo_tasks_testing::VcStorage::with(async {
+            let raw = request_to_string(Request::parse_string("./icon.svg?raw".to_string()))
+                .await
+                .unwrap();
+            let url = request_to_string(Request::parse_string("./icon.svg?url".to_string()))
+                .await
+                .unwrap();
+            let plain = request_to_string(Request::parse_string("./icon.svg".to_string()))
+                .await
+                .unwrap();
+            assert_eq!(*raw, "./icon.svg?raw");
+            assert_eq!(*url, "./icon.svg?url");
+            assert_eq!(*plain, "./icon.svg");
+        })
+        .await
+    }
 }
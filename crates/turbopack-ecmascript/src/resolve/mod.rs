@@ -1,15 +1,17 @@
 pub mod node_native_binding;
 
 use anyhow::Result;
+use indexmap::IndexMap;
 use turbo_tasks::{Value, Vc};
+use turbo_tasks_fs::FileSystemPath;
 use turbopack_core::{
     issue::{IssueSeverity, IssueSource},
     reference_type::{CommonJsReferenceSubType, EcmaScriptModulesReferenceSubType, ReferenceType},
     resolve::{
         handle_resolve_error,
         options::{
-            ConditionValue, ResolutionConditions, ResolveInPackage, ResolveIntoPackage,
-            ResolveOptions,
+            ConditionValue, ImportMap, ImportMapping, ResolutionConditions, ResolveInPackage,
+            ResolveIntoPackage, ResolveOptions,
         },
         origin::{ResolveOrigin, ResolveOriginExt},
         parse::Request,
@@ -55,18 +57,113 @@ pub async fn apply_cjs_specific_options(options: Vc<ResolveOptions>) -> Result<V
     Ok(options.into())
 }
 
+/// Pins the export conditions to `conditions`: the conditions set in `options`
+/// are unset, and `conditions` are set instead. Conditions that are unset or
+/// unknown are kept as they are.
+fn force_conditions(options: &mut ResolveOptions, conditions: &[String]) {
+    for condition_map in get_condition_maps(options) {
+        for value in condition_map.values_mut() {
+            if *value == ConditionValue::Set {
+                *value = ConditionValue::Unset;
+            }
+        }
+        for condition in conditions {
+            condition_map.insert(condition.clone(), ConditionValue::Set);
+        }
+    }
+}
+
+#[turbo_tasks::function]
+pub async fn apply_forced_conditions(
+    options: Vc<ResolveOptions>,
+    conditions: Vec<String>,
+) -> Result<Vc<ResolveOptions>> {
+    let mut options: ResolveOptions = options.await?.clone_value();
+    force_conditions(&mut options, &conditions);
+    Ok(options.into())
+}
+
+/// Whether an import map `target` is a URL, which is loaded as an external
+/// instead of being resolved.
+fn is_external_url(target: &str) -> bool {
+    ["http://", "https://", "data:"]
+        .iter()
+        .any(|scheme| target.starts_with(scheme))
+}
+
+/// Creates an [ImportMap] from the `imports` of a browser-style import map,
+/// mapping specifiers to their targets. Targets that are URLs are loaded as
+/// externals, other targets are resolved instead of the specifier, relative
+/// to `base`, the directory of the import map, like browsers resolve them
+/// against the URL of the import map. Specifiers ending with `/` map every
+/// request starting with them.
+pub fn browser_import_map(
+    imports: &IndexMap<String, String>,
+    base: Vc<FileSystemPath>,
+) -> ImportMap {
+    let mut import_map = ImportMap::empty();
+    for (specifier, target) in imports {
+        let prefix_match = specifier.ends_with('/') && target.ends_with('/');
+        let target = if prefix_match {
+            format!("{target}*")
+        } else {
+            target.clone()
+        };
+        let mapping = if is_external_url(&target) {
+            ImportMapping::External(Some(target))
+        } else {
+            ImportMapping::PrimaryAlternative(target, Some(base))
+        }
+        .cell();
+        if prefix_match {
+            import_map.insert_wildcard_alias(specifier, mapping);
+        } else {
+            import_map.insert_exact_alias(specifier, mapping);
+        }
+    }
+    import_map
+}
+
+/// Applies `import_map` on top of the import map of `options`. Entries of
+/// `import_map` take precedence over the ones for the same specifiers in
+/// `options`.
+#[turbo_tasks::function]
+pub async fn apply_import_map(
+    options: Vc<ResolveOptions>,
+    import_map: Vc<ImportMap>,
+) -> Result<Vc<ResolveOptions>> {
+    let mut options: ResolveOptions = options.await?.clone_value();
+    options.import_map = Some(match options.import_map {
+        Some(existing) => existing.extend(import_map),
+        None => import_map,
+    });
+    Ok(options.into())
+}
+
+/// Resolves `request` as an ESM import. With `conditions`, the package export
+/// conditions of the resolve options are replaced with them for this request,
+/// see [apply_forced_conditions]. With `import_map`, it's consulted before the
+/// import map of the resolve options, see [apply_import_map]. The `import`
+/// condition is set either way.
 #[turbo_tasks::function]
 pub async fn esm_resolve(
     origin: Vc<Box<dyn ResolveOrigin>>,
     request: Vc<Request>,
     ty: Value<EcmaScriptModulesReferenceSubType>,
+    conditions: Option<Vec<String>>,
+    import_map: Option<Vc<ImportMap>>,
     issue_severity: Vc<IssueSeverity>,
     issue_source: Option<Vc<IssueSource>>,
 ) -> Result<Vc<ModuleResolveResult>> {
     let ty = Value::new(ReferenceType::EcmaScriptModules(ty.into_value()));
-    let options = apply_esm_specific_options(origin.resolve_options(ty.clone()))
-        .resolve()
-        .await?;
+    let mut options = origin.resolve_options(ty.clone());
+    if let Some(conditions) = conditions {
+        options = apply_forced_conditions(options, conditions);
+    }
+    if let Some(import_map) = import_map {
+        options = apply_import_map(options, import_map);
+    }
+    let options = apply_esm_specific_options(options).resolve().await?;
     specific_resolve(origin, request, options, ty, issue_severity, issue_source).await
 }
 
@@ -114,3 +211,146 @@ pub fn try_to_severity(in_try: bool) -> Vc<IssueSeverity> {
         IssueSeverity::Error.cell()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use turbo_tasks_fs::{DiskFileSystem, FileSystem};
+    use turbopack_core::resolve::{options::ImportMapResult, ResolveResultItem};
+
+    use super::*;
+
+    #[test]
+    fn forced_conditions() {
+        let conditions: ResolutionConditions = [
+            ("node".to_string(), ConditionValue::Set),
+            ("import".to_string(), ConditionValue::Set),
+        ]
+        .into();
+        let mut forced = ResolveOptions {
+            into_package: vec![ResolveIntoPackage::ExportsField {
+                conditions: conditions.clone(),
+                unspecified_conditions: ConditionValue::Unset,
+            }],
+            in_package: vec![ResolveInPackage::ImportsField {
+                conditions,
+                unspecified_conditions: ConditionValue::Unset,
+            }],
+            ..Default::default()
+        };
+        force_conditions(&mut forced, &["worker".to_string()]);
+        for condition_map in get_condition_maps(&mut forced) {
+            assert_eq!(condition_map["worker"], ConditionValue::Set);
+            assert_eq!(condition_map["node"], ConditionValue::Unset);
+        }
+    }
+
+    /// A file system root for import maps, which isn't read by exact and
+    /// prefix entries.
+    fn root() -> Vc<FileSystemPath> {
+        DiskFileSystem::new("project".to_string(), "/project".to_string(), vec![]).root()
+    }
+
+    /// Looks `request` up in `import_map` from `lookup_path`.
+    async fn lookup(
+        import_map: &ImportMap,
+        lookup_path: Vc<FileSystemPath>,
+        request: &str,
+    ) -> Result<ImportMapResult> {
+        import_map
+            .lookup(lookup_path, Request::parse_string(request.to_string()))
+            .await
+    }
+
+    /// The request `result` aliases and the path it's resolved from, if any.
+    async fn alias(result: ImportMapResult) -> Result<Option<(String, Vc<FileSystemPath>)>> {
+        Ok(match result {
+            ImportMapResult::Alias(request, Some(lookup_path)) => request
+                .await?
+                .request()
+                .map(|request| (request, lookup_path)),
+            _ => None,
+        })
+    }
+
+    /// The external `result` loads, if any.
+    async fn external(result: ImportMapResult) -> Result<Option<String>> {
+        let ImportMapResult::Result(result) = result else {
+            return Ok(None);
+        };
+        Ok(match result.await?.primary.values().next() {
+            Some(ResolveResultItem::OriginalReferenceTypeExternal(name)) => Some(name.clone()),
+            _ => None,
+        })
+    }
+
+    #[tokio::test]
+    async fn bare_specifier_remapped_to_relative_path() {
+        crate::register();
+        let tt = turbo_tasks::TurboTasks::new(turbo_tasks_memory::MemoryBackend::default());
+        tt.run_once(async {
+            // Relative targets are resolved from the directory of the import
+            // map, not from the importing module.
+            let base = root();
+            let importer = base.join("src/pages".to_string());
+            let import_map = browser_import_map(
+                &IndexMap::from([
+                    ("lodash".to_string(), "./vendor/lodash.js".to_string()),
+                    ("lodash/".to_string(), "./vendor/lodash/".to_string()),
+                ]),
+                base,
+            );
+
+            assert_eq!(
+                alias(lookup(&import_map, importer, "lodash").await?).await?,
+                Some(("./vendor/lodash.js".to_string(), base))
+            );
+            assert_eq!(
+                alias(lookup(&import_map, importer, "lodash/fp.js").await?).await?,
+                Some(("./vendor/lodash/fp.js".to_string(), base))
+            );
+            assert!(matches!(
+                lookup(&import_map, importer, "react").await?,
+                ImportMapResult::NoEntry
+            ));
+
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn bare_specifier_remapped_to_external_url() {
+        crate::register();
+        let tt = turbo_tasks::TurboTasks::new(turbo_tasks_memory::MemoryBackend::default());
+        tt.run_once(async {
+            let base = root();
+            let import_map = browser_import_map(
+                &IndexMap::from([
+                    (
+                        "lodash".to_string(),
+                        "https://cdn.example.com/lodash.js".to_string(),
+                    ),
+                    (
+                        "lodash/".to_string(),
+                        "https://cdn.example.com/lodash/".to_string(),
+                    ),
+                ]),
+                base,
+            );
+
+            assert_eq!(
+                external(lookup(&import_map, base, "lodash").await?).await?,
+                Some("https://cdn.example.com/lodash.js".to_string())
+            );
+            assert_eq!(
+                external(lookup(&import_map, base, "lodash/fp.js").await?).await?,
+                Some("https://cdn.example.com/lodash/fp.js".to_string())
+            );
+
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+}
@@ -62,7 +62,7 @@ impl EcmascriptChunkItem for EcmascriptModuleReexportsChunkItem {
         let async_module_options = self
             .module
             .get_async_module()
-            .module_options(async_module_info);
+            .module_options(async_module_info, false);
 
         let async_module = async_module_options.await?.clone_value();
 
@@ -41,7 +41,7 @@ impl EcmascriptChunkItem for EcmascriptModuleLocalsChunkItem {
         let original_module = module.module;
         let async_module_options = original_module
             .get_async_module()
-            .module_options(async_module_info);
+            .module_options(async_module_info, false);
         let parsed = original_module.parse().resolve().await?;
 
         let analyze_result = original_module.analyze().await?.clone_value();
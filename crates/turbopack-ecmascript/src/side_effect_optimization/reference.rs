@@ -18,7 +18,7 @@ use crate::{
     chunk::{EcmascriptChunkPlaceable, EcmascriptChunkingContext},
     code_gen::{CodeGenerateable, CodeGeneration},
     create_visitor,
-    references::esm::base::{insert_hoisted_stmt, ReferencedAsset},
+    references::esm::{base::ReferencedAsset, hoisting::insert_hoisted_stmt},
 };
 
 /// A reference to the [EcmascriptModuleLocalsModule] variant of an original
@@ -108,10 +108,12 @@ impl CodeGenerateable for EcmascriptModulePartReference {
 
         let referenced_asset = ReferencedAsset::from_resolve_result(self.resolve_reference());
         let referenced_asset = referenced_asset.await?;
-        let ident = referenced_asset
-            .get_ident()
-            .await?
-            .context("part module reference should have an ident")?;
+        let ident = if *chunking_context.stable_import_idents().await? {
+            referenced_asset.get_stable_ident().await?
+        } else {
+            referenced_asset.get_ident().await?
+        }
+        .context("part module reference should have an ident")?;
 
         let ReferencedAsset::Some(module) = *referenced_asset else {
             bail!("part module reference should have an module reference");
@@ -120,6 +122,7 @@ impl CodeGenerateable for EcmascriptModulePartReference {
             .as_chunk_item(Vc::upcast(chunking_context))
             .id()
             .await?;
+        let markers = chunking_context.hoisting_markers().await?;
 
         visitors.push(create_visitor!(visit_mut_program(program: &mut Program) {
             let stmt = quote!(
@@ -130,7 +133,7 @@ impl CodeGenerateable for EcmascriptModulePartReference {
                     ModuleId::Number(n) => (*n as f64).into(),
                 })
             );
-            insert_hoisted_stmt(program, stmt);
+            insert_hoisted_stmt(program, stmt, &markers);
         }));
 
         Ok(CodeGeneration { visitors }.into())
@@ -1,4 +1,4 @@
-use std::{cell::RefCell, collections::HashMap, mem::take};
+use std::{borrow::Cow, cell::RefCell, collections::HashMap, mem::take};
 
 use swc_core::{
     base::SwcComments,
@@ -159,8 +159,11 @@ impl Comments for ImmutableComments {
     }
 }
 
+/// Comments that can be taken out by the SWC Emitter without mutating the
+/// [ImmutableComments] they borrow. Leading comments can be added for
+/// generated code.
 pub struct CowComments<'a> {
-    leading: RefCell<HashMap<BytePos, &'a Vec<Comment>>>,
+    leading: RefCell<HashMap<BytePos, Cow<'a, Vec<Comment>>>>,
     trailing: RefCell<HashMap<BytePos, &'a Vec<Comment>>>,
 }
 
@@ -171,7 +174,7 @@ impl<'a> CowComments<'a> {
                 comments
                     .leading
                     .iter()
-                    .map(|(&key, value)| (key, value))
+                    .map(|(&key, value)| (key, Cow::Borrowed(value)))
                     .collect(),
             ),
             trailing: RefCell::new(
@@ -188,18 +191,23 @@ impl<'a> CowComments<'a> {
 impl<'a> Comments for CowComments<'a> {
     fn add_leading(
         &self,
-        _pos: swc_core::common::BytePos,
-        _cmt: swc_core::common::comments::Comment,
+        pos: swc_core::common::BytePos,
+        cmt: swc_core::common::comments::Comment,
     ) {
-        panic!("Comments are immutable after parsing")
+        self.add_leading_comments(pos, vec![cmt]);
     }
 
     fn add_leading_comments(
         &self,
-        _pos: swc_core::common::BytePos,
-        _comments: Vec<swc_core::common::comments::Comment>,
+        pos: swc_core::common::BytePos,
+        comments: Vec<swc_core::common::comments::Comment>,
     ) {
-        panic!("Comments are immutable after parsing")
+        self.leading
+            .borrow_mut()
+            .entry(pos)
+            .or_default()
+            .to_mut()
+            .extend(comments);
     }
 
     fn has_leading(&self, pos: swc_core::common::BytePos) -> bool {
@@ -214,14 +222,14 @@ impl<'a> Comments for CowComments<'a> {
         &self,
         pos: swc_core::common::BytePos,
     ) -> Option<Vec<swc_core::common::comments::Comment>> {
-        self.leading.borrow_mut().remove(&pos).map(|v| v.to_owned())
+        self.leading.borrow_mut().remove(&pos).map(Cow::into_owned)
     }
 
     fn get_leading(
         &self,
         pos: swc_core::common::BytePos,
     ) -> Option<Vec<swc_core::common::comments::Comment>> {
-        self.leading.borrow().get(&pos).map(|&v| v.to_owned())
+        self.leading.borrow().get(&pos).map(|v| v.to_vec())
     }
 
     fn add_trailing(
@@ -43,7 +43,7 @@ impl EcmascriptChunkItem for EcmascriptModulePartChunkItem {
         let async_module_options = module
             .full_module
             .get_async_module()
-            .module_options(async_module_info);
+            .module_options(async_module_info, false);
 
         let split_data = split_module(module.full_module);
         let parsed = part_of_module(split_data, module.part);
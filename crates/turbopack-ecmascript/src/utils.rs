@@ -46,6 +46,9 @@ pub fn js_value_to_pattern(value: &JsValue) -> Pattern {
     result
 }
 
+/// The literal for `module_id`. Numeric ids are [u32]s, which [f64]
+/// represents exactly, so they're emitted as number literals without losing
+/// precision.
 pub fn module_id_to_lit(module_id: &ModuleId) -> Expr {
     Expr::Lit(match module_id {
         ModuleId::Number(n) => Lit::Num((*n as f64).into()),
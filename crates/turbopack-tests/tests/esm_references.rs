@@ -0,0 +1,2108 @@
+#![cfg(test)]
+
+mod util;
+
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+};
+
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
+use turbo_tasks::{TryJoinIterExt, TurboTasks, Value, ValueToString, Vc};
+use turbo_tasks_fs::{DiskFileSystem, File, FileSystem, FileSystemPath};
+use turbo_tasks_memory::MemoryBackend;
+use turbopack::{
+    ecmascript::{
+        chunk::{
+            EcmascriptChunkItem, EcmascriptChunkPlaceable, EcmascriptChunkingContext,
+            EcmascriptExports,
+        },
+        code_gen::CodeGenerateable,
+        references::{
+            async_module::{AsyncModule, OptionAsyncModule},
+            esm::{
+                EsmAssetReference, EsmAssetReferences, EsmExport, EsmImportedExportsCheck,
+                EsmNamespaceShape, GeneratedImportCounts, OutOfRootPolicy, PrefixRequestTransform,
+                ReferencedAsset, ResolveStatus,
+            },
+        },
+        resolve::browser_import_map,
+        EcmascriptModuleAsset, EcmascriptModuleContent, TreeShakingMode,
+    },
+    module_options::ModuleOptionsContext,
+    resolve_options_context::ResolveOptionsContext,
+    transition::{ContextTransition, Transition},
+    ModuleAssetContext,
+};
+use turbopack_core::{
+    asset::AssetContent,
+    chunk::{
+        availability_info::AvailabilityInfo, AsyncModuleInfo, ChunkItem, ChunkItemExt,
+        ChunkableModule, ChunkableModuleReference, ChunkingContext, ChunkingType, ModuleId,
+    },
+    compile_time_info::CompileTimeInfo,
+    context::AssetContext,
+    environment::{
+        BrowserEnvironment, EdgeWorkerEnvironment, Environment, ExecutionEnvironment,
+        NodeJsEnvironment,
+    },
+    file_source::FileSource,
+    issue::{Issue, IssueDescriptionExt, StyledString},
+    module::Module,
+    reference::ModuleReference,
+    reference_type::ReferenceType,
+    resolve::{
+        options::{ImportMap, ImportMapping},
+        origin::PlainResolveOrigin,
+        parse::Request,
+        ModulePart, ModuleResolveResult, ResolveResult,
+    },
+    virtual_source::VirtualSource,
+};
+use turbopack_dev::{DevChunkingContext, DevChunkingContextBuilder};
+
+use crate::util::REPO_ROOT;
+
+fn register() {
+    turbo_tasks::register();
+    turbo_tasks_fs::register();
+    turbopack::register();
+    turbopack_dev::register();
+    include!(concat!(env!("OUT_DIR"), "/register_test_esm_references.rs"));
+}
+
+/// Runs `test` in a fresh turbo tasks instance.
+async fn run(test: impl Future<Output = Result<()>> + Send + 'static) {
+    register();
+    let tt = TurboTasks::new(MemoryBackend::default());
+    tt.run_once(test).await.unwrap();
+}
+
+/// The root of a file system named `name` for the fixture directory `path`
+/// in `tests/esm_references`.
+fn fixture_root(name: &str, path: &str) -> Vc<FileSystemPath> {
+    DiskFileSystem::new(
+        name.to_string(),
+        format!(
+            "{}/crates/turbopack-tests/tests/esm_references/{path}",
+            *REPO_ROOT
+        ),
+        vec![],
+    )
+    .root()
+}
+
+fn node_environment() -> Vc<Environment> {
+    Environment::new(Value::new(ExecutionEnvironment::NodeJsBuildTime(
+        NodeJsEnvironment::default().into(),
+    )))
+}
+
+fn asset_context(
+    module_options_context: ModuleOptionsContext,
+    resolve_options_context: ResolveOptionsContext,
+) -> Vc<Box<dyn AssetContext>> {
+    Vc::upcast(ModuleAssetContext::new(
+        Vc::cell(HashMap::new()),
+        CompileTimeInfo::new(node_environment()),
+        module_options_context.cell(),
+        resolve_options_context.cell(),
+        Vc::cell("test".to_string()),
+    ))
+}
+
+fn module(
+    asset_context: Vc<Box<dyn AssetContext>>,
+    path: Vc<FileSystemPath>,
+) -> Vc<Box<dyn Module>> {
+    asset_context
+        .process(
+            Vc::upcast(FileSource::new(path)),
+            Value::new(ReferenceType::Undefined),
+        )
+        .module()
+}
+
+/// A development chunking context for `project`, configured by `configure`.
+fn chunking_context(
+    project: Vc<FileSystemPath>,
+    configure: impl FnOnce(DevChunkingContextBuilder) -> DevChunkingContextBuilder,
+) -> Vc<Box<dyn EcmascriptChunkingContext>> {
+    let output = project.join("output".to_string());
+    Vc::upcast(
+        configure(DevChunkingContext::builder(
+            project,
+            output,
+            output,
+            output,
+            output,
+            node_environment(),
+        ))
+        .build(),
+    )
+}
+
+/// The ESM references of `module`, in the order of the imports.
+async fn esm_references(module: Vc<Box<dyn Module>>) -> Result<Vc<EsmAssetReferences>> {
+    let mut references = Vec::new();
+    for &reference in module.references().await?.iter() {
+        if let Some(reference) =
+            Vc::try_resolve_downcast_type::<EsmAssetReference>(reference).await?
+        {
+            references.push(reference);
+        }
+    }
+    Ok(Vc::cell(references))
+}
+
+/// The ESM reference of `module` for the import of `request`.
+async fn esm_reference(
+    module: Vc<Box<dyn Module>>,
+    request: &str,
+) -> Result<Vc<EsmAssetReference>> {
+    for &reference in esm_references(module).await?.await?.iter() {
+        if reference.await?.request.await?.request().as_deref() == Some(request) {
+            return Ok(reference);
+        }
+    }
+    bail!(
+        "{} has no ESM reference for {request}",
+        module.ident().to_string().await?
+    )
+}
+
+/// The requests of `references`.
+async fn requests(references: Vc<EsmAssetReferences>) -> Result<Vec<String>> {
+    references
+        .await?
+        .iter()
+        .map(|reference| async move {
+            Ok(reference
+                .await?
+                .request
+                .await?
+                .request()
+                .unwrap_or_default())
+        })
+        .try_join()
+        .await
+}
+
+/// The statements the code generation of all `references` inserts, see
+/// [EsmAssetReference::preview_statements].
+#[turbo_tasks::function]
+async fn preview_all_statements(
+    references: Vc<EsmAssetReferences>,
+    chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+) -> Result<Vc<Vec<String>>> {
+    let stmts = references
+        .await?
+        .iter()
+        .map(|reference| async move {
+            Ok(reference
+                .preview_statements(chunking_context)
+                .await?
+                .clone_value())
+        })
+        .try_join()
+        .await?;
+    Ok(Vc::cell(stmts.into_iter().flatten().collect()))
+}
+
+/// The ident the statement `stmt` declares, e.g. `a` for `var a = 1;`.
+fn declared_ident(stmt: &str) -> Option<&str> {
+    stmt.strip_prefix("var ")?.split(' ').next()
+}
+
+/// The code of `module` as generated for `chunking_context`.
+async fn module_content(
+    module: Vc<Box<dyn Module>>,
+    chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+) -> Result<Vc<EcmascriptModuleContent>> {
+    let Some(module) = Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(module).await? else {
+        bail!(
+            "{} isn't an ECMAScript module",
+            module.ident().to_string().await?
+        );
+    };
+    Ok(module.module_content(chunking_context, None))
+}
+
+/// The [AsyncModule] of the ECMAScript module `module`.
+async fn async_module(module: Vc<Box<dyn Module>>) -> Result<Vc<AsyncModule>> {
+    let Some(module) = Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(module).await? else {
+        bail!(
+            "{} isn't an ECMAScript module",
+            module.ident().to_string().await?
+        );
+    };
+    let Some(async_module) = *module.analyze().await?.async_module.await? else {
+        bail!("{} has no async module", module.ident().to_string().await?);
+    };
+    Ok(async_module)
+}
+
+/// Whether the chunk item of `module` is async by itself, regardless of the
+/// modules it imports.
+async fn is_self_async(
+    module: Vc<Box<dyn Module>>,
+    chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+) -> Result<bool> {
+    let Some(module) = Vc::try_resolve_sidecast::<Box<dyn ChunkableModule>>(module).await? else {
+        bail!("{} isn't chunkable", module.ident().to_string().await?);
+    };
+    Ok(*module
+        .as_chunk_item(Vc::upcast(chunking_context))
+        .is_self_async()
+        .await?)
+}
+
+/// The descriptions of the issues emitted while computing `source`.
+async fn issue_descriptions<T: ?Sized + Send>(source: Vc<T>) -> Result<Vec<String>> {
+    let source = source.resolve_strongly_consistent().await?;
+    source
+        .peek_issues_with_path()
+        .await?
+        .iter()
+        .map(|issue| async move {
+            Ok(match *issue.description().await? {
+                Some(description) => match &*description.await? {
+                    StyledString::Text(description) => description.clone(),
+                    description => format!("{description:?}"),
+                },
+                None => String::new(),
+            })
+        })
+        .try_join()
+        .await
+}
+
+/// The titles of the issues emitted while computing `source`.
+async fn issue_titles<T: ?Sized + Send>(source: Vc<T>) -> Result<Vec<String>> {
+    let source = source.resolve_strongly_consistent().await?;
+    source
+        .peek_issues_with_path()
+        .await?
+        .iter()
+        .map(|issue| async move {
+            Ok(match &*issue.title().await? {
+                StyledString::Text(title) => title.clone(),
+                title => format!("{title:?}"),
+            })
+        })
+        .try_join()
+        .await
+}
+
+#[tokio::test]
+async fn out_of_root_resolution() {
+    run(async {
+        let project = fixture_root("project", "out-of-root/project");
+        let shared = fixture_root("shared", "out-of-root/shared");
+        let mut import_map = ImportMap::empty();
+        import_map.insert_exact_alias(
+            "shared",
+            ImportMapping::PrimaryAlternative("./index.js".to_string(), Some(shared)).cell(),
+        );
+        let asset_context = asset_context(
+            Default::default(),
+            ResolveOptionsContext {
+                import_map: Some(import_map.cell()),
+                ..Default::default()
+            },
+        );
+        let index = module(asset_context, project.join("index.js".to_string()));
+
+        let local = esm_reference(index, "./local.js").await?;
+        let local = local.with_out_of_root_policy(Value::new(OutOfRootPolicy::Warn));
+        assert!(!*local.is_out_of_root().await?);
+        assert!(issue_titles(local.resolve_reference()).await?.is_empty());
+
+        // The default policy only flags the resolution.
+        let shared = esm_reference(index, "shared").await?;
+        assert!(*shared.is_out_of_root().await?);
+        assert!(issue_titles(shared.resolve_reference()).await?.is_empty());
+
+        let shared = shared.with_out_of_root_policy(Value::new(OutOfRootPolicy::Warn));
+        assert!(*shared.is_out_of_root().await?);
+        assert_eq!(
+            issue_titles(shared.resolve_reference()).await?,
+            ["import outside of the project root"]
+        );
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn import_coverage_instrumentation() {
+    run(async {
+        let project = fixture_root("project", "import-coverage");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let instrumented = chunking_context(project, |builder| {
+            builder.import_coverage_instrumentation(true)
+        });
+        let production = chunking_context(project, |builder| builder);
+
+        // Both the binding import and the side effect import are counted.
+        for request in ["./a.js", "./b.js"] {
+            let reference = esm_reference(index, request).await?;
+            let stmts = reference.preview_statements(instrumented).await?;
+            assert!(stmts
+                .iter()
+                .any(|stmt| stmt.contains("__turbopack_import__(")));
+            assert!(stmts
+                .iter()
+                .any(|stmt| stmt.contains("__turbopack_import_coverage__")));
+
+            let stmts = reference.preview_statements(production).await?;
+            assert!(stmts
+                .iter()
+                .any(|stmt| stmt.contains("__turbopack_import__(")));
+            assert!(!stmts
+                .iter()
+                .any(|stmt| stmt.contains("__turbopack_import_coverage__")));
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn chunk_load_counts() {
+    run(async {
+        let project = fixture_root("project", "chunk-loads");
+        let mut import_map = ImportMap::empty();
+        import_map.insert_exact_alias("external", ImportMapping::External(None).cell());
+        let asset_context = asset_context(
+            Default::default(),
+            ResolveOptionsContext {
+                import_map: Some(import_map.cell()),
+                ..Default::default()
+            },
+        );
+        let index = module(asset_context, project.join("index.js".to_string()));
+
+        let counts = esm_references(index).await?.chunk_load_counts().await?;
+        assert_eq!(counts.same_chunk, 1);
+        assert_eq!(counts.cross_chunk, 1);
+        assert_eq!(counts.external, 1);
+        assert_eq!(counts.unresolvable, 1);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn imported_modules_with_the_same_path() {
+    run(async {
+        let project = fixture_root("project", "same-path");
+        // Two distinct modules with the same path.
+        let virtual_module = |code: &str| {
+            let source = VirtualSource::new(
+                project.join("virtual.js".to_string()),
+                AssetContent::file(File::from(code).into()),
+            );
+            ImportMapping::Direct(ResolveResult::source(Vc::upcast(source)).cell()).cell()
+        };
+        let mut import_map = ImportMap::empty();
+        import_map.insert_exact_alias("virtual-a", virtual_module("export const a = 1;"));
+        import_map.insert_exact_alias("virtual-b", virtual_module("export const b = 2;"));
+        let asset_context = asset_context(
+            Default::default(),
+            ResolveOptionsContext {
+                import_map: Some(import_map.cell()),
+                ..Default::default()
+            },
+        );
+        let index = module(asset_context, project.join("index.js".to_string()));
+        let chunking_context = chunking_context(project, |builder| builder);
+
+        let mut idents = Vec::new();
+        for request in ["virtual-a", "virtual-b"] {
+            let stmts = esm_reference(index, request)
+                .await?
+                .preview_statements(chunking_context)
+                .await?;
+            let import = stmts
+                .iter()
+                .find(|stmt| stmt.contains("__turbopack_import__("))
+                .unwrap();
+            idents.push(declared_ident(import).unwrap().to_string());
+        }
+        assert_ne!(idents[0], idents[1]);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn imported_module_parts() {
+    run(async {
+        let project = fixture_root("project", "module-parts");
+        let asset_context = asset_context(
+            ModuleOptionsContext {
+                tree_shaking_mode: Some(TreeShakingMode::ReexportsOnly),
+                ..Default::default()
+            },
+            Default::default(),
+        );
+        let index = module(asset_context, project.join("index.js".to_string()));
+        let chunking_context = chunking_context(project, |builder| builder);
+
+        // Each import of a part of `./m.js` binds its own ident.
+        let mut idents = Vec::new();
+        for reference in esm_references(index).await?.await?.iter() {
+            let stmts = reference.preview_statements(chunking_context).await?;
+            let import = stmts
+                .iter()
+                .find(|stmt| stmt.contains("__turbopack_import__("))
+                .unwrap();
+            idents.push(declared_ident(import).unwrap().to_string());
+        }
+        assert_eq!(idents.len(), 2);
+        assert_ne!(idents[0], idents[1]);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn several_parts_through_one_reference() {
+    run(async {
+        let project = fixture_root("project", "module-parts");
+        let asset_context = asset_context(
+            ModuleOptionsContext {
+                tree_shaking_mode: Some(TreeShakingMode::ReexportsOnly),
+                ..Default::default()
+            },
+            Default::default(),
+        );
+        let reference = EsmAssetReference::new_with_export_names(
+            Vc::upcast(PlainResolveOrigin::new(
+                asset_context,
+                project.join("index.js".to_string()),
+            )),
+            Request::parse_string("./m.js".to_string()),
+            None,
+            Value::new(Default::default()),
+            vec![
+                ModulePart::export("a".to_string()),
+                ModulePart::export("b".to_string()),
+            ],
+            false,
+        );
+
+        // Every part is resolved to a module of its own.
+        let modules = reference.resolve_reference().primary_modules().await?;
+        assert_eq!(modules.len(), 2);
+        assert_ne!(modules[0], modules[1]);
+
+        // The imported names are checked against the whole module.
+        let check = EsmImportedExportsCheck::new(vec![(
+            reference,
+            vec!["a".to_string(), "missing".to_string()],
+        )])
+        .code_generation(chunking_context(project, |builder| builder));
+        assert_eq!(
+            issue_descriptions(check).await?,
+            ["The following exports were not found in ./m.js:\n  - missing"]
+        );
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn chunked_and_unchunked_references() {
+    run(async {
+        let project = fixture_root("project", "chunking-types");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let references = esm_references(index).await?;
+
+        assert_eq!(
+            requests(references.chunked()).await?,
+            ["./parallel.js", "./default.js"]
+        );
+        assert_eq!(requests(references.unchunked()).await?, ["./none.js"]);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn chunking_types_per_environment() {
+    run(async {
+        let project = fixture_root("project", "chunking-types");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let browser = Environment::new(Value::new(ExecutionEnvironment::Browser(
+            BrowserEnvironment {
+                dom: true,
+                web_worker: false,
+                service_worker: false,
+                browserslist_query: String::new(),
+            }
+            .into(),
+        )));
+        let output = project.join("output".to_string());
+        let browser_chunking_context: Vc<Box<dyn ChunkingContext>> = Vc::upcast(
+            DevChunkingContext::builder(project, output, output, output, output, browser).build(),
+        );
+        // The server loads the modules of unannotated imports by other means.
+        let server_chunking_context: Vc<Box<dyn ChunkingContext>> =
+            Vc::upcast(chunking_context(project, |builder| {
+                builder.chunk_unannotated_references(false)
+            }));
+        let chunking_types = |request| async move {
+            let reference = esm_reference(index, request).await?;
+            anyhow::Ok((
+                *reference
+                    .chunking_type_for(browser_chunking_context)
+                    .await?,
+                *reference.chunking_type_for(server_chunking_context).await?,
+            ))
+        };
+
+        assert!(matches!(
+            chunking_types("./default.js").await?,
+            (Some(ChunkingType::ParallelInheritAsync), None)
+        ));
+        // Annotations take precedence.
+        assert!(matches!(
+            chunking_types("./parallel.js").await?,
+            (
+                Some(ChunkingType::ParallelInheritAsync),
+                Some(ChunkingType::ParallelInheritAsync)
+            )
+        ));
+        assert!(matches!(chunking_types("./none.js").await?, (None, None)));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn external_references_contribute_to_async() {
+    run(async {
+        let project = fixture_root("project", "externals");
+        let index = |import_externals| {
+            let mut import_map = ImportMap::empty();
+            import_map.insert_exact_alias("external", ImportMapping::External(None).cell());
+            let asset_context = asset_context(
+                ModuleOptionsContext {
+                    import_externals,
+                    ..Default::default()
+                },
+                ResolveOptionsContext {
+                    import_map: Some(import_map.cell()),
+                    ..Default::default()
+                },
+            );
+            module(asset_context, project.join("index.js".to_string()))
+        };
+        let chunking_context = chunking_context(project, |builder| builder);
+        let async_module_info = AsyncModuleInfo::new(vec![]);
+
+        // Externals are async when they're imported or when the environment
+        // supports ESM externals.
+        for import_externals in [true, false] {
+            let index = index(import_externals);
+            for esm_externals in [true, false] {
+                let external = esm_reference(index, "external").await?;
+                assert_eq!(
+                    *external
+                        .contributes_to_async(chunking_context, async_module_info, esm_externals)
+                        .await?,
+                    import_externals || esm_externals
+                );
+                let local = esm_reference(index, "./local.js").await?;
+                assert!(
+                    !*local
+                        .contributes_to_async(chunking_context, async_module_info, esm_externals)
+                        .await?
+                );
+            }
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn request_transform() {
+    run(async {
+        let project = fixture_root("project", "request-transform");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+
+        let reference = esm_reference(index, "@/utils").await?;
+        assert!(*reference.is_unresolveable().await?);
+
+        let reference = reference.with_request_transform(Vc::upcast(PrefixRequestTransform::new(
+            "@/".to_string(),
+            "./src/".to_string(),
+        )));
+        let Some(module) = *reference.resolve_reference().first_module().await? else {
+            bail!("@/utils doesn't resolve to a module");
+        };
+        assert_eq!(module.ident().path().await?.path, "src/utils.js");
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn explicit_transition_overrides_annotation() {
+    run(async {
+        let project = fixture_root("project", "transitions");
+        let compile_time_info = CompileTimeInfo::new(node_environment());
+        let module_options_context = ModuleOptionsContext::default().cell();
+        let resolve_options_context = ResolveOptionsContext::default().cell();
+        let transition = |layer: &str| {
+            Vc::upcast::<Box<dyn Transition>>(ContextTransition::new(
+                compile_time_info,
+                module_options_context,
+                resolve_options_context,
+                Vc::cell(layer.to_string()),
+            ))
+        };
+        let transitions = HashMap::from([
+            ("annotated".to_string(), transition("annotated-layer")),
+            ("explicit".to_string(), transition("explicit-layer")),
+        ]);
+        let asset_context = Vc::upcast(ModuleAssetContext::new(
+            Vc::cell(transitions),
+            compile_time_info,
+            module_options_context,
+            resolve_options_context,
+            Vc::cell("test".to_string()),
+        ));
+        let index = module(asset_context, project.join("index.js".to_string()));
+        let reference = esm_reference(index, "./a.js").await?;
+
+        let layer = |result: Vc<ModuleResolveResult>| async move {
+            let Some(module) = *result.first_module().await? else {
+                bail!("./a.js doesn't resolve to a module");
+            };
+            let Some(layer) = module.ident().await?.layer else {
+                bail!("./a.js is resolved without a layer");
+            };
+            Ok(layer.await?.clone_value())
+        };
+        assert_eq!(
+            layer(reference.resolve_reference()).await?,
+            "annotated-layer"
+        );
+        assert_eq!(
+            layer(reference.resolve_reference_with_transition(None)).await?,
+            "annotated-layer"
+        );
+        assert_eq!(
+            layer(reference.resolve_reference_with_transition(Some("explicit".to_string())))
+                .await?,
+            "explicit-layer"
+        );
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn resolve_all_references() {
+    run(async {
+        let project = fixture_root("project", "resolve-all");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+
+        let resolved = esm_references(index).await?.resolve_all().await?;
+        let paths = resolved
+            .results
+            .iter()
+            .map(|result| async move {
+                Ok(match *result.first_module().await? {
+                    Some(module) => Some(module.ident().path().await?.path.clone()),
+                    None => None,
+                })
+            })
+            .try_join()
+            .await?;
+        assert_eq!(
+            paths,
+            [
+                Some("a.js".to_string()),
+                None,
+                Some("b.js".to_string()),
+                None
+            ]
+        );
+        assert_eq!(
+            requests(Vc::cell(resolved.unresolvable.clone())).await?,
+            ["./missing.js", "./also-missing.js"]
+        );
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn imports_with_queries() {
+    run(async {
+        let project = fixture_root("project", "queries");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let chunking_context = chunking_context(project, |builder| builder);
+
+        // `./shared.js?a` and `./shared.js?b` resolve to distinct modules with
+        // distinct bindings.
+        let mut modules = Vec::new();
+        let mut idents = Vec::new();
+        for reference in esm_references(index).await?.await?.iter() {
+            let Some(module) = *reference.resolve_reference().first_module().await? else {
+                bail!(
+                    "{} doesn't resolve to a module",
+                    reference.to_string().await?
+                );
+            };
+            modules.push(module.ident().to_string().await?.clone_value());
+            let stmts = reference.preview_statements(chunking_context).await?;
+            let import = stmts
+                .iter()
+                .find(|stmt| stmt.contains("__turbopack_import__("))
+                .unwrap();
+            idents.push(declared_ident(import).unwrap().to_string());
+        }
+        assert_eq!(modules.len(), 2);
+        assert_ne!(modules[0], modules[1]);
+        assert_ne!(idents[0], idents[1]);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn generated_import_counts() {
+    run(async {
+        let project = fixture_root("project", "chunk-loads");
+        let mut import_map = ImportMap::empty();
+        import_map.insert_exact_alias("external", ImportMapping::External(None).cell());
+        let asset_context = asset_context(
+            Default::default(),
+            ResolveOptionsContext {
+                import_map: Some(import_map.cell()),
+                ..Default::default()
+            },
+        );
+        let index = module(asset_context, project.join("index.js".to_string()));
+        let chunking_context = chunking_context(project, |builder| builder);
+
+        let stmts = preview_all_statements(esm_references(index).await?, chunking_context)
+            .resolve_strongly_consistent()
+            .await?;
+        let counts = GeneratedImportCounts::collect(stmts).await?;
+        assert_eq!(counts.internal, 1);
+        assert_eq!(counts.cross_chunk, 1);
+        assert_eq!(counts.external_require, 1);
+        assert_eq!(counts.external_import, 0);
+        assert_eq!(counts.unresolvable, 1);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn references_grouped_by_module() {
+    run(async {
+        let project = fixture_root("project", "group-by-module");
+        let mut import_map = ImportMap::empty();
+        import_map.insert_exact_alias("external", ImportMapping::External(None).cell());
+        let asset_context = asset_context(
+            Default::default(),
+            ResolveOptionsContext {
+                import_map: Some(import_map.cell()),
+                ..Default::default()
+            },
+        );
+        let index = module(asset_context, project.join("index.js".to_string()));
+
+        let groups = esm_references(index).await?.group_by_module().await?;
+        let mut grouped_requests = Vec::new();
+        for references in groups.values() {
+            grouped_requests.push(requests(Vc::cell(references.clone())).await?);
+        }
+        assert_eq!(
+            grouped_requests,
+            [
+                vec!["./shared.js", "./shared"],
+                vec!["external"],
+                vec!["./other.js"],
+            ]
+        );
+        assert_eq!(groups.get_index(1).unwrap().0.as_str(), "external");
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn referenced_module_ids() {
+    run(async {
+        let project = fixture_root("project", "module-ids");
+        let mut import_map = ImportMap::empty();
+        import_map.insert_exact_alias("external", ImportMapping::External(None).cell());
+        let asset_context = asset_context(
+            Default::default(),
+            ResolveOptionsContext {
+                import_map: Some(import_map.cell()),
+                ..Default::default()
+            },
+        );
+        let index = module(asset_context, project.join("index.js".to_string()));
+        let chunking_context = chunking_context(project, |builder| builder);
+
+        // Development module ids are the idents of the modules.
+        let mut expected = Vec::new();
+        for request in ["./a.js", "./b.js"] {
+            let Some(module) = *esm_reference(index, request)
+                .await?
+                .resolve_reference()
+                .first_module()
+                .await?
+            else {
+                bail!("{request} doesn't resolve to a module");
+            };
+            expected.push(ModuleId::String(
+                module.ident().to_string().await?.clone_value(),
+            ));
+        }
+
+        let ids = esm_references(index)
+            .await?
+            .referenced_module_ids(chunking_context)
+            .await?
+            .iter()
+            .map(|id| async move { Ok(id.await?.clone_value()) })
+            .try_join()
+            .await?;
+        assert_eq!(ids, expected);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn namespace_import_shape() {
+    run(async {
+        let project = fixture_root("project", "namespace-shape");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let namespace_import = |chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+                                request: &'static str| async move {
+            let stmts = esm_reference(index, request)
+                .await?
+                .preview_statements(chunking_context)
+                .await?;
+            let Some(import) = stmts
+                .iter()
+                .find(|stmt| stmt.contains("__turbopack_import_namespace__("))
+            else {
+                bail!("{request} isn't imported as a namespace");
+            };
+            Ok(import.clone())
+        };
+
+        // Namespace objects are extensible by default.
+        let extensible = chunking_context(project, |builder| builder);
+        for request in ["./esm.js", "./cjs.js"] {
+            assert!(namespace_import(extensible, request)
+                .await?
+                .ends_with("\");"));
+        }
+
+        // Only namespace objects of modules with static ESM exports are frozen.
+        let frozen = chunking_context(project, |builder| {
+            builder.esm_namespace_shape(EsmNamespaceShape::Frozen)
+        });
+        assert!(namespace_import(frozen, "./esm.js")
+            .await?
+            .ends_with("\", true);"));
+        assert!(namespace_import(frozen, "./cjs.js")
+            .await?
+            .ends_with("\");"));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn missing_exports_grouped_by_module() {
+    run(async {
+        let project = fixture_root("project", "missing-exports");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let chunking_context = chunking_context(project, |builder| builder);
+
+        // Both imports of `./x.js` are reported together.
+        let content = module_content(index, chunking_context).await?;
+        assert_eq!(issue_titles(content).await?, ["missing exports"]);
+        assert_eq!(
+            issue_descriptions(content).await?,
+            ["The following exports were not found in ./x.js:\n  - missing\n  - other"]
+        );
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn rewritten_request() {
+    run(async {
+        let project = fixture_root("project", "request-rewrite");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let reference = esm_reference(index, "./old.js").await?;
+
+        let rewritten = reference.with_request(Request::parse_string("./new.js".to_string()));
+        let Some(module) = *rewritten.resolve_reference().first_module().await? else {
+            bail!("./new.js doesn't resolve to a module");
+        };
+        assert_eq!(module.ident().path().await?.path, "new.js");
+
+        // Everything but the request is preserved.
+        let (reference, rewritten) = (reference.await?, rewritten.await?);
+        assert_eq!(
+            rewritten.request.await?.request().as_deref(),
+            Some("./new.js")
+        );
+        assert_eq!(rewritten.origin, reference.origin);
+        assert_eq!(rewritten.annotations, reference.annotations);
+        assert_eq!(rewritten.annotations.chunking_type(), Some("isolated"));
+        assert_eq!(rewritten.issue_source, reference.issue_source);
+        assert_eq!(rewritten.export_names, reference.export_names);
+        assert_eq!(rewritten.namespace_import, reference.namespace_import);
+        assert!(rewritten.namespace_import);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn prefetch_when_idle() {
+    run(async {
+        let project = fixture_root("project", "prefetch-when-idle");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let chunking_context = chunking_context(project, |builder| builder);
+
+        let stmts = esm_reference(index, "./a.js")
+            .await?
+            .preview_statements(chunking_context)
+            .await?;
+        let position = |function: &str| {
+            stmts
+                .iter()
+                .position(|stmt| stmt.contains(&format!("{function}(")))
+        };
+        let (Some(import), Some(prefetch)) = (
+            position("__turbopack_import__"),
+            position("__turbopack_prefetch__"),
+        ) else {
+            bail!("expected an import and a prefetch, got {stmts:?}");
+        };
+        // The prefetch doesn't block evaluation, so it follows the binding.
+        assert!(import < prefetch);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn esm_externals_loaded_with_require() {
+    run(async {
+        let project = fixture_root("project", "required-externals");
+        let chunking_context = chunking_context(project, |builder| builder);
+        let index = |import_externals| {
+            let mut import_map = ImportMap::empty();
+            for package in ["esm-pkg", "cjs-pkg"] {
+                import_map.insert_exact_alias(package, ImportMapping::External(None).cell());
+            }
+            let asset_context = asset_context(
+                ModuleOptionsContext {
+                    import_externals,
+                    ..Default::default()
+                },
+                ResolveOptionsContext {
+                    import_map: Some(import_map.cell()),
+                    ..Default::default()
+                },
+            );
+            module(asset_context, project.join("index.js".to_string()))
+        };
+
+        // Only the genuine ESM external loses its live bindings when it's
+        // required.
+        let content = module_content(index(false), chunking_context).await?;
+        assert_eq!(
+            issue_titles(content).await?,
+            ["ESM external loaded with require"]
+        );
+        let descriptions = issue_descriptions(content).await?;
+        assert!(descriptions[0].contains("esm-pkg"));
+
+        let content = module_content(index(true), chunking_context).await?;
+        assert!(issue_titles(content).await?.is_empty());
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn data_url_requests() {
+    run(async {
+        let project = fixture_root("project", "data-urls");
+        let mut import_map = ImportMap::empty();
+        import_map.insert_exact_alias(
+            "mapped",
+            ImportMapping::External(Some("data:application/json,2".to_string())).cell(),
+        );
+        let index = module(
+            asset_context(
+                Default::default(),
+                ResolveOptionsContext {
+                    import_map: Some(import_map.cell()),
+                    ..Default::default()
+                },
+            ),
+            project.join("index.js".to_string()),
+        );
+
+        // Only a `data:` URL request is evaluated inline. An external whose
+        // name is a `data:` URL is still loaded as an external.
+        let inline = esm_reference(index, "data:application/json,1").await?;
+        assert_eq!(*inline.resolve_status().await?, ResolveStatus::Internal);
+        let mapped = esm_reference(index, "mapped").await?;
+        assert_eq!(*mapped.resolve_status().await?, ResolveStatus::External);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn placeable_of_referenced_assets() {
+    run(async {
+        let project = fixture_root("project", "referenced-assets");
+        let mut import_map = ImportMap::empty();
+        import_map.insert_exact_alias("external", ImportMapping::External(None).cell());
+        let index = module(
+            asset_context(
+                Default::default(),
+                ResolveOptionsContext {
+                    import_map: Some(import_map.cell()),
+                    ..Default::default()
+                },
+            ),
+            project.join("index.js".to_string()),
+        );
+        let referenced_asset = |request: &'static str| async move {
+            let referenced_asset = esm_reference(index, request)
+                .await?
+                .get_referenced_asset()
+                .resolve()
+                .await?;
+            anyhow::Ok((
+                referenced_asset.await?,
+                *referenced_asset.as_placeable().await?,
+            ))
+        };
+
+        // Only internal modules have a placeable.
+        let (local, placeable) = referenced_asset("./local.js").await?;
+        let ReferencedAsset::Some(local) = &*local else {
+            bail!("./local.js isn't an internal module");
+        };
+        assert_eq!(placeable, Some(*local));
+
+        for request in [
+            "external",
+            "data:text/javascript,export default 1",
+            "./missing.js",
+        ] {
+            let (asset, placeable) = referenced_asset(request).await?;
+            assert!(!matches!(*asset, ReferencedAsset::Some(_)));
+            assert_eq!(placeable, None);
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn strict_import_annotations() {
+    run(async {
+        let project = fixture_root("project", "strict-annotations");
+        let reference = |strict_import_annotations| {
+            let index = module(
+                asset_context(
+                    ModuleOptionsContext {
+                        strict_import_annotations,
+                        ..Default::default()
+                    },
+                    Default::default(),
+                ),
+                project.join("index.js".to_string()),
+            );
+            esm_reference(index, "./a.js")
+        };
+
+        // An unknown chunking type falls back to the default one with a
+        // warning, unless annotations are strict.
+        let chunking_type = reference(false).await?.chunking_type();
+        assert!(chunking_type.await?.is_some());
+        assert_eq!(
+            issue_titles(chunking_type).await?,
+            ["unknown chunking-type annotation"]
+        );
+
+        let Err(error) = reference(true).await?.chunking_type().await else {
+            bail!("the unknown chunking type didn't fail");
+        };
+        assert!(format!("{error:#}").contains("unknown chunking_type: unknown"));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn named_imports_of_isolated_modules() {
+    run(async {
+        let project = fixture_root("project", "isolated-bindings");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+
+        // The async loader has no `value`, so the isolated named import is an
+        // error and m.js is imported with the default chunking type instead.
+        assert_eq!(
+            issue_titles(index.references()).await?,
+            ["named import of an isolated module"]
+        );
+        let named = esm_reference(index, "./m.js").await?;
+        assert_eq!(named.await?.annotations.chunking_type(), None);
+        assert_eq!(
+            *named.chunking_type().await?,
+            Some(ChunkingType::ParallelInheritAsync)
+        );
+        // Namespace imports still bind the async loader.
+        let namespace = esm_reference(index, "./loader.js").await?;
+        assert_eq!(*namespace.chunking_type().await?, Some(ChunkingType::Async));
+
+        // `value` is read from m.js, not from its async loader.
+        let content = module_content(index, chunking_context(project, |builder| builder))
+            .await?
+            .await?;
+        let code = content.inner_code.to_str()?;
+        assert!(code.contains("m.js (ecmascript)\")"));
+        assert!(!code.contains("m.js (ecmascript, loader)"));
+        assert!(code.contains("[\"value\"]"));
+        assert!(code.contains("loader.js (ecmascript, loader)"));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn unresolvable_references() {
+    run(async {
+        let project = fixture_root("project", "unresolvable");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+
+        let existing = esm_reference(index, "./a.js").await?;
+        assert!(!*existing.is_unresolveable().await?);
+        let missing = esm_reference(index, "./missing.js").await?;
+        assert!(*missing.is_unresolveable().await?);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn async_module_references_in_insertion_order() {
+    run(async {
+        let project = fixture_root("project", "async-references");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+
+        let references = async_module(index).await?.references();
+        assert_eq!(
+            requests(references).await?,
+            vec!["./b.js", "./a.js", "./c.js"]
+        );
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn strict_module_options() {
+    run(async {
+        let project = fixture_root("project", "async-references");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let async_module = Vc::<OptionAsyncModule>::cell(Some(async_module(index).await?));
+
+        // Known to be sync, as none of the imported modules is async.
+        let options = async_module.module_options(Some(AsyncModuleInfo::new(vec![])), true);
+        assert!(options.await?.is_none());
+
+        // Unknown, which is only treated as sync when not strict.
+        assert!(async_module.module_options(None, true).await.is_err());
+        assert!(async_module.module_options(None, false).await?.is_none());
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn plain_and_namespace_import_share_one_binding() {
+    run(async {
+        let project = fixture_root("project", "plain-and-namespace");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let chunking_context = chunking_context(project, |builder| builder);
+
+        let stmts = preview_all_statements(esm_references(index).await?, chunking_context).await?;
+        let bindings = stmts
+            .iter()
+            .filter(|stmt| declared_ident(stmt).is_some())
+            .collect::<Vec<_>>();
+        assert_eq!(bindings.len(), 1);
+        assert!(bindings[0].contains("__turbopack_import_namespace__("));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn self_async_modules() {
+    run(async {
+        let project = fixture_root("project", "externals");
+        let chunking_context = chunking_context(project, |builder| builder);
+        for import_externals in [true, false] {
+            let mut import_map = ImportMap::empty();
+            import_map.insert_exact_alias("external", ImportMapping::External(None).cell());
+            let asset_context = asset_context(
+                ModuleOptionsContext {
+                    import_externals,
+                    ..Default::default()
+                },
+                ResolveOptionsContext {
+                    import_map: Some(import_map.cell()),
+                    ..Default::default()
+                },
+            );
+            let index = module(asset_context, project.join("index.js".to_string()));
+            // Only imported externals are async in the Node.js environment.
+            assert_eq!(
+                is_self_async(index, chunking_context).await?,
+                import_externals
+            );
+            // The local module imports no externals.
+            let local = module(asset_context, project.join("local.js".to_string()));
+            assert!(!is_self_async(local, chunking_context).await?);
+        }
+
+        let project = fixture_root("project", "top-level-await");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        assert!(is_self_async(index, chunking_context(project, |builder| builder)).await?);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn eager_imports() {
+    run(async {
+        let project = fixture_root("project", "eager");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let preview = |request: &'static str, inline_eager_json_imports: bool| async move {
+            let chunking_context = chunking_context(project, |builder| {
+                builder.inline_eager_json_imports(inline_eager_json_imports)
+            });
+            let stmts = esm_reference(index, request)
+                .await?
+                .preview_statements(chunking_context);
+            anyhow::Ok((stmts.await?.join("\n"), issue_titles(stmts).await?))
+        };
+
+        // Only the default import of a JSON module can be inlined.
+        let (stmts, titles) = preview("./data.json", true).await?;
+        assert!(stmts.contains("Object.freeze("));
+        assert!(!stmts.contains("__turbopack_import__("));
+        assert!(titles.is_empty());
+
+        let (stmts, titles) = preview("./hot.js", true).await?;
+        assert!(stmts.contains("__turbopack_import__("));
+        assert_eq!(titles, ["eager import not inlined"]);
+
+        // Without support, eager imports are parallel ones.
+        let (stmts, titles) = preview("./data.json", false).await?;
+        assert!(stmts.contains("__turbopack_import__("));
+        assert_eq!(titles, ["eager import not inlined"]);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn inline_small_json_imports() {
+    run(async {
+        let project = fixture_root("project", "inline-json");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let import = |request: &'static str, inline_json_max_size: Option<u32>| async move {
+            let chunking_context = chunking_context(project, |builder| {
+                builder.inline_json_max_size(inline_json_max_size)
+            });
+            let reference = esm_reference(index, request).await?;
+            let stmts = reference.preview_statements(chunking_context).await?;
+            anyhow::Ok((
+                stmts.join("\n"),
+                *reference
+                    .chunking_type_for(Vc::upcast(chunking_context))
+                    .await?,
+            ))
+        };
+
+        // The small module is inlined instead of being chunked. Both the
+        // namespace object and the value are frozen.
+        let (stmts, chunking_type) = import("./small.json", Some(64)).await?;
+        assert_eq!(stmts.matches("Object.freeze(").count(), 2);
+        assert!(!stmts.contains("__turbopack_import__("));
+        assert!(chunking_type.is_none());
+
+        let (stmts, chunking_type) = import("./large.json", Some(64)).await?;
+        assert!(stmts.contains("__turbopack_import__("));
+        assert!(matches!(
+            chunking_type,
+            Some(ChunkingType::ParallelInheritAsync)
+        ));
+
+        // Without a size, JSON modules are always imported.
+        let (stmts, chunking_type) = import("./small.json", None).await?;
+        assert!(stmts.contains("__turbopack_import__("));
+        assert!(matches!(
+            chunking_type,
+            Some(ChunkingType::ParallelInheritAsync)
+        ));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn reserved_names_in_async_modules() {
+    run(async {
+        let project = fixture_root("project", "reserved-names");
+        let asset_context = asset_context(Default::default(), Default::default());
+        let chunking_context = chunking_context(project, |builder| builder);
+
+        // Only async modules get the wrapper binding the reserved names.
+        let content = module_content(
+            module(asset_context, project.join("async.js".to_string())),
+            chunking_context,
+        )
+        .await?;
+        assert_eq!(issue_titles(content).await?, ["reserved turbopack name"]);
+
+        let content = module_content(
+            module(asset_context, project.join("sync.js".to_string())),
+            chunking_context,
+        )
+        .await?;
+        assert!(issue_titles(content).await?.is_empty());
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn esm_cycle_checks() {
+    run(async {
+        let project = fixture_root("project", "cycle");
+        let a = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("a.js".to_string()),
+        );
+        let preview = |request: &'static str, esm_cycle_checks: bool| async move {
+            let chunking_context = chunking_context(project, |builder| {
+                builder.esm_cycle_checks(esm_cycle_checks)
+            });
+            let stmts = esm_reference(a, request)
+                .await?
+                .preview_statements(chunking_context)
+                .await?;
+            anyhow::Ok(stmts.join("\n"))
+        };
+
+        // `./b.js` imports `./a.js` back, `./c.js` doesn't.
+        assert!(preview("./b.js", true).await?.contains("Circular import"));
+        assert!(!preview("./c.js", true).await?.contains("Circular import"));
+        assert!(!preview("./b.js", false).await?.contains("Circular import"));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn esm_live_bindings() {
+    run(async {
+        let project = fixture_root("project", "live-bindings");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let code = |esm_live_bindings: bool| async move {
+            let chunking_context = chunking_context(project, |builder| {
+                builder.esm_live_bindings(esm_live_bindings)
+            });
+            let content = module_content(index, chunking_context).await?.await?;
+            anyhow::Ok(content.inner_code.to_str()?.into_owned())
+        };
+
+        // Named imports read the export on every access.
+        let code_with_live_bindings = code(true).await?;
+        assert!(code_with_live_bindings.contains("__turbopack_esm_import__("));
+        assert!(code_with_live_bindings.contains("\"count\")"));
+        assert!(!code(false).await?.contains("__turbopack_esm_import__("));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn unused_imports_of_side_effect_free_modules() {
+    run(async {
+        let project = fixture_root("project", "side-effects");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let content = module_content(index, chunking_context(project, |builder| builder))
+            .await?
+            .await?;
+        let code = content.inner_code.to_str()?;
+
+        // The unused import of a side-effect free module is dropped.
+        assert!(!code.contains("pure/index.js"));
+        // Unused imports of modules with side effects and used imports are kept.
+        assert!(code.contains("effectful/index.js"));
+        assert!(code.contains("pure/used.js"));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn import_target_chunks() {
+    run(async {
+        let project = fixture_root("project", "target-chunks");
+        let asset_context = asset_context(Default::default(), Default::default());
+        let index = module(asset_context, project.join("index.js".to_string()));
+        let isolated = module(asset_context, project.join("isolated.js".to_string()));
+        let Some(isolated) = Vc::try_resolve_sidecast::<Box<dyn ChunkableModule>>(isolated).await?
+        else {
+            bail!("isolated.js isn't chunkable");
+        };
+        // The import of isolated.js by its async loader, and the id it imports.
+        let loader_import = |import_target_chunks| async move {
+            let chunking_context: Vc<Box<dyn ChunkingContext>> =
+                Vc::upcast(chunking_context(project, |builder| {
+                    builder.import_target_chunks(import_target_chunks)
+                }));
+            let loader = chunking_context
+                .async_loader_chunk_item(isolated, Value::new(AvailabilityInfo::Root));
+            let Some(loader) =
+                Vc::try_resolve_sidecast::<Box<dyn EcmascriptChunkItem>>(loader).await?
+            else {
+                bail!("the async loader isn't an ECMAScript chunk item");
+            };
+            let code = loader.content().await?.inner_code.to_str()?.into_owned();
+            let Some(import) = code
+                .lines()
+                .map(str::trim)
+                .find(|line| line.starts_with("return __turbopack_import__("))
+            else {
+                bail!("the async loader doesn't import isolated.js");
+            };
+            let id = isolated.as_chunk_item(chunking_context).id().await?;
+            anyhow::Ok((import.to_string(), serde_json::to_string(&*id)?))
+        };
+
+        // The loader passes the chunk containing isolated.js along with its id.
+        let (import, id) = loader_import(true).await?;
+        let prefix = format!("return __turbopack_import__({id}, \"");
+        assert!(import.starts_with(&prefix), "{import}");
+        assert!(import.ends_with(".js\");"), "{import}");
+        let (import, id) = loader_import(false).await?;
+        assert_eq!(import, format!("return __turbopack_import__({id});"));
+
+        // The module binds the loader, which is in its own chunk, so its imports
+        // don't pass a chunk.
+        let chunking_context =
+            chunking_context(project, |builder| builder.import_target_chunks(true));
+        for request in ["./isolated.js", "./same.js"] {
+            let stmts = esm_reference(index, request)
+                .await?
+                .preview_statements(chunking_context)
+                .await?;
+            let Some(import) = stmts
+                .iter()
+                .find(|stmt| stmt.contains("__turbopack_import__("))
+            else {
+                bail!("index.js doesn't import {request}");
+            };
+            assert!(!import.contains(".js\")"), "{import}");
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn renamed_reexports() {
+    run(async {
+        let project = fixture_root("project", "renamed-reexports");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let Some(index) = Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(index).await?
+        else {
+            bail!("index.js isn't an ECMAScript module");
+        };
+        let EcmascriptExports::EsmExports(exports) = *index.get_exports().await? else {
+            bail!("index.js doesn't have ESM exports");
+        };
+        let exports = exports.await?;
+
+        // `foo` is only exposed as `bar`, which binds the `foo` export of m.js.
+        assert_eq!(exports.exports.keys().collect::<Vec<_>>(), ["bar"]);
+        let EsmExport::ImportedBinding(reference, name) = &exports.exports["bar"] else {
+            bail!("bar isn't an imported binding");
+        };
+        assert_eq!(name, "foo");
+        // The string form of the reference is a cache key, the renaming isn't
+        // part of it.
+        assert_eq!(reference.to_string().await?.as_str(), "import ./m.js {}");
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn forced_conditions() {
+    run(async {
+        let project = fixture_root("project", "conditions");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+
+        // Both imports request `pkg`, the annotated one with the `worker`
+        // condition instead of the ones of the resolve options.
+        let mut targets = Vec::new();
+        for reference in esm_references(index).await?.await?.iter() {
+            let modules = reference.resolve_reference().primary_modules().await?;
+            targets.push(modules[0].ident().path().await?.path.clone());
+        }
+        assert_eq!(
+            targets,
+            ["node_modules/pkg/index.js", "node_modules/pkg/worker.js"]
+        );
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn duplicate_imports_share_one_binding() {
+    run(async {
+        let project = fixture_root("project", "duplicate-imports");
+        let index = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("index.js".to_string()),
+        );
+        let chunking_context = chunking_context(project, |builder| builder);
+
+        // Both imports of `./m.js` remain references of the module.
+        assert_eq!(
+            requests(esm_references(index).await?).await?,
+            vec!["./m.js", "./m.js"]
+        );
+        let content = module_content(index, chunking_context).await?.await?;
+        let code = content.inner_code.to_str()?;
+        assert_eq!(code.matches("__turbopack_import__(").count(), 1);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn dynamic_imports_of_async_modules() {
+    run(async {
+        let project = fixture_root("project", "dynamic-imports");
+        let asset_context = asset_context(Default::default(), Default::default());
+        let chunking_context = chunking_context(project, |builder| builder);
+
+        // Only the import of the module with a top-level await is awaited with
+        // the async import helper.
+        let index = module(asset_context, project.join("index.js".to_string()));
+        let content = module_content(index, chunking_context).await?.await?;
+        assert!(content.async_import);
+        let code = content.inner_code.to_str()?;
+        assert_eq!(code.matches("__turbopack_async_import__(").count(), 1);
+
+        let sync = module(asset_context, project.join("sync.js".to_string()));
+        let content = module_content(sync, chunking_context).await?.await?;
+        assert!(!content.async_import);
+        assert!(!content
+            .inner_code
+            .to_str()?
+            .contains("__turbopack_async_import__("));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn async_modules_contribute_to_async() {
+    run(async {
+        let project = fixture_root("project", "contributes-to-async");
+        let asset_context = asset_context(Default::default(), Default::default());
+        let chunking_context = chunking_context(project, |builder| builder);
+        let index = module(asset_context, project.join("index.js".to_string()));
+        let tla = module(asset_context, project.join("tla.js".to_string()));
+        let Some(tla_chunkable) = Vc::try_resolve_sidecast::<Box<dyn ChunkableModule>>(tla).await?
+        else {
+            bail!("tla.js isn't chunkable");
+        };
+        let tla_is_async = AsyncModuleInfo::new(vec![
+            tla_chunkable.as_chunk_item(Vc::upcast(chunking_context))
+        ]);
+
+        let tla = esm_reference(index, "./tla.js").await?;
+        let sync = esm_reference(index, "./sync.js").await?;
+        assert!(
+            *tla.contributes_to_async(chunking_context, tla_is_async, false)
+                .await?
+        );
+        assert!(
+            !*sync
+                .contributes_to_async(chunking_context, tla_is_async, false)
+                .await?
+        );
+
+        // Without async module info listing it, the module isn't async.
+        let none_async = AsyncModuleInfo::new(vec![]);
+        assert!(
+            !*tla
+                .contributes_to_async(chunking_context, none_async, false)
+                .await?
+        );
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn remote_imports() {
+    run(async {
+        let project = fixture_root("project", "remotes");
+        let asset_context = asset_context(Default::default(), Default::default());
+        let chunking_context = chunking_context(project, |builder| builder);
+        let index = module(asset_context, project.join("index.js".to_string()));
+
+        // The remote module is awaited where it's bound, so the importing
+        // module is async by itself, without async module info.
+        assert!(is_self_async(index, chunking_context).await?);
+        let async_module = Vc::<OptionAsyncModule>::cell(Some(async_module(index).await?));
+        let Some(options) = &*async_module.module_options(None, true).await? else {
+            bail!("index.js isn't async");
+        };
+        assert!(options.has_top_level_await);
+
+        let Some(index) = Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(index).await?
+        else {
+            bail!("index.js isn't an ECMAScript module");
+        };
+        let content = index.module_content(chunking_context, None).await?;
+        assert!(content
+            .inner_code
+            .to_str()?
+            .contains(r#"await __turbopack_federation_import__("app1", "./Button")"#));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn star_exports_from_remotes() {
+    run(async {
+        let project = fixture_root("project", "remotes");
+        let star = module(
+            asset_context(Default::default(), Default::default()),
+            project.join("star.js".to_string()),
+        );
+        let Some(star) = Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(star).await? else {
+            bail!("star.js isn't an ECMAScript module");
+        };
+        let EcmascriptExports::EsmExports(exports) = *star.get_exports().await? else {
+            bail!("star.js doesn't have ESM exports");
+        };
+
+        // The remote module can't be re-exported, which is reported instead
+        // of re-exporting nothing.
+        let expanded = exports.expand_exports();
+        assert!(expanded.await?.exports.is_empty());
+        assert_eq!(
+            issue_titles(expanded).await?,
+            ["star export from a module federation remote"]
+        );
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn async_module_kinds() {
+    run(async {
+        let project = fixture_root("project", "async-kinds");
+        let asset_context = asset_context(Default::default(), Default::default());
+        let chunking_context = chunking_context(project, |builder| builder);
+        let tla = module(asset_context, project.join("tla.js".to_string()));
+        let Some(tla_chunkable) = Vc::try_resolve_sidecast::<Box<dyn ChunkableModule>>(tla).await?
+        else {
+            bail!("tla.js isn't chunkable");
+        };
+        let tla_is_async = AsyncModuleInfo::new(vec![
+            tla_chunkable.as_chunk_item(Vc::upcast(chunking_context))
+        ]);
+
+        // The wrapper and the dependency handler each depend on one of the two
+        // reasons for a module to be async.
+        for (path, async_module_info, has_top_level_await, awaits_dependencies) in [
+            ("tla.js", AsyncModuleInfo::new(vec![]), true, false),
+            ("deps.js", tla_is_async, false, true),
+            ("both.js", tla_is_async, true, true),
+        ] {
+            let module = module(asset_context, project.join(path.to_string()));
+            let options = async_module(module)
+                .await?
+                .module_options(Some(async_module_info));
+            let Some(options) = &*options.await? else {
+                bail!("{path} isn't async");
+            };
+            assert_eq!(options.has_top_level_await, has_top_level_await, "{path}");
+
+            let Some(module) =
+                Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(module).await?
+            else {
+                bail!("{path} isn't an ECMAScript module");
+            };
+            let content = module
+                .module_content(chunking_context, Some(async_module_info))
+                .await?;
+            assert_eq!(
+                content
+                    .inner_code
+                    .to_str()?
+                    .contains("__turbopack_handle_async_dependencies__("),
+                awaits_dependencies,
+                "{path}"
+            );
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn externals_not_provided_by_the_environment() {
+    run(async {
+        let browser = Environment::new(Value::new(ExecutionEnvironment::Browser(
+            BrowserEnvironment {
+                dom: true,
+                web_worker: false,
+                service_worker: false,
+                browserslist_query: String::new(),
+            }
+            .into(),
+        )));
+        let edge_worker = Environment::new(Value::new(ExecutionEnvironment::EdgeWorker(
+            EdgeWorkerEnvironment {}.into(),
+        )));
+        let custom = Environment::new(Value::new(ExecutionEnvironment::Custom(0)));
+        assert!(browser.builtin_modules().await?.is_empty());
+        assert!(custom.builtin_modules().await?.is_empty());
+        assert!(node_environment()
+            .builtin_modules()
+            .await?
+            .contains(&"fs".to_string()));
+        let edge_builtin_modules = edge_worker.builtin_modules().await?;
+        assert!(edge_builtin_modules.contains(&"buffer".to_string()));
+        assert!(!edge_builtin_modules.contains(&"fs".to_string()));
+
+        let project = fixture_root("project", "environment-externals");
+        let mut import_map = ImportMap::empty();
+        for request in ["node:fs", "node:buffer"] {
+            import_map.insert_exact_alias(request, ImportMapping::External(None).cell());
+        }
+        let index = module(
+            asset_context(
+                Default::default(),
+                ResolveOptionsContext {
+                    import_map: Some(import_map.cell()),
+                    ..Default::default()
+                },
+            ),
+            project.join("index.js".to_string()),
+        );
+        let output = project.join("output".to_string());
+        let content = |environment: Vc<Environment>| {
+            let chunking_context =
+                DevChunkingContext::builder(project, output, output, output, output, environment)
+                    .build();
+            module_content(index, Vc::upcast(chunking_context))
+        };
+
+        // Edge workers don't provide `fs`, which is still loaded as an external.
+        let content_for_edge_worker = content(edge_worker).await?;
+        content_for_edge_worker.await?;
+        assert_eq!(
+            issue_titles(content_for_edge_worker).await?,
+            vec!["externals not provided by the environment"]
+        );
+        assert!(issue_descriptions(content_for_edge_worker).await?[0]
+            .starts_with("The module imports node:fs, which"));
+
+        // Node.js provides both.
+        let content_for_node = content(node_environment()).await?;
+        content_for_node.await?;
+        assert!(issue_titles(content_for_node).await?.is_empty());
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn referenced_asset_equality() {
+    run(async {
+        let asset_context = asset_context(Default::default(), Default::default());
+        let referenced_assets = |module: Vc<Box<dyn Module>>| async move {
+            esm_references(module)
+                .await?
+                .await?
+                .iter()
+                .map(|reference| async move {
+                    anyhow::Ok((*reference.get_referenced_asset().await?).clone())
+                })
+                .try_join()
+                .await
+        };
+
+        // Both imports of `./m.js` reference the same placeable.
+        let project = fixture_root("project", "duplicate-imports");
+        let duplicates =
+            referenced_assets(module(asset_context, project.join("index.js".to_string()))).await?;
+        assert!(matches!(duplicates[0], ReferencedAsset::Some(_)));
+        assert_eq!(duplicates[0], duplicates[1]);
+
+        let project = fixture_root("project", "referenced-assets");
+        let local = referenced_assets(module(asset_context, project.join("index.js".to_string())))
+            .await?
+            .swap_remove(0);
+        assert!(matches!(local, ReferencedAsset::Some(_)));
+        assert_ne!(local, duplicates[0]);
+
+        let keys = HashSet::from([duplicates[0].clone(), duplicates[1].clone(), local]);
+        assert_eq!(keys.len(), 2);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn resolve_statuses_of_references() {
+    run(async {
+        let project = fixture_root("project", "referenced-assets");
+        let mut import_map = ImportMap::empty();
+        import_map.insert_exact_alias("external", ImportMapping::External(None).cell());
+        import_map.insert_exact_alias("ignored", ImportMapping::Ignore.cell());
+        let index = module(
+            asset_context(
+                Default::default(),
+                ResolveOptionsContext {
+                    import_map: Some(import_map.cell()),
+                    ..Default::default()
+                },
+            ),
+            project.join("index.js".to_string()),
+        );
+
+        for (request, status) in [
+            ("./local.js", ResolveStatus::Internal),
+            ("external", ResolveStatus::External),
+            (
+                "data:text/javascript,export default 1",
+                ResolveStatus::Internal,
+            ),
+            ("./missing.js", ResolveStatus::Unresolvable),
+            ("ignored", ResolveStatus::Ignored),
+        ] {
+            let reference = esm_reference(index, request).await?;
+            assert_eq!(*reference.resolve_status().await?, status, "{request}");
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn browser_import_maps() {
+    run(async {
+        let project = fixture_root("project", "import-maps");
+        let import_map = browser_import_map(
+            &IndexMap::from([
+                ("lodash".to_string(), "./vendor/lodash.js".to_string()),
+                ("lodash/".to_string(), "./vendor/lodash/".to_string()),
+                (
+                    "cdn-lib".to_string(),
+                    "https://cdn.example.com/lib.js".to_string(),
+                ),
+            ]),
+            project,
+        );
+        let index = module(
+            asset_context(
+                ModuleOptionsContext {
+                    browser_import_map: Some(import_map.cell()),
+                    ..Default::default()
+                },
+                Default::default(),
+            ),
+            project.join("src/index.js".to_string()),
+        );
+
+        // Relative targets are resolved from the directory of the import map,
+        // not from `src`.
+        for (request, target) in [
+            ("lodash", "vendor/lodash.js"),
+            ("lodash/fp.js", "vendor/lodash/fp.js"),
+        ] {
+            let reference = esm_reference(index, request).await?;
+            let modules = reference.resolve_reference().primary_modules().await?;
+            assert_eq!(modules[0].ident().path().await?.path, target);
+        }
+
+        // Targets that are URLs are loaded as externals.
+        let cdn = esm_reference(index, "cdn-lib").await?;
+        assert_eq!(
+            *cdn.get_referenced_asset().await?,
+            ReferencedAsset::OriginalReferenceTypeExternal(
+                "https://cdn.example.com/lib.js".to_string()
+            )
+        );
+
+        Ok(())
+    })
+    .await
+}
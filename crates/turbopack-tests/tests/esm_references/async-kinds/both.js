@@ -0,0 +1,3 @@
+import { value } from "./tla.js";
+
+export const triple = await Promise.resolve(value * 3);
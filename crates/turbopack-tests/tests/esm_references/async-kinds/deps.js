@@ -0,0 +1,3 @@
+import { value } from "./tla.js";
+
+export const double = value * 2;
@@ -0,0 +1,5 @@
+import { b } from "./b.js";
+import { a } from "./a.js";
+import { c } from "./c.js";
+
+console.log(a, b, c);
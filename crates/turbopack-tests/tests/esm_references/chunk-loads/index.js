@@ -0,0 +1,5 @@
+import "./same.js";
+"TURBOPACK { chunking-type: isolated }";
+import "./isolated.js";
+import "external";
+import "./missing.js";
@@ -0,0 +1,5 @@
+"TURBOPACK { chunking-type: parallel }";
+import "./parallel.js";
+"TURBOPACK { chunking-type: none }";
+import "./none.js";
+import "./default.js";
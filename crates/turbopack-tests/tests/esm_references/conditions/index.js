@@ -0,0 +1,5 @@
+import plain from "pkg";
+"TURBOPACK { conditions: worker }";
+import worker from "pkg";
+
+console.log(plain, worker);
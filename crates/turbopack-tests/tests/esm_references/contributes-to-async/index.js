@@ -0,0 +1,4 @@
+import { value } from "./tla.js";
+import { other } from "./sync.js";
+
+console.log(value, other);
@@ -0,0 +1,5 @@
+import { b } from "./b.js";
+import { c } from "./c.js";
+
+export const a = 1;
+console.log(b, c);
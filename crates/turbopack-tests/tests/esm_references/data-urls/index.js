@@ -0,0 +1,4 @@
+import inline from "data:application/json,1";
+import mapped from "mapped";
+
+console.log(inline, mapped);
@@ -0,0 +1,2 @@
+import("./async.js");
+import("./sync.js");
@@ -0,0 +1,2 @@
+import("./sync-2.js");
+export const value = "sync";
@@ -0,0 +1,6 @@
+"TURBOPACK { chunking-type: eager }";
+import data from "./data.json";
+"TURBOPACK { chunking-type: eager }";
+import { hot } from "./hot.js";
+
+console.log(data, hot);
@@ -0,0 +1,4 @@
+import { readFileSync } from "node:fs";
+import { Buffer } from "node:buffer";
+
+console.log(readFileSync, Buffer);
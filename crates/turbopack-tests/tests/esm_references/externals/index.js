@@ -0,0 +1,4 @@
+import ext from "external";
+import local from "./local.js";
+
+console.log(ext, local);
@@ -0,0 +1,6 @@
+import "./shared.js";
+import "external";
+import { shared } from "./shared";
+import "./other.js";
+
+console.log(shared);
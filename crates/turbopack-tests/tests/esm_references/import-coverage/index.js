@@ -0,0 +1,4 @@
+import { a } from "./a.js";
+import "./b.js";
+
+console.log(a);
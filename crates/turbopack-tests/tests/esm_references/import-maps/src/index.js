@@ -0,0 +1,5 @@
+import lodash from "lodash";
+import fp from "lodash/fp.js";
+import cdn from "cdn-lib";
+
+console.log(lodash, fp, cdn);
@@ -0,0 +1,4 @@
+import small from "./small.json";
+import large from "./large.json";
+
+console.log(small, large);
@@ -0,0 +1,6 @@
+"TURBOPACK { chunking-type: isolated }";
+import { value } from "./m.js";
+"TURBOPACK { chunking-type: isolated }";
+import * as loader from "./loader.js";
+
+console.log(value, loader);
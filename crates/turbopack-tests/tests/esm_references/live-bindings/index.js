@@ -0,0 +1,4 @@
+import { count, increment } from "./counter.js";
+
+increment();
+console.log(count);
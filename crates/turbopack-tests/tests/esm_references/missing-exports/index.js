@@ -0,0 +1,5 @@
+import { a, missing } from "./x.js";
+import { b, other } from "./x.js";
+import { c } from "./y.js";
+
+console.log(a, missing, b, other, c);
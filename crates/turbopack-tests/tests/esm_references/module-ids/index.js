@@ -0,0 +1,8 @@
+import "./a.js";
+import "external";
+import "./b.js";
+import { a } from "./a.js";
+"TURBOPACK { chunking-type: none }";
+import "./c.js";
+
+console.log(a);
@@ -0,0 +1,4 @@
+import { a } from "./m.js";
+import { b } from "./m.js";
+
+console.log(a, b);
@@ -0,0 +1,4 @@
+import * as esm from "./esm.js";
+import * as cjs from "./cjs.js";
+
+console.log(esm, cjs);
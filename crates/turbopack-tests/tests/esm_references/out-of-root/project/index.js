@@ -0,0 +1,2 @@
+import "./local.js";
+import "shared";
@@ -0,0 +1,4 @@
+import { a } from "./a.js";
+import * as ns from "./a.js";
+
+console.log(a, ns);
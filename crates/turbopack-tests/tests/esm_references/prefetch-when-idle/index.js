@@ -0,0 +1,4 @@
+"TURBOPACK { chunking-type: parallel-prefetch }";
+import { value } from "./a.js";
+
+console.log(value);
@@ -0,0 +1,4 @@
+import * as a from "./shared.js?a";
+import * as b from "./shared.js?b";
+
+console.log(a, b);
@@ -0,0 +1,7 @@
+import local from "./local.js";
+import ext from "external";
+import inline from "data:text/javascript,export default 1";
+import missing from "./missing.js";
+import ignored from "ignored";
+
+console.log(local, ext, inline, missing, ignored);
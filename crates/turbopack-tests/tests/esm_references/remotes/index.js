@@ -0,0 +1,4 @@
+"TURBOPACK { remote: app1/Button }";
+import { Button } from "app1/Button";
+
+console.log(Button);
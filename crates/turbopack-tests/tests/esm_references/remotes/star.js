@@ -0,0 +1,2 @@
+"TURBOPACK { remote: app1/Button }";
+export * from "app1/Button";
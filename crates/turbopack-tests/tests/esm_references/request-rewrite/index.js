@@ -0,0 +1,4 @@
+"TURBOPACK { chunking-type: isolated }";
+import * as lib from "./old.js";
+
+console.log(lib);
@@ -0,0 +1,3 @@
+import { util } from "@/utils";
+
+util();
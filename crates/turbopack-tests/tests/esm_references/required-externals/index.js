@@ -0,0 +1,4 @@
+import esm from "esm-pkg";
+import cjs from "cjs-pkg";
+
+console.log(esm, cjs);
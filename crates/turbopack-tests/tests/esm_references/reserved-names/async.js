@@ -0,0 +1,2 @@
+var __turbopack_async_result__ = 1;
+export const value = await Promise.resolve(__turbopack_async_result__);
@@ -0,0 +1,2 @@
+var __turbopack_async_result__ = 1;
+export const value = __turbopack_async_result__;
@@ -0,0 +1,4 @@
+import "./a.js";
+import "./missing.js";
+import "./b.js";
+import "./also-missing.js";
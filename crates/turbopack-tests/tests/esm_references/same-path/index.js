@@ -0,0 +1,4 @@
+import { a } from "virtual-a";
+import { b } from "virtual-b";
+
+console.log(a, b);
@@ -0,0 +1,3 @@
+console.log("effect");
+
+export const effect = "effect";
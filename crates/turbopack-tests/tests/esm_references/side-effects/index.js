@@ -0,0 +1,5 @@
+import { pure } from "./pure/index.js";
+import { effect } from "./effectful/index.js";
+import { used } from "./pure/used.js";
+
+console.log(used);
@@ -0,0 +1,4 @@
+"TURBOPACK { chunking-type: unknown }";
+import a from "./a.js";
+
+console.log(a);
@@ -0,0 +1,3 @@
+import "./same.js";
+"TURBOPACK { chunking-type: isolated }";
+import "./isolated.js";
@@ -0,0 +1,4 @@
+"TURBOPACK { transition: annotated }";
+import { a } from "./a.js";
+
+console.log(a);
@@ -0,0 +1,4 @@
+import a from "./a.js";
+import missing from "./missing.js";
+
+console.log(a, missing);
@@ -19,8 +19,11 @@ use turbo_tasks_fs::{
 };
 use turbo_tasks_memory::MemoryBackend;
 use turbopack::{
-    condition::ContextCondition, ecmascript::TreeShakingMode, module_options::ModuleOptionsContext,
-    resolve_options_context::ResolveOptionsContext, ModuleAssetContext,
+    condition::ContextCondition,
+    ecmascript::{references::esm::EsmNamespaceShape, TreeShakingMode},
+    module_options::ModuleOptionsContext,
+    resolve_options_context::ResolveOptionsContext,
+    ModuleAssetContext,
 };
 use turbopack_core::{
     chunk::{EvaluatableAssetExt, EvaluatableAssets},
@@ -166,6 +169,8 @@ async fn run(resource: PathBuf, snapshot_mode: IssueSnapshotMode) -> Result<JsRe
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 struct TestOptions {
     tree_shaking_mode: Option<TreeShakingMode>,
+    esm_namespace_shape: Option<EsmNamespaceShape>,
+    esm_live_bindings: Option<bool>,
 }
 
 #[turbo_tasks::value]
@@ -298,6 +303,8 @@ async fn run_test(prepared_test: Vc<PreparedTest>) -> Result<Vc<RunTestResult>>
         static_root_path,
         env,
     )
+    .esm_namespace_shape(options.esm_namespace_shape.unwrap_or_default())
+    .esm_live_bindings(options.esm_live_bindings.unwrap_or_default())
     .build();
 
     let jest_entry_asset = process_path_to_asset(jest_entry_path, asset_context).module();
@@ -0,0 +1,3 @@
+it("should resolve an async module with its exported value", async () => {
+  await expect(require("./value.mjs")).resolves.toBe(42);
+});
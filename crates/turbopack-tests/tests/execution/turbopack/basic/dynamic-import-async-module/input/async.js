@@ -0,0 +1,5 @@
+export let value = "pending";
+
+await new Promise((resolve) => setTimeout(resolve, 10));
+
+value = "evaluated";
@@ -0,0 +1,3 @@
+await Promise.reject(new Error("failed to evaluate"));
+
+export const value = "unreachable";
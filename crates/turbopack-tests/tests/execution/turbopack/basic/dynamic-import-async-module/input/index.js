@@ -0,0 +1,15 @@
+it("should resolve a dynamic import of an async module after its evaluation", async () => {
+  const module = await import("./async.js");
+  expect(module.value).toBe("evaluated");
+});
+
+it("should resolve to the exports instead of the module's promise", async () => {
+  const module = await import("./async.js");
+  expect(typeof module.then).toBe("undefined");
+});
+
+it("should reject when the evaluation of the module rejects", async () => {
+  await expect(import("./failing.js")).rejects.toThrow(
+    "failed to evaluate"
+  );
+});
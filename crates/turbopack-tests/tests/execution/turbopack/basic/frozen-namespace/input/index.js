@@ -0,0 +1,21 @@
+import * as counter from "./counter.js";
+import * as asyncModule from "./async.js";
+
+it("should freeze namespace objects", () => {
+  expect(Object.isFrozen(counter)).toBe(true);
+  expect(() => {
+    counter.added = 1;
+  }).toThrow(TypeError);
+  expect(counter.added).toBeUndefined();
+});
+
+it("should keep live bindings of frozen namespace objects", () => {
+  expect(counter.count).toBe(0);
+  counter.increment();
+  expect(counter.count).toBe(1);
+});
+
+it("should not freeze namespace objects of async modules", () => {
+  expect(Object.isFrozen(asyncModule)).toBe(false);
+  expect(asyncModule.value).toBe(42);
+});
@@ -0,0 +1,11 @@
+import { instance as first } from "./shared.js?first";
+import { instance as second } from "./shared.js?second";
+import { instance as again } from "./shared.js?first";
+
+it("should resolve the same file with different queries to distinct modules", () => {
+  expect(first).not.toBe(second);
+});
+
+it("should resolve the same query to the same module", () => {
+  expect(first).toBe(again);
+});
@@ -0,0 +1,7 @@
+import { count, increment } from "./counter.js";
+
+it("should observe reassignments of let exports", () => {
+  expect(count).toBe(0);
+  increment();
+  expect(count).toBe(1);
+});
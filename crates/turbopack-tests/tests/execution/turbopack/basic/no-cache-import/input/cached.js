@@ -0,0 +1 @@
+export { count } from "./counter.js";
@@ -0,0 +1,8 @@
+import { count as cachedCount } from "./cached.js";
+"TURBOPACK { no-cache }";
+import { count as freshCount } from "./counter.js";
+
+it("should evaluate a module imported without the cache again", () => {
+  expect(cachedCount).toBe(1);
+  expect(freshCount).toBe(2);
+});
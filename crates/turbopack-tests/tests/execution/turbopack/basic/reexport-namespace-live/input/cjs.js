@@ -0,0 +1,6 @@
+exports.a = "a";
+exports.shadowed = "cjs";
+
+exports.addLater = () => {
+  exports.b = "b";
+};
@@ -0,0 +1,13 @@
+import * as ns from "./reexport.js";
+
+it("should re-export exports the namespace gets later", () => {
+  expect(ns.a).toBe("a");
+  expect(ns.b).toBeUndefined();
+  ns.addLater();
+  expect(ns.b).toBe("b");
+  expect(Object.keys(ns)).toContain("b");
+});
+
+it("should prefer the module's own exports", () => {
+  expect(ns.shadowed).toBe("esm");
+});
@@ -0,0 +1,3 @@
+export * from "./cjs.js";
+
+export const shadowed = "esm";
@@ -0,0 +1,9 @@
+"TURBOPACK { prefetch }";
+import { value as prefetched } from "./a.js";
+"TURBOPACK { preload }";
+import { value as preloaded } from "./b.js";
+
+it("should import modules with resource hints", () => {
+  expect(prefetched).toBe("a");
+  expect(preloaded).toBe("b");
+});
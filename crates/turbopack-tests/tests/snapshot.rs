@@ -21,7 +21,9 @@ use turbo_tasks_fs::{
 use turbo_tasks_memory::MemoryBackend;
 use turbopack::{
     condition::ContextCondition,
-    ecmascript::{EcmascriptInputTransform, EcmascriptModuleAsset},
+    ecmascript::{
+        references::esm::UrlRewriteBehavior, EcmascriptInputTransform, EcmascriptModuleAsset,
+    },
     module_options::{
         JsxTransformOptions, ModuleOptionsContext, ModuleRule, ModuleRuleCondition,
         ModuleRuleEffect,
@@ -87,6 +89,12 @@ struct SnapshotOptions {
     runtime_type: RuntimeType,
     #[serde(default)]
     environment: SnapshotEnvironment,
+    #[serde(default)]
+    url_rewrite_behavior: Option<UrlRewriteBehavior>,
+    #[serde(default)]
+    browser: bool,
+    #[serde(default)]
+    async_dependency_timeout_ms: Option<u32>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -112,6 +120,9 @@ impl Default for SnapshotOptions {
             runtime: Default::default(),
             runtime_type: default_runtime_type(),
             environment: Default::default(),
+            url_rewrite_behavior: Default::default(),
+            browser: Default::default(),
+            async_dependency_timeout_ms: Default::default(),
         }
     }
 }
@@ -260,6 +271,7 @@ async fn run_test(resource: String) -> Result<Vc<FileSystemPath>> {
                 ..Default::default()
             })),
             preset_env_versions: Some(env),
+            esm_url_rewrite_behavior: options.url_rewrite_behavior,
             rules: vec![(
                 ContextCondition::InDirectory("node_modules".to_string()),
                 ModuleOptionsContext {
@@ -276,6 +288,7 @@ async fn run_test(resource: String) -> Result<Vc<FileSystemPath>> {
             enable_react: true,
             enable_node_modules: Some(project_root),
             custom_conditions: vec!["development".to_string()],
+            browser: options.browser,
             rules: vec![(
                 ContextCondition::InDirectory("node_modules".to_string()),
                 ResolveOptionsContext {
@@ -309,6 +322,7 @@ async fn run_test(resource: String) -> Result<Vc<FileSystemPath>> {
                 env,
             )
             .runtime_type(options.runtime_type)
+            .async_dependency_timeout_ms(options.async_dependency_timeout_ms)
             .build(),
         ),
         Runtime::Build => Vc::upcast(
@@ -0,0 +1,6 @@
+// This is a top-level-await
+await new Promise((r) => setTimeout(r, 100));
+
+export const dbCall = (data) => {
+  console.log("dbCall", data);
+};
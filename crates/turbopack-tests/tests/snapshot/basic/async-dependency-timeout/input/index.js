@@ -0,0 +1,4 @@
+import { dbCall } from "./db-connection.js";
+
+// The handler of the async dependencies warns after 1000ms.
+dbCall({ command: "SELECT 1" });
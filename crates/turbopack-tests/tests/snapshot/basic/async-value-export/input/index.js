@@ -0,0 +1,3 @@
+require("./value.mjs").then((value) => {
+  console.log(value);
+});
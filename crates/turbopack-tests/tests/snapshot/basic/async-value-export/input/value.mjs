@@ -0,0 +1,3 @@
+const value = await Promise.resolve(42);
+
+__turbopack_export_value__(value);
@@ -1,3 +1,3 @@
-module.exports={"[project]/crates/turbopack-tests/tests/snapshot/basic/ecmascript_minify/input/index.js [test] (ecmascript)":function({r:t,f:s,i:e,s:o,v:c,n:l,c:i,M:n,l:p,j:r,p:a,P:m,U:u,R:f,g:d,__dirname:g,m:j,e:b,t:h}){(function(){let t="Hello";console.log("Hello, world!",3,t),console.log(t)}).call(this)}};
+module.exports={"[project]/crates/turbopack-tests/tests/snapshot/basic/ecmascript_minify/input/index.js [test] (ecmascript)":function({r:t,f:s,i:e,I:o,s:c,v:l,n:i,c:n,M:p,l:r,j:a,p:m,P:u,U:f,R:d,g:g,__dirname:j,m:b,e:h,t:x}){(function(){let t="Hello";console.log("Hello, world!",3,t),console.log(t)}).call(this)}};
 
 //# sourceMappingURL=crates_turbopack-tests_tests_snapshot_basic_ecmascript_minify_input_index_dc5b16.js.map
\ No newline at end of file
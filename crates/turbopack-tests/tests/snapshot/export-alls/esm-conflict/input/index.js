@@ -0,0 +1,2 @@
+import * as ns from "./reexports.js";
+console.log(ns);
@@ -0,0 +1,11 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/79fb1_turbopack-tests_tests_snapshot_export-alls_esm-conflict_input_index_38ada0.js",
+    {},
+]);
+(globalThis.TURBOPACK_CHUNK_LISTS = globalThis.TURBOPACK_CHUNK_LISTS || []).push({
+  "path": "output/79fb1_turbopack-tests_tests_snapshot_export-alls_esm-conflict_input_index_38ada0.js",
+  "chunks": [
+    "output/crates_turbopack-tests_tests_snapshot_export-alls_esm-conflict_input_8f48aa._.js"
+  ],
+  "source": "entry"
+});
\ No newline at end of file
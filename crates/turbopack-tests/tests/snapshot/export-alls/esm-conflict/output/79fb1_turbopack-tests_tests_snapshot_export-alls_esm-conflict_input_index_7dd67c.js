@@ -0,0 +1,6 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/79fb1_turbopack-tests_tests_snapshot_export-alls_esm-conflict_input_index_7dd67c.js",
+    {},
+    {"otherChunks":["output/crates_turbopack-tests_tests_snapshot_export-alls_esm-conflict_input_8f48aa._.js"],"runtimeModuleIds":["[project]/crates/turbopack-tests/tests/snapshot/export-alls/esm-conflict/input/index.js [test] (ecmascript)"]}
+]);
+// Dummy runtime
\ No newline at end of file
@@ -0,0 +1,64 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push(["output/crates_turbopack-tests_tests_snapshot_export-alls_esm-conflict_input_8f48aa._.js", {
+
+"[project]/crates/turbopack-tests/tests/snapshot/export-alls/esm-conflict/input/shared.js [test] (ecmascript)": (({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname, k: __turbopack_refresh__ }) => (() => {
+"use strict";
+
+__turbopack_esm__({
+    "shared": ()=>shared
+});
+const shared = "shared";
+
+})()),
+"[project]/crates/turbopack-tests/tests/snapshot/export-alls/esm-conflict/input/a.js [test] (ecmascript)": (({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname, k: __turbopack_refresh__ }) => (() => {
+"use strict";
+
+__turbopack_esm__({
+    "name": ()=>name,
+    "shared": ()=>__TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$export$2d$alls$2f$esm$2d$conflict$2f$input$2f$shared$2e$js__$5b$test$5d$__$28$ecmascript$29$__["shared"]
+});
+var __TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$export$2d$alls$2f$esm$2d$conflict$2f$input$2f$shared$2e$js__$5b$test$5d$__$28$ecmascript$29$__ = Object.freeze(__turbopack_import_namespace__("[project]/crates/turbopack-tests/tests/snapshot/export-alls/esm-conflict/input/shared.js [test] (ecmascript)"));
+"__TURBOPACK__ecmascript__hoisting__location__";
+const name = "a";
+;
+
+})()),
+"[project]/crates/turbopack-tests/tests/snapshot/export-alls/esm-conflict/input/b.js [test] (ecmascript)": (({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname, k: __turbopack_refresh__ }) => (() => {
+"use strict";
+
+__turbopack_esm__({
+    "name": ()=>name,
+    "shared": ()=>__TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$export$2d$alls$2f$esm$2d$conflict$2f$input$2f$shared$2e$js__$5b$test$5d$__$28$ecmascript$29$__["shared"]
+});
+var __TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$export$2d$alls$2f$esm$2d$conflict$2f$input$2f$shared$2e$js__$5b$test$5d$__$28$ecmascript$29$__ = Object.freeze(__turbopack_import_namespace__("[project]/crates/turbopack-tests/tests/snapshot/export-alls/esm-conflict/input/shared.js [test] (ecmascript)"));
+"__TURBOPACK__ecmascript__hoisting__location__";
+const name = "b";
+;
+
+})()),
+"[project]/crates/turbopack-tests/tests/snapshot/export-alls/esm-conflict/input/reexports.js [test] (ecmascript)": (({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname, k: __turbopack_refresh__ }) => (() => {
+"use strict";
+
+__turbopack_esm__({
+    "name": ()=>__TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$export$2d$alls$2f$esm$2d$conflict$2f$input$2f$a$2e$js__$5b$test$5d$__$28$ecmascript$29$__["name"],
+    "shared": ()=>__TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$export$2d$alls$2f$esm$2d$conflict$2f$input$2f$a$2e$js__$5b$test$5d$__$28$ecmascript$29$__["shared"]
+});
+var __TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$export$2d$alls$2f$esm$2d$conflict$2f$input$2f$a$2e$js__$5b$test$5d$__$28$ecmascript$29$__ = Object.freeze(__turbopack_import_namespace__("[project]/crates/turbopack-tests/tests/snapshot/export-alls/esm-conflict/input/a.js [test] (ecmascript)"));
+var __TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$export$2d$alls$2f$esm$2d$conflict$2f$input$2f$b$2e$js__$5b$test$5d$__$28$ecmascript$29$__ = Object.freeze(__turbopack_import_namespace__("[project]/crates/turbopack-tests/tests/snapshot/export-alls/esm-conflict/input/b.js [test] (ecmascript)"));
+"__TURBOPACK__ecmascript__hoisting__location__";
+;
+;
+
+})()),
+"[project]/crates/turbopack-tests/tests/snapshot/export-alls/esm-conflict/input/index.js [test] (ecmascript)": (({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname, k: __turbopack_refresh__ }) => (() => {
+"use strict";
+
+__turbopack_esm__({});
+var __TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$export$2d$alls$2f$esm$2d$conflict$2f$input$2f$reexports$2e$js__$5b$test$5d$__$28$ecmascript$29$__ = Object.freeze(__turbopack_import_namespace__("[project]/crates/turbopack-tests/tests/snapshot/export-alls/esm-conflict/input/reexports.js [test] (ecmascript)"));
+"__TURBOPACK__ecmascript__hoisting__location__";
+;
+console.log(__TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$export$2d$alls$2f$esm$2d$conflict$2f$input$2f$reexports$2e$js__$5b$test$5d$__$28$ecmascript$29$__);
+
+})()),
+}]);
+
+//# sourceMappingURL=crates_turbopack-tests_tests_snapshot_export-alls_esm-conflict_input_8f48aa._.js.map
\ No newline at end of file
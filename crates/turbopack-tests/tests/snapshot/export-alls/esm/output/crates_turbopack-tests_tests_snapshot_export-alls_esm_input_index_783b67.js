@@ -0,0 +1,11 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/crates_turbopack-tests_tests_snapshot_export-alls_esm_input_index_783b67.js",
+    {},
+]);
+(globalThis.TURBOPACK_CHUNK_LISTS = globalThis.TURBOPACK_CHUNK_LISTS || []).push({
+  "path": "output/crates_turbopack-tests_tests_snapshot_export-alls_esm_input_index_783b67.js",
+  "chunks": [
+    "output/crates_turbopack-tests_tests_snapshot_export-alls_esm_input_071f9f._.js"
+  ],
+  "source": "entry"
+});
\ No newline at end of file
@@ -0,0 +1,6 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/crates_turbopack-tests_tests_snapshot_export-alls_esm_input_index_ce2df6.js",
+    {},
+    {"otherChunks":["output/crates_turbopack-tests_tests_snapshot_export-alls_esm_input_071f9f._.js"],"runtimeModuleIds":["[project]/crates/turbopack-tests/tests/snapshot/export-alls/esm/input/index.js [test] (ecmascript)"]}
+]);
+// Dummy runtime
\ No newline at end of file
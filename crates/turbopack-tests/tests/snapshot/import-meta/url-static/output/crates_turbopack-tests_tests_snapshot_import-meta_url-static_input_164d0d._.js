@@ -0,0 +1,32 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push(["output/crates_turbopack-tests_tests_snapshot_import-meta_url-static_input_164d0d._.js", {
+
+"[project]/crates/turbopack-tests/tests/snapshot/import-meta/url-static/input/asset.txt [test] (static)": (({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname }) => (() => {
+
+__turbopack_export_value__("/static/asset.05254cf2.txt");
+})()),
+"[project]/crates/turbopack-tests/tests/snapshot/import-meta/url-static/input/mod.mjs [test] (ecmascript)": (({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname, k: __turbopack_refresh__ }) => (() => {
+"use strict";
+
+__turbopack_esm__({});
+const __TURBOPACK__import$2e$meta__ = {
+    get url () {
+        return `file://${__turbopack_resolve_absolute_path__("crates/turbopack-tests/tests/snapshot/import-meta/url-static/input/mod.mjs")}`;
+    }
+};
+"__TURBOPACK__ecmascript__hoisting__location__";
+const assetUrl = new URL("/static/asset.05254cf2.txt", __TURBOPACK__import$2e$meta__.url);
+console.log(assetUrl);
+fetch(assetUrl).then((res)=>res.text()).then(console.log);
+
+})()),
+"[project]/crates/turbopack-tests/tests/snapshot/import-meta/url-static/input/index.js [test] (ecmascript)": (function({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname, k: __turbopack_refresh__, m: module, e: exports, t: require }) { !function() {
+
+__turbopack_esm__({});
+var __TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$import$2d$meta$2f$url$2d$static$2f$input$2f$mod$2e$mjs__$5b$test$5d$__$28$ecmascript$29$__ = __turbopack_import__("[project]/crates/turbopack-tests/tests/snapshot/import-meta/url-static/input/mod.mjs [test] (ecmascript)");
+"__TURBOPACK__ecmascript__hoisting__location__";
+;
+
+}.call(this) }),
+}]);
+
+//# sourceMappingURL=crates_turbopack-tests_tests_snapshot_import-meta_url-static_input_164d0d._.js.map
\ No newline at end of file
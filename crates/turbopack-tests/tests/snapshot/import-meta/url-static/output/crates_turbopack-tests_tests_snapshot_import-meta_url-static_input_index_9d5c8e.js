@@ -0,0 +1,11 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/crates_turbopack-tests_tests_snapshot_import-meta_url-static_input_index_9d5c8e.js",
+    {},
+]);
+(globalThis.TURBOPACK_CHUNK_LISTS = globalThis.TURBOPACK_CHUNK_LISTS || []).push({
+  "path": "output/crates_turbopack-tests_tests_snapshot_import-meta_url-static_input_index_9d5c8e.js",
+  "chunks": [
+    "output/crates_turbopack-tests_tests_snapshot_import-meta_url-static_input_164d0d._.js"
+  ],
+  "source": "entry"
+});
\ No newline at end of file
@@ -0,0 +1,6 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/crates_turbopack-tests_tests_snapshot_import-meta_url-static_input_index_dc3d52.js",
+    {},
+    {"otherChunks":["output/crates_turbopack-tests_tests_snapshot_import-meta_url-static_input_164d0d._.js"],"runtimeModuleIds":["[project]/crates/turbopack-tests/tests/snapshot/import-meta/url-static/input/index.js [test] (ecmascript)"]}
+]);
+// Dummy runtime
\ No newline at end of file
@@ -0,0 +1,4 @@
+import "data:text/javascript,console.log('inline script')";
+import data from "data:application/json,%7B%22name%22%3A%22inline%20json%22%7D";
+
+console.log(data);
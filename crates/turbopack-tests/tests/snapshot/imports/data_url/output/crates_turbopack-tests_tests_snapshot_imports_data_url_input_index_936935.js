@@ -0,0 +1,22 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push(["output/crates_turbopack-tests_tests_snapshot_imports_data_url_input_index_936935.js", {
+
+"[project]/crates/turbopack-tests/tests/snapshot/imports/data_url/input/index.js [test] (ecmascript)": (({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname, k: __turbopack_refresh__ }) => (() => {
+"use strict";
+
+__turbopack_esm__({});
+var __TURBOPACK__inline__module__35744a3f44ab1d41__ = (()=>{
+    console.log('inline script');
+    return {};
+})();
+var __TURBOPACK__inline__module__aeb8e2effcfc7bf3__ = {
+    default: JSON.parse('{"name":"inline json"}')
+};
+"__TURBOPACK__ecmascript__hoisting__location__";
+;
+;
+console.log(__TURBOPACK__inline__module__aeb8e2effcfc7bf3__["default"]);
+
+})()),
+}]);
+
+//# sourceMappingURL=crates_turbopack-tests_tests_snapshot_imports_data_url_input_index_936935.js.map
\ No newline at end of file
@@ -0,0 +1,6 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/crates_turbopack-tests_tests_snapshot_imports_data_url_input_index_d63ad5.js",
+    {},
+    {"otherChunks":["output/crates_turbopack-tests_tests_snapshot_imports_data_url_input_index_936935.js"],"runtimeModuleIds":["[project]/crates/turbopack-tests/tests/snapshot/imports/data_url/input/index.js [test] (ecmascript)"]}
+]);
+// Dummy runtime
\ No newline at end of file
@@ -0,0 +1,11 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/crates_turbopack-tests_tests_snapshot_imports_data_url_input_index_d9a6f0.js",
+    {},
+]);
+(globalThis.TURBOPACK_CHUNK_LISTS = globalThis.TURBOPACK_CHUNK_LISTS || []).push({
+  "path": "output/crates_turbopack-tests_tests_snapshot_imports_data_url_input_index_d9a6f0.js",
+  "chunks": [
+    "output/crates_turbopack-tests_tests_snapshot_imports_data_url_input_index_936935.js"
+  ],
+  "source": "entry"
+});
\ No newline at end of file
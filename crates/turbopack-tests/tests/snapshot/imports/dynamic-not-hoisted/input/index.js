@@ -0,0 +1,7 @@
+console.log("before");
+
+function load() {
+  return import("./lazy.js");
+}
+
+load().then((m) => console.log(m.default));
@@ -0,0 +1,12 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_index_0dcb06.js",
+    {},
+]);
+(globalThis.TURBOPACK_CHUNK_LISTS = globalThis.TURBOPACK_CHUNK_LISTS || []).push({
+  "path": "output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_index_0dcb06.js",
+  "chunks": [
+    "output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_index_65d19d.js",
+    "output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_lazy_28cfcd.js"
+  ],
+  "source": "entry"
+});
\ No newline at end of file
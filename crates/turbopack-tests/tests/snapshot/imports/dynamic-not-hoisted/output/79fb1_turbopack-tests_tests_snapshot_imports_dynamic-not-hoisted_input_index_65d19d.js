@@ -0,0 +1,14 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push(["output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_index_65d19d.js", {
+
+"[project]/crates/turbopack-tests/tests/snapshot/imports/dynamic-not-hoisted/input/index.js [test] (ecmascript)": (function({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname, k: __turbopack_refresh__, m: module, e: exports, t: require }) { !function() {
+
+console.log("before");
+function load() {
+    return __turbopack_require__("[project]/crates/turbopack-tests/tests/snapshot/imports/dynamic-not-hoisted/input/lazy.js [test] (ecmascript, loader)")(__turbopack_import__);
+}
+load().then((m)=>console.log(m.default));
+
+}.call(this) }),
+}]);
+
+//# sourceMappingURL=79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_index_65d19d.js.map
\ No newline at end of file
@@ -0,0 +1,6 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_index_dc2811.js",
+    {},
+    {"otherChunks":["output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_index_65d19d.js","output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_lazy_28cfcd.js"],"runtimeModuleIds":["[project]/crates/turbopack-tests/tests/snapshot/imports/dynamic-not-hoisted/input/index.js [test] (ecmascript)"]}
+]);
+// Dummy runtime
\ No newline at end of file
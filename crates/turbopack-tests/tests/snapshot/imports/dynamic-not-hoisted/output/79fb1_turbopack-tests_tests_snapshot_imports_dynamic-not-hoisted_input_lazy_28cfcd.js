@@ -0,0 +1,16 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push(["output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_lazy_28cfcd.js", {
+
+"[project]/crates/turbopack-tests/tests/snapshot/imports/dynamic-not-hoisted/input/lazy.js [test] (ecmascript, loader)": (({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname }) => (() => {
+
+__turbopack_export_value__((__turbopack_import__) => {
+    return Promise.all(["output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_lazy_bd955b.js","output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_lazy_e579a3.js"].map((chunk) => __turbopack_load__(chunk))).then(() => {
+        return __turbopack_require__("[project]/crates/turbopack-tests/tests/snapshot/imports/dynamic-not-hoisted/input/lazy.js [test] (ecmascript, manifest chunk)");
+    }).then((chunks) => {
+        return Promise.all(chunks.map((chunk) => __turbopack_load__(chunk)));
+    }).then(() => {
+        return __turbopack_import__("[project]/crates/turbopack-tests/tests/snapshot/imports/dynamic-not-hoisted/input/lazy.js [test] (ecmascript)");
+    });
+});
+
+})()),
+}]);
\ No newline at end of file
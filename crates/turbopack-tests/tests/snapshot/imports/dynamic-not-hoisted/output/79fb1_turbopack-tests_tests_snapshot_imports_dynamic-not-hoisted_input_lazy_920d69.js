@@ -0,0 +1,11 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_lazy_920d69.js",
+    {},
+]);
+(globalThis.TURBOPACK_CHUNK_LISTS = globalThis.TURBOPACK_CHUNK_LISTS || []).push({
+  "path": "output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_lazy_920d69.js",
+  "chunks": [
+    "output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_lazy_beac03.js"
+  ],
+  "source": "dynamic"
+});
\ No newline at end of file
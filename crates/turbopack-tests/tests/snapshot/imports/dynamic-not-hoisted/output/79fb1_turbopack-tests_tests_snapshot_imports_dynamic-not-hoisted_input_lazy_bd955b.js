@@ -0,0 +1,11 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push(["output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_lazy_bd955b.js", {
+
+"[project]/crates/turbopack-tests/tests/snapshot/imports/dynamic-not-hoisted/input/lazy.js [test] (ecmascript, manifest chunk)": (({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname }) => (() => {
+
+__turbopack_export_value__([
+  "output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_lazy_beac03.js",
+  "output/79fb1_turbopack-tests_tests_snapshot_imports_dynamic-not-hoisted_input_lazy_920d69.js"
+]);
+
+})()),
+}]);
\ No newline at end of file
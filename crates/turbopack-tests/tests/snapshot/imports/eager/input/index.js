@@ -0,0 +1,4 @@
+"TURBOPACK { chunking-type: eager }";
+import { hot } from "./hot.js";
+
+console.log(hot);
@@ -0,0 +1,11 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/crates_turbopack-tests_tests_snapshot_imports_eager_input_index_2cf032.js",
+    {},
+]);
+(globalThis.TURBOPACK_CHUNK_LISTS = globalThis.TURBOPACK_CHUNK_LISTS || []).push({
+  "path": "output/crates_turbopack-tests_tests_snapshot_imports_eager_input_index_2cf032.js",
+  "chunks": [
+    "output/crates_turbopack-tests_tests_snapshot_imports_eager_input_4400f5._.js"
+  ],
+  "source": "entry"
+});
\ No newline at end of file
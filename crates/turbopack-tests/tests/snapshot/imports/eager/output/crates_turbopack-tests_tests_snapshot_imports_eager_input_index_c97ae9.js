@@ -0,0 +1,6 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/crates_turbopack-tests_tests_snapshot_imports_eager_input_index_c97ae9.js",
+    {},
+    {"otherChunks":["output/crates_turbopack-tests_tests_snapshot_imports_eager_input_4400f5._.js"],"runtimeModuleIds":["[project]/crates/turbopack-tests/tests/snapshot/imports/eager/input/index.js [test] (ecmascript)"]}
+]);
+// Dummy runtime
\ No newline at end of file
@@ -0,0 +1,2 @@
+export default { value: "not ignored" };
+export const named = "not ignored";
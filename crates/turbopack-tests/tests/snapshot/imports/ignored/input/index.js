@@ -0,0 +1,4 @@
+import ignored, { named } from "./ignored.js";
+import unused from "./unused.js";
+
+console.log(ignored, named);
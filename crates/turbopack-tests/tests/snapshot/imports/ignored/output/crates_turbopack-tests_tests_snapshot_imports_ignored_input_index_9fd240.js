@@ -0,0 +1,11 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/crates_turbopack-tests_tests_snapshot_imports_ignored_input_index_9fd240.js",
+    {},
+]);
+(globalThis.TURBOPACK_CHUNK_LISTS = globalThis.TURBOPACK_CHUNK_LISTS || []).push({
+  "path": "output/crates_turbopack-tests_tests_snapshot_imports_ignored_input_index_9fd240.js",
+  "chunks": [
+    "output/crates_turbopack-tests_tests_snapshot_imports_ignored_input_index_872a34.js"
+  ],
+  "source": "entry"
+});
\ No newline at end of file
@@ -0,0 +1,6 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/crates_turbopack-tests_tests_snapshot_imports_ignored_input_index_bf4572.js",
+    {},
+    {"otherChunks":["output/crates_turbopack-tests_tests_snapshot_imports_ignored_input_index_872a34.js"],"runtimeModuleIds":["[project]/crates/turbopack-tests/tests/snapshot/imports/ignored/input/index.js [test] (ecmascript)"]}
+]);
+// Dummy runtime
\ No newline at end of file
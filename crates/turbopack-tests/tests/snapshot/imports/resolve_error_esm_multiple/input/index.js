@@ -0,0 +1,4 @@
+import a from "./does-not-exist-a.js";
+import b from "does-not-exist-b";
+
+console.log(a, b);
@@ -0,0 +1,11 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/a587c_tests_snapshot_imports_resolve_error_esm_multiple_input_index_1c0402.js",
+    {},
+]);
+(globalThis.TURBOPACK_CHUNK_LISTS = globalThis.TURBOPACK_CHUNK_LISTS || []).push({
+  "path": "output/a587c_tests_snapshot_imports_resolve_error_esm_multiple_input_index_1c0402.js",
+  "chunks": [
+    "output/a587c_tests_snapshot_imports_resolve_error_esm_multiple_input_index_cb98a1.js"
+  ],
+  "source": "entry"
+});
\ No newline at end of file
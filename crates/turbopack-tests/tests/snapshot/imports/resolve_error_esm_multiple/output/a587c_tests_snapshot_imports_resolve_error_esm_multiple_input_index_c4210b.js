@@ -0,0 +1,6 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/a587c_tests_snapshot_imports_resolve_error_esm_multiple_input_index_c4210b.js",
+    {},
+    {"otherChunks":["output/a587c_tests_snapshot_imports_resolve_error_esm_multiple_input_index_cb98a1.js"],"runtimeModuleIds":["[project]/crates/turbopack-tests/tests/snapshot/imports/resolve_error_esm_multiple/input/index.js [test] (ecmascript)"]}
+]);
+// Dummy runtime
\ No newline at end of file
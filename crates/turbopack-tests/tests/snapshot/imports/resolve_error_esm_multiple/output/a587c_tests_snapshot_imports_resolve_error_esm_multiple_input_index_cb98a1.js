@@ -0,0 +1,25 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push(["output/a587c_tests_snapshot_imports_resolve_error_esm_multiple_input_index_cb98a1.js", {
+
+"[project]/crates/turbopack-tests/tests/snapshot/imports/resolve_error_esm_multiple/input/index.js [test] (ecmascript)": (({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname, k: __turbopack_refresh__ }) => (() => {
+"use strict";
+
+__turbopack_esm__({});
+(()=>{
+    const e = new Error("Cannot find module './does-not-exist-a.js' imported from 'crates/turbopack-tests/tests/snapshot/imports/resolve_error_esm_multiple/input/index.js'");
+    e.code = 'MODULE_NOT_FOUND';
+    throw e;
+})();
+(()=>{
+    const e = new Error("Cannot find module 'does-not-exist-b' imported from 'crates/turbopack-tests/tests/snapshot/imports/resolve_error_esm_multiple/input/index.js'");
+    e.code = 'MODULE_NOT_FOUND';
+    throw e;
+})();
+"__TURBOPACK__ecmascript__hoisting__location__";
+;
+;
+console.log(a, b);
+
+})()),
+}]);
+
+//# sourceMappingURL=a587c_tests_snapshot_imports_resolve_error_esm_multiple_input_index_cb98a1.js.map
\ No newline at end of file
@@ -0,0 +1 @@
+export const excluded = "excluded";
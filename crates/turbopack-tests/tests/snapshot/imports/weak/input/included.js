@@ -0,0 +1 @@
+export const included = "included";
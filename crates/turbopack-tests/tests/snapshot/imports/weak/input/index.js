@@ -0,0 +1,7 @@
+"TURBOPACK { chunking-type: weak }";
+import * as excluded from "./excluded.js";
+"TURBOPACK { chunking-type: weak }";
+import { included } from "./included.js";
+import "./included.js";
+
+console.log(excluded, included);
@@ -0,0 +1,30 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push(["output/crates_turbopack-tests_tests_snapshot_imports_weak_input_d93e15._.js", {
+
+"[project]/crates/turbopack-tests/tests/snapshot/imports/weak/input/included.js [test] (ecmascript)": (({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname, k: __turbopack_refresh__ }) => (() => {
+"use strict";
+
+__turbopack_esm__({
+    "included": ()=>included
+});
+const included = "included";
+
+})()),
+"[project]/crates/turbopack-tests/tests/snapshot/imports/weak/input/index.js [test] (ecmascript)": (({ r: __turbopack_require__, f: __turbopack_require_context__, i: __turbopack_import__, I: __turbopack_import_namespace__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, N: __turbopack_reexport_namespace__, p: __turbopack_lookup__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, g: global, __dirname, k: __turbopack_refresh__ }) => (() => {
+"use strict";
+
+__turbopack_esm__({});
+"TURBOPACK { chunking-type: weak }";
+var __TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$imports$2f$weak$2f$input$2f$excluded$2e$js__$5b$test$5d$__$28$ecmascript$29$__ = __turbopack_modules__["[project]/crates/turbopack-tests/tests/snapshot/imports/weak/input/excluded.js [test] (ecmascript)"] ? Object.freeze(__turbopack_import_namespace__("[project]/crates/turbopack-tests/tests/snapshot/imports/weak/input/excluded.js [test] (ecmascript)")) : ("TURBOPACK weak import, the module isn't included by another import", undefined);
+var __TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$imports$2f$weak$2f$input$2f$included$2e$js__$5b$test$5d$__$28$ecmascript$29$__ = __turbopack_modules__["[project]/crates/turbopack-tests/tests/snapshot/imports/weak/input/included.js [test] (ecmascript)"] ? __turbopack_import__("[project]/crates/turbopack-tests/tests/snapshot/imports/weak/input/included.js [test] (ecmascript)") : ("TURBOPACK weak import, the module isn't included by another import", undefined);
+var __TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$imports$2f$weak$2f$input$2f$included$2e$js__$5b$test$5d$__$28$ecmascript$29$__ = __turbopack_import__("[project]/crates/turbopack-tests/tests/snapshot/imports/weak/input/included.js [test] (ecmascript)");
+"__TURBOPACK__ecmascript__hoisting__location__";
+;
+"TURBOPACK { chunking-type: weak }";
+;
+;
+console.log(__TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$imports$2f$weak$2f$input$2f$excluded$2e$js__$5b$test$5d$__$28$ecmascript$29$__, __TURBOPACK__imported__module__$5b$project$5d2f$crates$2f$turbopack$2d$tests$2f$tests$2f$snapshot$2f$imports$2f$weak$2f$input$2f$included$2e$js__$5b$test$5d$__$28$ecmascript$29$__["included"]);
+
+})()),
+}]);
+
+//# sourceMappingURL=crates_turbopack-tests_tests_snapshot_imports_weak_input_d93e15._.js.map
\ No newline at end of file
@@ -0,0 +1,6 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/crates_turbopack-tests_tests_snapshot_imports_weak_input_index_03ca73.js",
+    {},
+    {"otherChunks":["output/crates_turbopack-tests_tests_snapshot_imports_weak_input_d93e15._.js"],"runtimeModuleIds":["[project]/crates/turbopack-tests/tests/snapshot/imports/weak/input/index.js [test] (ecmascript)"]}
+]);
+// Dummy runtime
\ No newline at end of file
@@ -0,0 +1,11 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
+    "output/crates_turbopack-tests_tests_snapshot_imports_weak_input_index_2b5dc9.js",
+    {},
+]);
+(globalThis.TURBOPACK_CHUNK_LISTS = globalThis.TURBOPACK_CHUNK_LISTS || []).push({
+  "path": "output/crates_turbopack-tests_tests_snapshot_imports_weak_input_index_2b5dc9.js",
+  "chunks": [
+    "output/crates_turbopack-tests_tests_snapshot_imports_weak_input_d93e15._.js"
+  ],
+  "source": "entry"
+});
\ No newline at end of file
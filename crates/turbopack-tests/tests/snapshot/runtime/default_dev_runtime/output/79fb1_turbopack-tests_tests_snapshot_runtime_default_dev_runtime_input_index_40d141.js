@@ -80,6 +80,19 @@ function ensureDynamicExports(module, exports) {
         module[REEXPORTED_OBJECTS].push(object);
     }
 }
+/**
+ * Copies the own enumerable exports of a star re-exported namespace, except
+ * for `default` and the names the module declares itself.
+ */ function reexportNamespace(exports, object, exclude) {
+    if (typeof object !== "object" || object === null) return;
+    for (const key of Object.keys(object)){
+        if (key === "default" || exclude.includes(key)) continue;
+        defineProp(exports, key, {
+            get: createGetter(object, key),
+            enumerable: true
+        });
+    }
+}
 /**
  * Access one entry from a mapping from name to functor.
  */ function moduleLookup(map, name, returnPromise = false) {
@@ -144,6 +157,33 @@ function esmImport(sourceModule, id) {
     const raw = module.exports;
     return module.namespaceObject = interopEsm(raw, {}, raw && raw.__esModule);
 }
+/**
+ * Imports the namespace object of a module for a namespace import
+ * (`import * as ns from "..."`). It's the same object `esmImport` returns,
+ * but namespace objects set with `exportNamespace` don't have a
+ * `Symbol.toStringTag` of "Module" yet.
+ */ function esmImportNamespace(sourceModule, id) {
+    const namespace = esmImport(sourceModule, id);
+    // The namespace object of an async module is a promise, which the async
+    // dependency handler replaces with its exports.
+    if (toStringTag && typeof namespace === "object" && namespace !== null && !(namespace instanceof Promise) && Object.isExtensible(namespace)) {
+        defineProp(namespace, toStringTag, {
+            value: "Module"
+        });
+    }
+    return namespace;
+}
+/**
+ * Reads the export `name` of a module for a live binding of a named import.
+ * It's read on every access, so reassignments of the export in the exporting
+ * module are observable.
+ */ function esmImportBinding(sourceModule, id, name) {
+    const namespace = esmImport(sourceModule, id);
+    // The namespace object of an async module is a promise, which carries its
+    // exports.
+    const exports = isAsyncModuleExt(namespace) ? namespace[turbopackExports] : namespace;
+    return exports[name];
+}
 // Add a simple runtime require so that environments without one can still pass
 // `typeof require` CommonJS checks so that exports are correctly registered.
 const runtimeRequire = typeof require === "function" ? require : function require1() {
@@ -210,6 +250,16 @@ function resolveQueue(queue) {
         queue.forEach((fn)=>fn.queueCount-- ? fn.queueCount++ : fn());
     }
 }
+/**
+ * Warns when the async dependencies of the module `id` haven't resolved
+ * `timeout` milliseconds after `promise` was created. This helps diagnosing
+ * async modules that hang, e.g. on a top-level await that never settles.
+ */ function warnOnSlowAsyncDependencies(id, promise, timeout) {
+    const timer = setTimeout(()=>{
+        console.warn(`The async dependencies of module ${id} haven't resolved after ${timeout}ms.`);
+    }, timeout);
+    promise.then(()=>clearTimeout(timer));
+}
 function wrapDeps(deps) {
     return deps.map((dep)=>{
         if (dep !== null && typeof dep === "object") {
@@ -256,7 +306,7 @@ function asyncModule(module, body, hasAwait) {
         }
     });
     module.exports = module.namespaceObject = promise;
-    function handleAsyncDependencies(deps) {
+    function handleAsyncDependencies(deps, options) {
         const currentDeps = wrapDeps(deps);
         const getResult = ()=>currentDeps.map((d)=>{
                 if (d[turbopackError]) throw d[turbopackError];
@@ -276,7 +326,11 @@ function asyncModule(module, body, hasAwait) {
             }
         }
         currentDeps.map((dep)=>dep[turbopackQueues](fnQueue));
-        return fn.queueCount ? promise : getResult();
+        if (!fn.queueCount) return getResult();
+        if (options?.timeout != null) {
+            warnOnSlowAsyncDependencies(module.id, promise, options.timeout);
+        }
+        return promise;
     }
     function asyncResult(err) {
         if (err) {
@@ -521,8 +575,11 @@ function instantiateModule(id, source) {
                 t: runtimeRequire,
                 f: requireContext.bind(null, module),
                 i: esmImport.bind(null, module),
+                I: esmImportNamespace.bind(null, module),
+                E: esmImportBinding.bind(null, module),
                 s: esmExport.bind(null, module, module.exports),
                 j: dynamicExport.bind(null, module, module.exports),
+                N: reexportNamespace.bind(null, module.exports),
                 p: moduleLookup,
                 v: exportValue.bind(null, module),
                 n: exportNamespace.bind(null, module),
@@ -77,6 +77,8 @@ impl ModuleOptions {
             ref rules,
             esm_url_rewrite_behavior,
             import_externals,
+            strict_import_annotations,
+            browser_import_map,
             use_lightningcss,
             ..
         } = *module_options_context.await?;
@@ -111,6 +113,8 @@ impl ModuleOptions {
             tree_shaking_mode,
             url_rewrite_behavior: esm_url_rewrite_behavior,
             import_externals,
+            strict_import_annotations,
+            import_map: browser_import_map,
             ..Default::default()
         };
 
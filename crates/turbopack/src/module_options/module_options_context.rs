@@ -1,7 +1,10 @@
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use turbo_tasks::{trace::TraceRawVcs, ValueDefault, Vc};
-use turbopack_core::{environment::Environment, resolve::options::ImportMapping};
+use turbopack_core::{
+    environment::Environment,
+    resolve::options::{ImportMap, ImportMapping},
+};
 use turbopack_ecmascript::{references::esm::UrlRewriteBehavior, TreeShakingMode};
 use turbopack_node::{
     execution_context::ExecutionContext,
@@ -152,6 +155,13 @@ pub struct ModuleOptionsContext {
     /// References to externals from ESM imports should use `import()` and make
     /// async modules.
     pub import_externals: bool,
+    /// Unknown `chunking-type` import annotations fail the build instead of
+    /// emitting a warning, e.g. to enforce valid annotations in CI.
+    pub strict_import_annotations: bool,
+    /// A browser-style import map ESM imports are resolved with, created with
+    /// [turbopack_ecmascript::resolve::browser_import_map]. Its entries take
+    /// precedence over the import map of the resolve options.
+    pub browser_import_map: Option<Vc<ImportMap>>,
 
     pub use_lightningcss: bool,
 }
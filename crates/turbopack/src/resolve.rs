@@ -1,13 +1,16 @@
 use anyhow::Result;
 use turbo_tasks::Vc;
 use turbo_tasks_fs::{FileSystem, FileSystemPath};
-use turbopack_core::resolve::{
-    find_context_file,
-    options::{
-        ConditionValue, ImportMap, ImportMapping, ResolutionConditions, ResolveInPackage,
-        ResolveIntoPackage, ResolveModules, ResolveOptions,
+use turbopack_core::{
+    environment::{EDGE_BUILTIN_MODULES, NODE_BUILTIN_MODULES},
+    resolve::{
+        find_context_file,
+        options::{
+            ConditionValue, ImportMap, ImportMapping, ResolutionConditions, ResolveInPackage,
+            ResolveIntoPackage, ResolveModules, ResolveOptions,
+        },
+        AliasMap, AliasPattern, FindContextFileResult,
     },
-    AliasMap, AliasPattern, FindContextFileResult,
 };
 use turbopack_ecmascript::typescript::resolve::{
     apply_tsconfig_resolve_options, tsconfig, tsconfig_resolve_options,
@@ -15,61 +18,8 @@ use turbopack_ecmascript::typescript::resolve::{
 
 use crate::resolve_options_context::ResolveOptionsContext;
 
-const NODE_EXTERNALS: [&str; 51] = [
-    "assert",
-    "async_hooks",
-    "buffer",
-    "child_process",
-    "cluster",
-    "console",
-    "constants",
-    "crypto",
-    "dgram",
-    "diagnostics_channel",
-    "dns",
-    "dns/promises",
-    "domain",
-    "events",
-    "fs",
-    "fs/promises",
-    "http",
-    "http2",
-    "https",
-    "inspector",
-    "module",
-    "net",
-    "os",
-    "path",
-    "path/posix",
-    "path/win32",
-    "perf_hooks",
-    "process",
-    "punycode",
-    "querystring",
-    "readline",
-    "repl",
-    "stream",
-    "stream/promises",
-    "stream/web",
-    "string_decoder",
-    "sys",
-    "timers",
-    "timers/promises",
-    "tls",
-    "trace_events",
-    "tty",
-    "url",
-    "util",
-    "util/types",
-    "v8",
-    "vm",
-    "wasi",
-    "worker_threads",
-    "zlib",
-    "pnpapi",
-];
-
-const EDGE_NODE_EXTERNALS: [&str; 5] = ["buffer", "events", "assert", "util", "async_hooks"];
+/// Yarn's PnP API is provided by the runtime like a Node.js builtin.
+const PNP_API: &str = "pnpapi";
 
 #[turbo_tasks::function]
 async fn base_resolve_options(
@@ -91,7 +41,7 @@ async fn base_resolve_options(
         opt.enable_node_externals
     };
     if node_externals {
-        for req in NODE_EXTERNALS {
+        for req in NODE_BUILTIN_MODULES.into_iter().chain([PNP_API]) {
             direct_mappings.insert(
                 AliasPattern::exact(req),
                 ImportMapping::External(None).into(),
@@ -103,7 +53,7 @@ async fn base_resolve_options(
         }
     }
     if opt.enable_edge_node_externals {
-        for req in EDGE_NODE_EXTERNALS {
+        for req in EDGE_BUILTIN_MODULES {
             direct_mappings.insert(
                 AliasPattern::exact(req),
                 ImportMapping::External(Some(format!("node:{req}"))).into(),